@@ -28,6 +28,7 @@ mod feature_id {
     pub const TEXTLAYOUT: &str = "textlayout";
     pub const WEBPE: &str = "webpe";
     pub const WEBPD: &str = "webpd";
+    pub const LOG: &str = "log";
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -170,6 +171,7 @@ impl Default for BuildConfiguration {
                 text_layout: cfg!(feature = "textlayout"),
                 webp_encode: cfg!(feature = "webp-encode"),
                 webp_decode: cfg!(feature = "webp-decode"),
+                log: cfg!(feature = "log") && supports_debugf_handler(&cargo::target()),
                 dng: false,
                 particles: false,
             },
@@ -289,6 +291,9 @@ pub struct Features {
     /// Support the decoding of the WEBP image format to bitmap data.
     pub webp_decode: bool,
 
+    /// Pass the messages Skia prints with `SkDebugf()` to a handler instead of `stderr`.
+    pub log: bool,
+
     /// Support DNG file format (currently unsupported because of build errors).
     pub dng: bool,
 
@@ -326,11 +331,35 @@ impl Features {
         if self.webp_decode {
             feature_ids.push(feature_id::WEBPD);
         }
+        if self.log {
+            feature_ids.push(feature_id::LOG);
+        }
 
         feature_ids
     }
 }
 
+/// Returns `true` if skia-bindings can replace `SkDebugf()` on `target`.
+///
+/// The replacement relies on `SkDebug_stdio.cpp` not being pulled from the Skia library, because
+/// skia-bindings already defines the symbol. This is only tested on Linux and macOS. Android
+/// (logcat) and Windows (OutputDebugString) use different ports, which must not be replaced
+/// silently.
+fn supports_debugf_handler(target: &cargo::Target) -> bool {
+    let supported = match target.as_strs() {
+        // Android targets have the system "android" or "androideabi".
+        (_, _, "linux", _) | (_, "apple", "darwin", _) => true,
+        _ => false,
+    };
+    if cfg!(feature = "log") && !supported {
+        cargo::warning(format!(
+            "The feature `log` is not supported on {}, Skia's messages are not forwarded.",
+            target
+        ));
+    }
+    supported
+}
+
 /// This is the final, low level build configuration.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct FinalBuildConfiguration {
@@ -612,6 +641,9 @@ impl FinalBuildConfiguration {
                 sources.extend(vec!["src/shaper.cpp".into(), "src/paragraph.cpp".into()]);
             }
             sources.push("src/svg.cpp".into());
+            if features.log {
+                sources.push("src/debugf.cpp".into());
+            }
             sources
         };
 
@@ -759,6 +791,11 @@ impl BinariesConfiguration {
         built_libraries.insert(lib::SKIA.into());
         built_libraries.insert(lib::SKIA_BINDINGS.into());
 
+        // skia-bindings is linked first, so that its definitions take precedence over the ones
+        // in the Skia libraries, for example `SkDebugf()` with the `log` feature.
+        let mut built_libraries: Vec<String> = built_libraries.into_iter().collect();
+        built_libraries.sort_by_key(|lib| lib != lib::SKIA_BINDINGS);
+
         BinariesConfiguration {
             feature_ids: feature_ids.into_iter().map(|f| f.to_string()).collect(),
             output_directory,
@@ -766,7 +803,7 @@ impl BinariesConfiguration {
                 .into_iter()
                 .map(|lib| lib.to_string())
                 .collect(),
-            built_libraries: built_libraries.into_iter().collect(),
+            built_libraries,
             additional_files,
             skia_debug: build.skia_debug,
         }
@@ -29,10 +29,12 @@
 #include "include/core/SkImage.h"
 #include "include/core/SkImageEncoder.h"
 #include "include/core/SkImageFilter.h"
+#include "src/core/SkImageFilter_Base.h"
 #include "include/core/SkImageGenerator.h"
 #include "include/core/SkImageInfo.h"
 #include "include/core/SkM44.h"
 #include "include/core/SkMatrix44.h"
+#include "src/core/SkMatrixPriv.h"
 #include "include/core/SkMaskFilter.h"
 #include "include/core/SkPaint.h"
 #include "include/core/SkPath.h"
@@ -55,6 +57,7 @@
 #include "include/core/SkSurfaceCharacterization.h"
 #include "include/core/SkSwizzle.h"
 #include "include/core/SkTextBlob.h"
+#include "src/core/SkTextBlobPriv.h"
 #include "include/core/SkTypeface.h"
 #include "include/core/SkTypes.h"
 #include "include/core/SkVertices.h"
@@ -171,6 +174,18 @@ extern "C" SkEncodedImageFormat C_SkCodec_getEncodedFormat(const SkCodec* self)
     return self->getEncodedFormat();
 }
 
+extern "C" SkCodec::Result C_SkCodec_startScanlineDecode(SkCodec* self, const SkImageInfo* info, const SkCodec::Options* options) {
+    return self->startScanlineDecode(*info, options);
+}
+
+extern "C" int C_SkCodec_getScanlines(SkCodec* self, void* dst, int countLines, size_t rowBytes) {
+    return self->getScanlines(dst, countLines, rowBytes);
+}
+
+extern "C" bool C_SkCodec_isScanlineOrderTopDown(const SkCodec* self) {
+    return self->getScanlineOrder() == SkCodec::kTopDown_SkScanlineOrder;
+}
+
 //
 // codec/SkEncodedOrigin.h
 //
@@ -201,6 +216,13 @@ extern "C" SkSurface* C_SkSurface_MakeRasterDirect(const SkImageInfo* imageInfo,
     return SkSurface::MakeRasterDirect(*imageInfo, pixels, rowBytes, surfaceProps).release();
 }
 
+extern "C" SkSurface* C_SkSurface_MakeRasterDirectReleaseProc(
+        const SkImageInfo* imageInfo, void* pixels, size_t rowBytes,
+        void (*releaseProc)(void* pixels, void* context), void* context,
+        const SkSurfaceProps* surfaceProps) {
+    return SkSurface::MakeRasterDirectReleaseProc(*imageInfo, pixels, rowBytes, releaseProc, context, surfaceProps).release();
+}
+
 extern "C" SkSurface* C_SkSurface_MakeRaster(const SkImageInfo* imageInfo, size_t rowBytes, const SkSurfaceProps* surfaceProps) {
     return SkSurface::MakeRaster(*imageInfo, rowBytes, surfaceProps).release();
 }
@@ -487,6 +509,10 @@ extern "C" void C_SkPaint_setImageFilter(SkPaint* self, SkImageFilter* imageFilt
     self->setImageFilter(sp(imageFilter));
 }
 
+extern "C" SkScalar C_SkPaint_resScaleForStroking(const SkMatrix* matrix) {
+    return SkMatrixPriv::ComputeResScaleForStroking(*matrix);
+}
+
 //
 // core/SkPath.h
 //
@@ -814,6 +840,10 @@ extern "C" void C_SkImageInfo_reset(SkImageInfo* self) {
 
 extern "C" void C_SkColorSpace_Types(SkColorSpacePrimaries *) {}
 
+extern "C" bool C_SkColorSpacePrimaries_toXYZD50(const SkColorSpacePrimaries* self, skcms_Matrix3x3* toXYZD50) {
+    return self->toXYZD50(toXYZD50);
+}
+
 extern "C" void C_SkColorSpace_ref(const SkColorSpace* self) {
     self->ref();
 }
@@ -834,6 +864,10 @@ extern "C" SkColorSpace* C_SkColorSpace_MakeSRGBLinear() {
     return SkColorSpace::MakeSRGBLinear().release();
 }
 
+extern "C" SkColorSpace* C_SkColorSpace_MakeRGB(const skcms_TransferFunction* transferFn, const skcms_Matrix3x3* toXYZ) {
+    return SkColorSpace::MakeRGB(*transferFn, *toXYZ).release();
+}
+
 extern "C" SkColorSpace* C_SkColorSpace_makeLinearGamma(const SkColorSpace* self) {
     return self->makeLinearGamma().release();
 }
@@ -1043,6 +1077,20 @@ extern "C" void C_SkPicture_playback(const SkPicture* self, SkCanvas* canvas) {
     self->playback(canvas);
 }
 
+extern "C" void C_SkPicture_playbackWithAbort(const SkPicture* self, SkCanvas* canvas, void* context, bool (*abort)(void* context)) {
+    struct Callback : public SkPicture::AbortCallback {
+        void* context;
+        bool (*abortFn)(void* context);
+
+        bool abort() override {
+            return abortFn(context);
+        }
+    } callback;
+    callback.context = context;
+    callback.abortFn = abort;
+    self->playback(canvas, &callback);
+}
+
 extern "C" SkRect C_SkPicture_cullRect(const SkPicture* self) {
     return self->cullRect();
 }
@@ -1305,6 +1353,35 @@ extern "C" void C_SkTextBlob_Iter_destruct(SkTextBlob::Iter* self) {
     self->~Iter();
 }
 
+extern "C" void C_SkTextBlobRunIterator_construct(SkTextBlobRunIterator* uninitialized, const SkTextBlob* blob) {
+    new(uninitialized) SkTextBlobRunIterator(blob);
+}
+
+extern "C" void C_SkTextBlobRunIterator_destruct(SkTextBlobRunIterator* self) {
+    self->~SkTextBlobRunIterator();
+}
+
+extern "C" bool C_SkTextBlobRunIterator_next(
+        SkTextBlobRunIterator* self,
+        SkFont* font,
+        const SkGlyphID** glyphs,
+        uint32_t* glyphCount,
+        const SkScalar** pos,
+        SkPoint* offset,
+        uint8_t* positioning) {
+    if (self->done()) {
+        return false;
+    }
+    *font = self->font();
+    *glyphs = self->glyphs();
+    *glyphCount = self->glyphCount();
+    *pos = self->pos();
+    *offset = self->offset();
+    *positioning = self->positioning();
+    self->next();
+    return true;
+}
+
 extern "C" void C_SkTextBlobBuilder_destruct(SkTextBlobBuilder* self) {
     self->~SkTextBlobBuilder();
 }
@@ -1421,6 +1498,10 @@ extern "C" void C_SkFont_ConstructFromTypefaceWithSizeScaleAndSkew(SkFont* unini
     new(uninitialized) SkFont(sp(typeface), size, scaleX, skewX);
 }
 
+extern "C" void C_SkFont_CopyConstruct(SkFont* uninitialized, const SkFont* font) {
+    new(uninitialized) SkFont(*font);
+}
+
 extern "C" bool C_SkFont_Equals(const SkFont* self, const SkFont* other) {
     return *self == *other;
 }
@@ -1809,6 +1890,42 @@ extern "C" SkRect C_SkDrawable_getBounds(SkDrawable* self) {
     return self->getBounds();
 }
 
+class RustDrawable : public SkDrawable {
+public:
+    struct Param {
+        void* drawable;
+        void (*drop)(void* drawable);
+        void (*draw)(void* drawable, SkCanvas* canvas);
+        void (*bounds)(void* drawable, SkRect* bounds);
+    };
+
+    explicit RustDrawable(const Param& param)
+    : _param(param) {
+    }
+
+    ~RustDrawable() override {
+        _param.drop(_param.drawable);
+    }
+
+protected:
+    void onDraw(SkCanvas* canvas) override {
+        _param.draw(_param.drawable, canvas);
+    }
+
+    SkRect onGetBounds() override {
+        SkRect bounds = SkRect::MakeEmpty();
+        _param.bounds(_param.drawable, &bounds);
+        return bounds;
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkDrawable* C_RustDrawable_new(const RustDrawable::Param* param) {
+    return new RustDrawable(*param);
+}
+
 //
 // SkImageFilter
 //
@@ -1850,6 +1967,25 @@ extern "C" const SkImageFilter* C_SkImageFilter_getInput(const SkImageFilter* se
     return self->getInput(i);
 }
 
+namespace {
+    // The crop rect accessors of SkImageFilter_Base are protected, but can be accessed through
+    // member pointers that are formed in a derived class.
+    struct ImageFilterBaseAccess : SkImageFilter_Base {
+        static bool cropRect(const SkImageFilter* filter, SkImageFilter::CropRect* cropRect) {
+            auto base = as_IFB(filter);
+            if (!(base->*(&ImageFilterBaseAccess::cropRectIsSet))()) {
+                return false;
+            }
+            *cropRect = (base->*(&ImageFilterBaseAccess::getCropRect))();
+            return true;
+        }
+    };
+}
+
+extern "C" bool C_SkImageFilter_cropRect(const SkImageFilter* self, SkImageFilter::CropRect* cropRect) {
+    return ImageFilterBaseAccess::cropRect(self, cropRect);
+}
+
 //
 // core/SkImageGenerator.h
 //
@@ -1862,6 +1998,40 @@ extern "C" SkData *C_SkImageGenerator_refEncodedData(SkImageGenerator *self) {
     return self->refEncodedData().release();
 }
 
+class RustImageGenerator : public SkImageGenerator {
+public:
+    struct Param {
+        void* generator;
+        void (*drop)(void* generator);
+        bool (*getPixels)(void* generator, const SkImageInfo* info, void* pixels, size_t rowBytes);
+        SkData* (*refEncodedData)(void* generator);
+    };
+
+    RustImageGenerator(const SkImageInfo& info, const Param& param)
+    : SkImageGenerator(info), _param(param) {
+    }
+
+    ~RustImageGenerator() override {
+        _param.drop(_param.generator);
+    }
+
+protected:
+    sk_sp<SkData> onRefEncodedData() override {
+        return sk_sp<SkData>(_param.refEncodedData(_param.generator));
+    }
+
+    bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+        return _param.getPixels(_param.generator, &info, pixels, rowBytes);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkImageGenerator* C_RustImageGenerator_new(const SkImageInfo* info, const RustImageGenerator::Param* param) {
+    return new RustImageGenerator(*info, *param);
+}
+
 extern "C" SkImageGenerator *C_SkImageGenerator_MakeFromEncoded(SkData *data) {
     return SkImageGenerator::MakeFromEncoded(sp(data)).release();
 }
@@ -3045,4 +3215,12 @@ extern "C" bool C_SkSVGDOM_unique(const SkSVGDOM* self) {
     return self->unique();
 }
 
+extern "C" void C_SkSVGDOM_containerSize(const SkSVGDOM* self, SkSize* size) {
+    *size = self->containerSize();
+}
+
+extern "C" void C_SkSVGDOM_setContainerSize(SkSVGDOM* self, const SkSize* size) {
+    self->setContainerSize(*size);
+}
+
 #endif // SK_XML
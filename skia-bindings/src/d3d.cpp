@@ -6,6 +6,7 @@
     #define SK_DIRECT3D
 #endif
 
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrBackendSurface.h"
 #include "include/gpu/GrContext.h"
 #include "include/gpu/GrDirectContext.h"
@@ -35,6 +36,20 @@ extern "C" void C_GrBackendRenderTarget_ConstructD3D(GrBackendRenderTarget* unin
     new(uninitialized)GrBackendRenderTarget(width, height, *resourceInfo);
 }
 
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_ConstructD3D(GrBackendSemaphore* uninitialized, const GrD3DFenceInfo* fenceInfo) {
+    new(uninitialized) GrBackendSemaphore();
+    uninitialized->initDirect3D(*fenceInfo);
+}
+
+extern "C" bool C_GrBackendSemaphore_getD3DFenceInfo(const GrBackendSemaphore* self, GrD3DFenceInfo* uninitialized) {
+    new(uninitialized) GrD3DFenceInfo();
+    return self->getD3DFenceInfo(uninitialized);
+}
+
 //
 // gpu/GrContext.h
 //
@@ -0,0 +1,38 @@
+#include "include/core/SkTypes.h"
+
+#include <atomic>
+#include <cstdarg>
+#include <cstdio>
+#include <string>
+
+typedef void (*SkDebugfHandler)(const char* message);
+
+static std::atomic<SkDebugfHandler> debugfHandler(nullptr);
+
+extern "C" void C_SkDebugf_setHandler(SkDebugfHandler handler) {
+    debugfHandler.store(handler);
+}
+
+// Replaces the platform implementation of SkDebugf() in the Skia library, which is not linked
+// because skia-bindings is linked first.
+void SkDebugf(const char format[], ...) {
+    va_list args;
+    va_start(args, format);
+    auto handler = debugfHandler.load();
+    if (!handler) {
+        vfprintf(stderr, format, args);
+        va_end(args);
+        return;
+    }
+
+    va_list argsCopy;
+    va_copy(argsCopy, args);
+    auto size = vsnprintf(nullptr, 0, format, args);
+    va_end(args);
+    if (size > 0) {
+        std::string message(size, '\0');
+        vsnprintf(&message[0], size + 1, format, argsCopy);
+        handler(message.c_str());
+    }
+    va_end(argsCopy);
+}
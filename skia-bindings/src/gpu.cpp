@@ -1,11 +1,18 @@
 #include "bindings.h"
+#include "include/gpu/GrContextOptions.h"
 #include "include/gpu/GrDirectContext.h"
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrBackendDrawableInfo.h"
+#include "include/gpu/mock/GrMockTypes.h"
 #include "include/core/SkCanvas.h"
 #include "include/core/SkDrawable.h"
 #include "include/core/SkSurface.h"
 #include "include/core/SkSurfaceCharacterization.h"
 #include "include/core/SkImageGenerator.h"
+#include "src/core/SkImagePriv.h"
+#include "src/gpu/GrCaps.h"
+#include "src/gpu/GrRecordingContextPriv.h"
+#include "src/gpu/GrShaderCaps.h"
 
 //
 // core/SkSurface.h
@@ -72,6 +79,14 @@ extern "C" SkSurface* C_SkSurface_MakeRenderTarget2(
             budgeted).release();
 }
 
+extern "C" bool C_SkSurface_wait(
+        SkSurface* self,
+        int numSemaphores,
+        const GrBackendSemaphore* waitSemaphores,
+        bool deleteSemaphoresAfterWait) {
+    return self->wait(numSemaphores, waitSemaphores, deleteSemaphoresAfterWait);
+}
+
 extern "C" void C_SkSurface_getBackendTexture(
         SkSurface* self,
         SkSurface::BackendHandleAccess handleAccess,
@@ -173,6 +188,26 @@ extern "C" void C_GrBackendFormat_makeTexture2D(const GrBackendFormat* self, GrB
     *format = self->makeTexture2D();
 }
 
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_Construct(GrBackendSemaphore* uninitialized) {
+    new(uninitialized) GrBackendSemaphore();
+}
+
+extern "C" void C_GrBackendSemaphore_CopyConstruct(GrBackendSemaphore* uninitialized, const GrBackendSemaphore* semaphore) {
+    new(uninitialized) GrBackendSemaphore(*semaphore);
+}
+
+extern "C" void C_GrBackendSemaphore_destruct(GrBackendSemaphore* self) {
+    self->~GrBackendSemaphore();
+}
+
+extern "C" bool C_GrBackendSemaphore_isInitialized(const GrBackendSemaphore* self) {
+    return self->isInitialized();
+}
+
 //
 // gpu/GrBackendSurfaceMutableState.h
 //
@@ -212,6 +247,11 @@ extern "C" bool C_GrRecordingContext_abandoned(GrRecordingContext* self) {
     return self->abandoned();
 }
 
+// GrContext_Base.h
+extern "C" uint32_t C_GrRecordingContext_contextID(const GrRecordingContext* self) {
+    return self->priv().contextID();
+}
+
 //
 // gpu/GrDirectContext.h
 //
@@ -228,10 +268,65 @@ extern "C" void C_GrContext_compressedBackendFormat(const GrDirectContext* self,
     *result = self->compressedBackendFormat(compression);
 }
 
+extern "C" GrDirectContext* C_GrDirectContext_MakeMock(const GrContextOptions* options) {
+    if (options) {
+        return GrDirectContext::MakeMock(nullptr, *options).release();
+    }
+    return GrDirectContext::MakeMock(nullptr).release();
+}
+
+extern "C" bool C_GrDirectContext_wait(
+        GrDirectContext* self,
+        int numSemaphores,
+        const GrBackendSemaphore* waitSemaphores,
+        bool deleteSemaphoresAfterWait) {
+    return self->wait(numSemaphores, waitSemaphores, deleteSemaphoresAfterWait);
+}
+
+extern "C" bool C_GrDirectContext_abandoned(GrDirectContext* self) {
+    return self->abandoned();
+}
+
+extern "C" void C_GrDirectContext_abandonContext(GrDirectContext* self) {
+    self->abandonContext();
+}
+
+extern "C" void C_GrDirectContext_releaseResourcesAndAbandonContext(GrDirectContext* self) {
+    self->releaseResourcesAndAbandonContext();
+}
+
 extern "C" void C_GrContext_performDeferredCleanup(GrDirectContext* self, long msNotUsed) {
     self->performDeferredCleanup(std::chrono::milliseconds(msNotUsed));
 }
 
+extern "C" void C_GrDirectContext_freeGpuResources(GrDirectContext* self) {
+    self->freeGpuResources();
+}
+
+extern "C" bool C_GrDirectContext_precompileShader(GrDirectContext* self, const SkData* key, const SkData* data) {
+    return self->precompileShader(*key, *data);
+}
+
+extern "C" void C_GrDirectContext_purgeUnlockedResources(GrDirectContext* self, bool scratchResourcesOnly) {
+    self->purgeUnlockedResources(scratchResourcesOnly);
+}
+
+extern "C" void C_GrDirectContext_getResourceCacheUsage(const GrDirectContext* self, int* resourceCount, size_t* resourceBytes) {
+    self->getResourceCacheUsage(resourceCount, resourceBytes);
+}
+
+extern "C" size_t C_GrDirectContext_getResourceCachePurgeableBytes(const GrDirectContext* self) {
+    return self->getResourceCachePurgeableBytes();
+}
+
+extern "C" size_t C_GrDirectContext_getResourceCacheLimit(const GrDirectContext* self) {
+    return self->getResourceCacheLimit();
+}
+
+extern "C" void C_GrDirectContext_setResourceCacheLimit(GrDirectContext* self, size_t maxResourceBytes) {
+    self->setResourceCacheLimit(maxResourceBytes);
+}
+
 //
 // gpu/GrContextOptions.h
 //
@@ -240,10 +335,95 @@ extern "C" void C_GrContextOptions_Construct(GrContextOptions* unintialized) {
     new(unintialized) GrContextOptions();
 }
 
+class RustPersistentCache : public GrContextOptions::PersistentCache {
+public:
+    struct Param {
+        void* cache;
+        SkData* (*load)(void* cache, const SkData* key);
+        void (*store)(void* cache, const SkData* key, const SkData* data);
+    };
+
+    explicit RustPersistentCache(const Param& param)
+    : _param(param) {
+    }
+
+    sk_sp<SkData> load(const SkData& key) override {
+        return sk_sp<SkData>(_param.load(_param.cache, &key));
+    }
+
+    void store(const SkData& key, const SkData& data) override {
+        _param.store(_param.cache, &key, &data);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" GrContextOptions::PersistentCache* C_RustPersistentCache_new(const RustPersistentCache::Param* param) {
+    return new RustPersistentCache(*param);
+}
+
+extern "C" void C_GrContextOptions_PersistentCache_delete(GrContextOptions::PersistentCache* self) {
+    delete self;
+}
+
+//
+// gpu/GrDriverBugWorkarounds.h
+//
+
+extern "C" void C_GrDriverBugWorkarounds_Construct(GrDriverBugWorkarounds* uninitialized) {
+    new(uninitialized) GrDriverBugWorkarounds();
+}
+
+static const char* const DRIVER_BUG_WORKAROUND_NAMES[] = {
+#define GPU_OP(type, name) #name,
+    GPU_DRIVER_BUG_WORKAROUNDS(GPU_OP)
+#undef GPU_OP
+};
+
+extern "C" size_t C_GrDriverBugWorkarounds_namesCount() {
+    return SK_ARRAY_COUNT(DRIVER_BUG_WORKAROUND_NAMES);
+}
+
+extern "C" const char* C_GrDriverBugWorkarounds_name(size_t index) {
+    return DRIVER_BUG_WORKAROUND_NAMES[index];
+}
+
+extern "C" bool C_GrDriverBugWorkarounds_set(GrDriverBugWorkarounds* self, const char* name, size_t len, bool enabled) {
+    std::string n(name, len);
+#define GPU_OP(type, name_)      \
+    if (n == #name_) {           \
+        self->name_ = enabled;   \
+        return true;             \
+    }
+    GPU_DRIVER_BUG_WORKAROUNDS(GPU_OP)
+#undef GPU_OP
+    return false;
+}
+
 //
 // gpu/GrRecordingContext.h
 //
 
+struct GrShaderCapabilities {
+    bool shaderDerivativeSupport;
+    bool integerSupport;
+    bool floatIs32Bits;
+    bool halfIs32Bits;
+    bool flatInterpolationSupport;
+    bool dualSourceBlendingSupport;
+};
+
+extern "C" void C_GrRecordingContext_shaderCapabilities(GrRecordingContext* self, GrShaderCapabilities* capabilities) {
+    auto caps = self->priv().caps()->shaderCaps();
+    capabilities->shaderDerivativeSupport = caps->shaderDerivativeSupport();
+    capabilities->integerSupport = caps->integerSupport();
+    capabilities->floatIs32Bits = caps->floatIs32Bits();
+    capabilities->halfIs32Bits = caps->halfIs32Bits();
+    capabilities->flatInterpolationSupport = caps->flatInterpolationSupport();
+    capabilities->dualSourceBlendingSupport = caps->dualSourceBlendingSupport();
+}
+
 extern "C" bool C_GrRecordingContext_colorTypeSupportedAsSurface(const GrRecordingContext* self, SkColorType colorType) {
     return self->colorTypeSupportedAsSurface(colorType);
 }
@@ -319,6 +499,14 @@ extern "C" void C_SkImage_getBackendTexture(
     *result = self->getBackendTexture(flushPendingGrContextIO, origin);
 }
 
+extern "C" bool C_SkImage_pinAsTexture(const SkImage* self, GrDirectContext* context) {
+    return SkImage_pinAsTexture(self, context);
+}
+
+extern "C" void C_SkImage_unpinAsTexture(const SkImage* self, GrDirectContext* context) {
+    SkImage_unpinAsTexture(self, context);
+}
+
 extern "C" SkImage* C_SkImage_MakeFromTexture(
         GrRecordingContext* context,
         const GrBackendTexture* backendTexture,
@@ -13,6 +13,9 @@ use crate::{
 use std::ffi::CStr;
 
 impl SkBlendMode {
+    /// Returns the source and destination coefficients of the blend mode, so that the result is
+    /// `src * src_coeff + dst * dst_coeff`, or `None` if the blend mode can not be expressed
+    /// with coefficients, like the separable and non-separable advanced modes.
     pub fn as_coeff(self) -> Option<(SkBlendModeCoeff, SkBlendModeCoeff)> {
         let mut src = SkBlendModeCoeff::Zero;
         let mut dst = SkBlendModeCoeff::Zero;
@@ -23,6 +26,8 @@ impl SkBlendMode {
         }
     }
 
+    /// Returns the name of the blend mode, for example `"SrcOver"`, which can be shown in debug
+    /// user interfaces.
     pub fn name(self) -> &'static str {
         unsafe {
             let name_ptr = crate::SkBlendMode_Name(self);
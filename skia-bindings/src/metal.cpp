@@ -51,6 +51,11 @@ extern "C" void C_GrMtlTextureInfo_Construct(GrMtlTextureInfo* unintialized, con
     unintialized->fTexture = sk_cf_obj<const void*>(texture);
 }
 
+extern "C" void C_GrMtlTextureInfo_ConstructRetained(GrMtlTextureInfo* unintialized, const void* texture) {
+    new (unintialized) GrMtlTextureInfo();
+    unintialized->fTexture.retain(texture);
+}
+
 extern "C" void C_GrMtlTextureInfo_Destruct(GrMtlTextureInfo* self) {
     self->~GrMtlTextureInfo();
 }
@@ -15,6 +15,8 @@
 
 // m84: needs definition of SkFontData
 #include "src/core/SkFontDescriptor.h"
+#include "modules/skparagraph/src/ParagraphImpl.h"
+#include "modules/skparagraph/src/ParagraphBuilderImpl.h"
 
 using namespace skia::textlayout;
 
@@ -285,15 +287,69 @@ extern "C" {
         return self->lineNumber();
     }
 
+    void C_Paragraph_getText(const Paragraph* self, const char** text, size_t* len, TextDirection* direction) {
+        auto impl = static_cast<const ParagraphImpl*>(self);
+        auto t = impl->text();
+        *text = t.data();
+        *len = t.size();
+        *direction = impl->paragraphStyle().getTextDirection();
+    }
+
     void C_Paragraph_markDirty(Paragraph* self) {
         self->markDirty();
     }
+
+    // Lays out a copy of the paragraph without its max lines limit at the current width. The copy
+    // is rebuilt with a ParagraphBuilder by replaying the styled blocks and placeholders.
+    void C_Paragraph_unlimitedMetrics(Paragraph* self, SkScalar* height, size_t* lineNumber) {
+        auto impl = static_cast<ParagraphImpl*>(self);
+        ParagraphStyle style = impl->paragraphStyle();
+        style.setMaxLines(std::numeric_limits<size_t>::max());
+        style.setEllipsis(std::u16string());
+        auto builder = ParagraphBuilder::make(style, impl->fontCollection());
+        auto text = impl->text();
+        auto placeholders = impl->placeholders();
+        size_t placeholder = 0;
+        for (auto& block : impl->styles()) {
+            // the builder adds a text block for each placeholder.
+            while (placeholder < placeholders.size() &&
+                   placeholders[placeholder].fRange.end <= block.fRange.start) {
+                ++placeholder;
+            }
+            if (placeholder < placeholders.size() &&
+                placeholders[placeholder].fRange.start == block.fRange.start &&
+                placeholders[placeholder].fRange.end == block.fRange.end) {
+                builder->pushStyle(placeholders[placeholder].fTextStyle);
+                builder->addPlaceholder(placeholders[placeholder].fStyle);
+                builder->pop();
+                continue;
+            }
+            builder->pushStyle(block.fStyle);
+            builder->addText(text.data() + block.fRange.start, block.fRange.width());
+            builder->pop();
+        }
+        auto unlimited = builder->Build();
+        unlimited->layout(self->getMaxWidth());
+        *height = unlimited->getHeight();
+        *lineNumber = unlimited->lineNumber();
+    }
 }
 
 //
 // ParagraphBuilder.h
 //
 
+namespace {
+    // The style stack of ParagraphBuilderImpl is protected, but can be accessed through a member
+    // pointer that is formed in a derived class.
+    struct ParagraphBuilderStylesAccess : ParagraphBuilderImpl {
+        static size_t depth(const ParagraphBuilder* builder) {
+            auto impl = static_cast<const ParagraphBuilderImpl*>(builder);
+            return (impl->*(&ParagraphBuilderStylesAccess::fTextStyles)).size();
+        }
+    };
+}
+
 extern "C" {
     void C_ParagraphBuilder_delete(ParagraphBuilder* self) {
         delete self;
@@ -311,6 +367,10 @@ extern "C" {
         *style = self->peekStyle();
     }
 
+    size_t C_ParagraphBuilder_styleStackDepth(const ParagraphBuilder* self) {
+        return ParagraphBuilderStylesAccess::depth(self);
+    }
+
     void C_ParagraphBuilder_addText(ParagraphBuilder* self, const char* text, size_t len) {
         self->addText(text, len);
     }
@@ -3,6 +3,7 @@
 #endif
 
 #include "include/gpu/GrBackendDrawableInfo.h"
+#include "include/gpu/GrBackendSemaphore.h"
 #include "include/gpu/GrBackendSurface.h"
 #include "include/gpu/GrDirectContext.h"
 #include "include/gpu/vk/GrVkVulkan.h"
@@ -114,3 +115,16 @@ extern "C" bool C_GrVkYcbcrConversionInfo_Equals(const GrVkYcbcrConversionInfo*
 extern "C" void C_GrBackendSurfaceMutableState_ConstructVK(GrBackendSurfaceMutableState* uninitialized, VkImageLayout layout, uint32_t queueFamilyIndex) {
     new(uninitialized)GrBackendSurfaceMutableState(layout, queueFamilyIndex);
 }
+
+//
+// gpu/GrBackendSemaphore.h
+//
+
+extern "C" void C_GrBackendSemaphore_ConstructVk(GrBackendSemaphore* uninitialized, VkSemaphore semaphore) {
+    new(uninitialized) GrBackendSemaphore();
+    uninitialized->initVulkan(semaphore);
+}
+
+extern "C" VkSemaphore C_GrBackendSemaphore_vkSemaphore(const GrBackendSemaphore* self) {
+    return self->vkSemaphore();
+}
@@ -0,0 +1,92 @@
+//! Renders the artifacts repeatedly and reports how long a frame takes, to compare the drivers
+//! and to validate that a build actually renders with the GPU.
+
+use crate::drivers::DrawingDriver;
+use skia_safe::{Canvas, Color, Surface};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// A driver that renders into surfaces, which can be benchmarked.
+pub trait SurfaceDriver: DrawingDriver {
+    /// Creates a surface of `size` device pixels.
+    fn new_surface(&mut self, size: (i32, i32)) -> Surface;
+}
+
+/// Renders every artifact `iterations` times into a surface of the wrapped driver instead of
+/// writing it to a file, and records the average time per frame.
+pub struct Benchmark<D: SurfaceDriver> {
+    driver: D,
+    iterations: usize,
+    results: Vec<(String, Duration)>,
+}
+
+impl<D: SurfaceDriver> Benchmark<D> {
+    pub fn with_driver(driver: D, iterations: usize) -> Self {
+        assert!(iterations > 0, "At least one iteration is required");
+        Self {
+            driver,
+            iterations,
+            results: Vec::new(),
+        }
+    }
+
+    /// Prints the time per frame of each artifact and the total.
+    pub fn report(&self) {
+        for (name, frame_time) in &self.results {
+            println!(
+                "{:<10} {:<40} {:>9.3} ms/frame",
+                D::NAME,
+                name,
+                ms(*frame_time)
+            );
+        }
+        let total: Duration = self.results.iter().map(|(_, frame_time)| *frame_time).sum();
+        println!(
+            "{:<10} {:<40} {:>9.3} ms/frame",
+            D::NAME,
+            format!("total ({} artifacts)", self.results.len()),
+            ms(total)
+        );
+    }
+}
+
+impl<D: SurfaceDriver> DrawingDriver for Benchmark<D> {
+    const NAME: &'static str = D::NAME;
+
+    fn new() -> Self {
+        Self::with_driver(D::new(), 10)
+    }
+
+    fn draw_image(
+        &mut self,
+        (width, height): (i32, i32),
+        _path: &Path,
+        name: &str,
+        func: impl Fn(&mut Canvas),
+    ) {
+        let mut surface = self.driver.new_surface((width * 2, height * 2));
+        let render = |surface: &mut Surface| {
+            let canvas = surface.canvas();
+            canvas.save();
+            canvas.clear(Color::TRANSPARENT);
+            canvas.scale((2.0, 2.0));
+            func(canvas);
+            canvas.restore();
+            // wait for the GPU, so that its work is included in the time.
+            surface.flush_and_submit(true);
+        };
+
+        // the first frame compiles shaders and fills caches.
+        render(&mut surface);
+        let start = Instant::now();
+        for _ in 0..self.iterations {
+            render(&mut surface);
+        }
+        let frame_time = start.elapsed() / self.iterations as u32;
+        self.results.push((name.to_owned(), frame_time));
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
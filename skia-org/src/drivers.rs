@@ -21,6 +21,10 @@ pub use vulkan::Vulkan;
 pub mod d3d;
 #[cfg(feature = "d3d")]
 pub use d3d::D3D;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
+#[cfg(feature = "wgpu")]
+pub use self::wgpu::Wgpu;
 
 pub trait DrawingDriver {
     const NAME: &'static str;
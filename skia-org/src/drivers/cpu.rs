@@ -1,4 +1,5 @@
 use crate::artifact;
+use crate::bench::SurfaceDriver;
 use crate::drivers::DrawingDriver;
 use skia_safe::{Canvas, Surface};
 use std::path::Path;
@@ -19,7 +20,13 @@ impl DrawingDriver for CPU {
         name: &str,
         func: impl Fn(&mut Canvas),
     ) {
-        let mut surface = Surface::new_raster_n32_premul((width * 2, height * 2)).unwrap();
+        let mut surface = self.new_surface((width * 2, height * 2));
         artifact::draw_image_on_surface(&mut surface, path, name, func);
     }
 }
+
+impl SurfaceDriver for CPU {
+    fn new_surface(&mut self, size: (i32, i32)) -> Surface {
+        Surface::new_raster_n32_premul(size).unwrap()
+    }
+}
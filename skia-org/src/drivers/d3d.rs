@@ -1,4 +1,5 @@
 use crate::artifact;
+use crate::bench::SurfaceDriver;
 use crate::drivers::DrawingDriver;
 use skia_safe::{
     gpu,
@@ -74,8 +75,19 @@ impl DrawingDriver for D3D {
         name: &str,
         func: impl Fn(&mut Canvas),
     ) {
-        let image_info = ImageInfo::new_n32_premul((width * 2, height * 2), None);
-        let mut surface = Surface::new_render_target(
+        let mut surface = self.new_surface((width * 2, height * 2));
+        artifact::draw_image_on_surface(&mut surface, path, name, func);
+    }
+
+    fn draw_image_256(&mut self, path: &Path, name: &str, func: impl Fn(&mut Canvas)) {
+        self.draw_image((256, 256), path, name, func)
+    }
+}
+
+impl SurfaceDriver for D3D {
+    fn new_surface(&mut self, size: (i32, i32)) -> Surface {
+        let image_info = ImageInfo::new_n32_premul(size, None);
+        Surface::new_render_target(
             &mut self.context,
             Budgeted::Yes,
             &image_info,
@@ -84,13 +96,7 @@ impl DrawingDriver for D3D {
             None,
             false,
         )
-        .unwrap();
-
-        artifact::draw_image_on_surface(&mut surface, path, name, func);
-    }
-
-    fn draw_image_256(&mut self, path: &Path, name: &str, func: impl Fn(&mut Canvas)) {
-        self.draw_image((256, 256), path, name, func)
+        .unwrap()
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::artifact;
+use crate::bench::SurfaceDriver;
 use crate::drivers::DrawingDriver;
 use skia_safe::{gpu, Budgeted, Canvas, ImageInfo, Surface};
 use std::path::Path;
@@ -23,8 +24,15 @@ impl DrawingDriver for OpenGL {
         name: &str,
         func: impl Fn(&mut Canvas),
     ) {
-        let image_info = ImageInfo::new_n32_premul((width * 2, height * 2), None);
-        let mut surface = Surface::new_render_target(
+        let mut surface = self.new_surface((width * 2, height * 2));
+        artifact::draw_image_on_surface(&mut surface, path, name, func);
+    }
+}
+
+impl SurfaceDriver for OpenGL {
+    fn new_surface(&mut self, size: (i32, i32)) -> Surface {
+        let image_info = ImageInfo::new_n32_premul(size, None);
+        Surface::new_render_target(
             // `self.context` is a refcounted pointer, and so cloning here is valid and will still
             // mutate the internal context correctly.
             &mut self.context.clone().into(),
@@ -35,8 +43,6 @@ impl DrawingDriver for OpenGL {
             None,
             false,
         )
-        .unwrap();
-
-        artifact::draw_image_on_surface(&mut surface, path, name, func);
+        .unwrap()
     }
 }
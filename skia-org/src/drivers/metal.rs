@@ -1,11 +1,9 @@
 use crate::artifact;
 use crate::drivers::DrawingDriver;
 use cocoa::foundation::NSAutoreleasePool;
-use foreign_types::ForeignType;
 use metal_rs::*;
 use skia_safe::gpu;
 use skia_safe::{Budgeted, Canvas, ImageInfo, Surface};
-use std::ffi;
 use std::path::Path;
 
 #[allow(dead_code)]
@@ -26,13 +24,7 @@ impl DrawingDriver for Metal {
         let device = Device::system_default().expect("no Metal device");
         let queue = device.new_command_queue();
 
-        let context = unsafe {
-            gpu::Context::new_metal(
-                device.as_ptr() as *mut ffi::c_void,
-                queue.as_ptr() as *mut ffi::c_void,
-            )
-        }
-        .unwrap();
+        let context = gpu::Context::new_metal(&device, &queue, None).unwrap();
 
         Self {
             pool,
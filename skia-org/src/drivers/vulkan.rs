@@ -1,4 +1,5 @@
 use crate::artifact;
+use crate::bench::SurfaceDriver;
 use crate::drivers::DrawingDriver;
 use ash::version::{DeviceV1_0, EntryV1_0, InstanceV1_0};
 use ash::vk;
@@ -63,8 +64,15 @@ impl DrawingDriver for Vulkan {
         name: &str,
         func: impl Fn(&mut Canvas),
     ) {
-        let image_info = ImageInfo::new_n32_premul((width * 2, height * 2), None);
-        let mut surface = Surface::new_render_target(
+        let mut surface = self.new_surface((width * 2, height * 2));
+        artifact::draw_image_on_surface(&mut surface, path, name, func);
+    }
+}
+
+impl SurfaceDriver for Vulkan {
+    fn new_surface(&mut self, size: (i32, i32)) -> Surface {
+        let image_info = ImageInfo::new_n32_premul(size, None);
+        Surface::new_render_target(
             &mut self.context,
             Budgeted::Yes,
             &image_info,
@@ -73,9 +81,7 @@ impl DrawingDriver for Vulkan {
             None,
             false,
         )
-        .unwrap();
-
-        artifact::draw_image_on_surface(&mut surface, path, name, func);
+        .unwrap()
     }
 }
 
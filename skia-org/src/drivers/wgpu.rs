@@ -0,0 +1,237 @@
+//! Renders with Skia into textures that are owned by wgpu.
+//!
+//! Skia and wgpu share the Vulkan device and queue wgpu creates, which are accessed through
+//! wgpu's HAL. Skia renders into a wgpu texture and wgpu then copies the texture into a buffer
+//! that is read back and written to a file. Compositing the texture in a wgpu render pass works
+//! the same way.
+//!
+//! Because Skia and wgpu submit to the same queue, the order of the submissions synchronizes
+//! them, and Skia transitions the image back to the layout wgpu expects when the surface is
+//! flushed. If Skia renders on a different queue, a semaphore that is signaled by the other queue
+//! must be passed to Skia with `gpu::BackendSemaphore::new_vulkan()` and
+//! `gpu::DirectContext::wait()` before Skia renders.
+
+use crate::artifact;
+use crate::drivers::DrawingDriver;
+use ash35::vk;
+use ash35::vk::Handle;
+use skia_safe::gpu::BackendSurfaceMutableState;
+use skia_safe::{gpu, AlphaType, Canvas, ColorType, Data, EncodedImageFormat, Image, ImageInfo};
+use std::convert::TryInto;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::ptr;
+use wgpu_hal::api::Vulkan as VulkanApi;
+use wgpu_rs as wgpu;
+
+/// The format of the textures, which matches `ColorType::RGBA8888`.
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+pub struct Wgpu {
+    // ordered for drop order
+    context: gpu::DirectContext,
+    queue_family_index: u32,
+    queue: wgpu::Queue,
+    device: wgpu::Device,
+}
+
+impl DrawingDriver for Wgpu {
+    const NAME: &'static str = "wgpu";
+
+    fn new() -> Self {
+        let instance = wgpu::Instance::new(wgpu::Backends::VULKAN);
+        let adapter = pollster::block_on(instance.request_adapter(&Default::default()))
+            .expect("Failed to find a Vulkan adapter.");
+        let (device, queue) = pollster::block_on(adapter.request_device(&Default::default(), None))
+            .expect("Failed to create a wgpu device.");
+
+        let (context, queue_family_index) = unsafe {
+            device.as_hal::<VulkanApi, _, _>(|hal_device| {
+                let hal_device = hal_device.expect("The wgpu device is not backed by Vulkan.");
+                let shared = hal_device.shared_instance();
+                let entry = shared.entry();
+                let raw_instance = shared.raw_instance();
+
+                let get_proc = |of| match of {
+                    gpu::vk::GetProcOf::Instance(instance, name) => entry
+                        .get_instance_proc_addr(vk::Instance::from_raw(instance as _), name)
+                        .map_or(ptr::null(), |f| f as _),
+                    gpu::vk::GetProcOf::Device(device, name) => raw_instance
+                        .get_device_proc_addr(vk::Device::from_raw(device as _), name)
+                        .map_or(ptr::null(), |f| f as _),
+                };
+
+                let backend_context = gpu::vk::BackendContext::new(
+                    raw_instance.handle().as_raw() as _,
+                    hal_device.raw_physical_device().as_raw() as _,
+                    hal_device.raw_device().handle().as_raw() as _,
+                    (
+                        hal_device.raw_queue().as_raw() as _,
+                        hal_device.queue_index().try_into().unwrap(),
+                    ),
+                    &get_proc,
+                );
+
+                let context = gpu::DirectContext::new_vulkan(&backend_context, None)
+                    .expect("Failed to create a Skia context for the wgpu device.");
+                (context, hal_device.queue_family_index())
+            })
+        };
+
+        Self {
+            context,
+            queue_family_index,
+            queue,
+            device,
+        }
+    }
+
+    fn draw_image(
+        &mut self,
+        (width, height): (i32, i32),
+        path: &Path,
+        name: &str,
+        func: impl Fn(&mut Canvas),
+    ) {
+        let (width, height) = (width * 2, height * 2);
+        let extent = wgpu::Extent3d {
+            width: width as _,
+            height: height as _,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(name),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+        });
+
+        // Clear the texture with wgpu first, so that wgpu considers it initialized and does not
+        // clear it again after Skia rendered into it. This leaves the image in the color
+        // attachment layout.
+        let view = texture.create_view(&Default::default());
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        self.queue.submit(Some(encoder.finish()));
+
+        self.render_with_skia(&texture, (width, height), func);
+
+        let image = self.read_back(&texture, extent);
+        let data = image.encode_to_data(EncodedImageFormat::PNG).unwrap();
+        artifact::write_file(data.as_bytes(), path, name, "png");
+    }
+}
+
+impl Wgpu {
+    fn render_with_skia(
+        &mut self,
+        texture: &wgpu::Texture,
+        (width, height): (i32, i32),
+        func: impl Fn(&mut Canvas),
+    ) {
+        let layout = gpu::vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL;
+
+        let mut raw_image = None;
+        unsafe {
+            texture.as_hal::<VulkanApi, _>(|hal_texture| {
+                raw_image = hal_texture.map(|texture| texture.raw_handle());
+            })
+        };
+        let raw_image = raw_image.expect("The wgpu texture is not backed by Vulkan.");
+
+        let backend_texture = unsafe {
+            let image_info = gpu::vk::ImageInfo::new(
+                raw_image.as_raw() as _,
+                gpu::vk::Alloc::default(),
+                gpu::vk::ImageTiling::OPTIMAL,
+                layout,
+                gpu::vk::Format::R8G8B8A8_UNORM,
+                1,
+                self.queue_family_index,
+                None,
+                None,
+                None,
+            );
+            gpu::BackendTexture::new_vulkan((width, height), &image_info)
+        };
+
+        let mut surface = skia_safe::Surface::from_backend_texture(
+            &mut self.context,
+            &backend_texture,
+            gpu::SurfaceOrigin::TopLeft,
+            None,
+            ColorType::RGBA8888,
+            None,
+            None,
+        )
+        .expect("Failed to wrap the wgpu texture.");
+
+        let canvas = surface.canvas();
+        canvas.scale((2.0, 2.0));
+        func(canvas);
+
+        // Leave the image in the layout and queue family wgpu tracked for it.
+        let state = BackendSurfaceMutableState::new_vk(layout, self.queue_family_index);
+        surface.flush_with_mutable_state(&Default::default(), &state);
+        self.context.submit(false);
+    }
+
+    fn read_back(&self, texture: &wgpu::Texture, extent: wgpu::Extent3d) -> Image {
+        let row_bytes = align_to(extent.width * 4, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: u64::from(row_bytes * extent.height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(row_bytes),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let mapped = slice.map_async(wgpu::MapMode::Read);
+        self.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(mapped).expect("Failed to map the read back buffer.");
+
+        let info = ImageInfo::new(
+            (extent.width as i32, extent.height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Premul,
+            None,
+        );
+        let pixels = Data::new_copy(&slice.get_mapped_range());
+        Image::from_raster_data(&info, pixels, row_bytes as usize).unwrap()
+    }
+}
+
+fn align_to(value: u32, alignment: u32) -> u32 {
+    (value + alignment - 1) / alignment * alignment
+}
@@ -1,8 +1,9 @@
-use clap::{App, Arg};
+use clap::{App, Arg, ArgMatches, SubCommand};
 #[cfg(feature = "gl")]
 use offscreen_gl_context::{GLContext, GLVersion, NativeGLContext};
 use std::path::{Path, PathBuf};
 
+use crate::bench::{Benchmark, SurfaceDriver};
 use crate::drivers::DrawingDriver;
 
 #[cfg(feature = "vulkan")]
@@ -15,6 +16,7 @@ extern crate objc;
 // TODO: think about making the examples more Rust-idiomatic, by using method chaining for Paint / Paths, for example.
 
 mod artifact;
+mod bench;
 mod drivers;
 mod skcanvas_overview;
 mod skpaint_overview;
@@ -24,10 +26,12 @@ mod skpath_overview;
 #[cfg(feature = "textlayout")]
 mod skshaper_example;
 
-fn main() {
-    const OUT_PATH: &str = "OUT_PATH";
-    const DRIVER: &str = "driver";
+const OUT_PATH: &str = "OUT_PATH";
+const DRIVER: &str = "driver";
+const BENCH: &str = "bench";
+const ITERATIONS: &str = "iterations";
 
+fn main() {
     let matches = App::new("skia-org examples")
         .about("Renders examples from skia.org with rust-skia")
         .arg(
@@ -44,21 +48,39 @@ fn main() {
                 .multiple(true)
                 .help("In addition to the CPU, render with the given driver."),
         )
+        .subcommand(
+            SubCommand::with_name(BENCH)
+                .about("Renders the examples repeatedly and reports the time per frame")
+                .arg(
+                    Arg::with_name(DRIVER)
+                        .long(DRIVER)
+                        .takes_value(true)
+                        .possible_values(get_benchmark_drivers().as_slice())
+                        .multiple(true)
+                        .help(
+                            "In addition to the CPU, benchmark the given driver. \
+                             The metal and wgpu drivers can not be benchmarked yet.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name(ITERATIONS)
+                        .long(ITERATIONS)
+                        .takes_value(true)
+                        .default_value("10")
+                        .validator(validate_iterations)
+                        .help("The number of frames to render per example."),
+                ),
+        )
         .get_matches();
 
+    if let Some(matches) = matches.subcommand_matches(BENCH) {
+        bench_drivers(matches);
+        return;
+    }
+
     let out_path = PathBuf::from(matches.value_of(OUT_PATH).unwrap());
 
-    let drivers = {
-        let drivers = matches
-            .values_of(DRIVER)
-            .unwrap_or_default()
-            .collect::<Vec<&str>>();
-        if drivers.is_empty() {
-            vec!["cpu"]
-        } else {
-            drivers
-        }
-    };
+    let drivers = selected_drivers(&matches);
 
     if drivers.contains(&drivers::CPU::NAME) {
         draw_all(&mut drivers::CPU::new(), &out_path);
@@ -134,19 +156,106 @@ fn main() {
         }
     }
 
-    fn draw_all<Driver: DrawingDriver>(driver: &mut Driver, out_path: &Path) {
-        let out_path = out_path.join(Driver::NAME);
+    #[cfg(feature = "wgpu")]
+    {
+        use drivers::Wgpu;
+
+        if drivers.contains(&Wgpu::NAME) {
+            draw_all(&mut Wgpu::new(), &out_path)
+        }
+    }
+}
+
+fn selected_drivers<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+    let drivers = matches
+        .values_of(DRIVER)
+        .unwrap_or_default()
+        .collect::<Vec<&str>>();
+    if drivers.is_empty() {
+        vec!["cpu"]
+    } else {
+        drivers
+    }
+}
+
+fn draw_all<Driver: DrawingDriver>(driver: &mut Driver, out_path: &Path) {
+    let out_path = out_path.join(Driver::NAME);
 
-        skcanvas_overview::draw(driver, &out_path);
-        skpath_overview::draw(driver, &out_path);
-        skpaint_overview::draw(driver, &out_path);
+    skcanvas_overview::draw(driver, &out_path);
+    skpath_overview::draw(driver, &out_path);
+    skpaint_overview::draw(driver, &out_path);
 
-        #[cfg(feature = "textlayout")]
-        {
-            skshaper_example::draw(driver, &out_path);
-            skparagraph_example::draw(driver, &out_path);
+    #[cfg(feature = "textlayout")]
+    {
+        skshaper_example::draw(driver, &out_path);
+        skparagraph_example::draw(driver, &out_path);
+    }
+}
+
+fn bench_drivers(matches: &ArgMatches) {
+    let drivers = selected_drivers(matches);
+    // validated by `validate_iterations()`.
+    let iterations: usize = matches.value_of(ITERATIONS).unwrap().parse().unwrap();
+
+    if drivers.contains(&drivers::CPU::NAME) {
+        bench_all(drivers::CPU::new(), iterations);
+    }
+
+    #[cfg(feature = "gl")]
+    {
+        if drivers.contains(&drivers::OpenGL::NAME) {
+            let context = GLContext::<NativeGLContext>::create(
+                sparkle::gl::GlType::Gl,
+                GLVersion::MajorMinor(3, 3),
+                None,
+            )
+            .unwrap();
+
+            context.make_current().unwrap();
+            bench_all(drivers::OpenGL::new(), iterations);
+        }
+    }
+
+    #[cfg(feature = "vulkan")]
+    {
+        if drivers.contains(&drivers::Vulkan::NAME) {
+            bench_all(drivers::Vulkan::new(), iterations);
         }
     }
+
+    #[cfg(feature = "d3d")]
+    {
+        if drivers.contains(&drivers::D3D::NAME) {
+            bench_all(drivers::D3D::new(), iterations);
+        }
+    }
+
+    fn bench_all<Driver: SurfaceDriver>(driver: Driver, iterations: usize) {
+        let mut benchmark = Benchmark::with_driver(driver, iterations);
+        draw_all(&mut benchmark, Path::new(""));
+        benchmark.report();
+    }
+}
+
+fn validate_iterations(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(iterations) if iterations > 0 => Ok(()),
+        _ => Err("The number of iterations must be a positive number".into()),
+    }
+}
+
+fn get_benchmark_drivers() -> Vec<&'static str> {
+    let mut drivers = vec!["cpu"];
+    if cfg!(feature = "gl") {
+        drivers.push("opengl");
+    }
+    if cfg!(feature = "vulkan") {
+        drivers.push("vulkan")
+    }
+    if cfg!(feature = "d3d") {
+        drivers.push("d3d")
+    }
+    drivers
 }
 
 fn get_available_drivers() -> Vec<&'static str> {
@@ -163,6 +272,9 @@ fn get_available_drivers() -> Vec<&'static str> {
     if cfg!(feature = "d3d") {
         drivers.push("d3d")
     }
+    if cfg!(feature = "wgpu") {
+        drivers.push("wgpu")
+    }
     drivers
 }
 
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+// cargo 1.45.1 / rustfmt 1.4.17-stable fails to process the relative path on Windows.
+#[rustfmt::skip]
+#[path = "../../icon/renderer.rs"]
+mod renderer;
+
+#[cfg(target_os = "android")]
+fn main() {
+    println!("This example is not supported on Android (https://github.com/rust-windowing/winit/issues/948).")
+}
+
+#[cfg(not(target_os = "android"))]
+fn main() {
+    use skia_safe::softbuffer::WindowPresenter;
+    use skia_safe::Color;
+    use winit::event::{Event, KeyboardInput, VirtualKeyCode, WindowEvent};
+    use winit::event_loop::{ControlFlow, EventLoop};
+    use winit::window::WindowBuilder;
+
+    let el = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_title("rust-skia-softbuffer-window")
+        .build(&el)
+        .unwrap();
+
+    // The window is moved into the event loop closure together with the presenter and lives as
+    // long as it.
+    let mut presenter = unsafe { WindowPresenter::new(&window) }.unwrap();
+    let mut frame = 0;
+
+    el.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Wait;
+
+        #[allow(deprecated)]
+        match event {
+            Event::LoopDestroyed => {}
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::Resized(_) => window.request_redraw(),
+                WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            virtual_keycode,
+                            modifiers,
+                            ..
+                        },
+                    ..
+                } => {
+                    if modifiers.logo() {
+                        if let Some(VirtualKeyCode::Q) = virtual_keycode {
+                            *control_flow = ControlFlow::Exit;
+                        }
+                    }
+                    frame += 1;
+                    window.request_redraw();
+                }
+                _ => (),
+            },
+            Event::RedrawRequested(_) => {
+                let size = window.inner_size();
+                presenter
+                    .present((size.width as i32, size.height as i32), |canvas| {
+                        canvas.clear(Color::WHITE);
+                        renderer::render_frame(frame % 360, 12, 60, canvas);
+                    })
+                    .unwrap();
+            }
+            _ => (),
+        }
+    });
+}
@@ -9,9 +9,16 @@ use std::{
     fmt, io,
     ops::{Deref, DerefMut},
     path::Path,
+    time::Instant,
 };
 
-use crate::{interop::RustStream, prelude::*, Canvas, FontMgr, RCHandle, Rect, Size};
+#[cfg(feature = "gpu")]
+use crate::{gpu, Budgeted};
+use crate::{
+    interop::RustStream, matrix::ScaleToFit, prelude::*, AlphaType, Canvas, Color, ColorSpace,
+    ColorType, FontMgr, IRect, ISize, Image, ImageInfo, Matrix, RCHandle, Rect, RoundOut, Size,
+    Surface,
+};
 use skia_bindings as sb;
 
 bitflags::bitflags! {
@@ -435,3 +442,492 @@ impl Animation {
         out
     }
 }
+
+/// Renders the current frame of `animation` into a new raster [Image].
+///
+/// The frame is scaled to fit `size`, which defaults to the animation's own size (see
+/// `Animation::size`), and is drawn over `background`, which defaults to transparent. Returns
+/// [None] if the surface could not be created.
+pub fn render_to_image(
+    animation: &Animation,
+    size: impl Into<Option<ISize>>,
+    background: impl Into<Option<Color>>,
+) -> Option<Image> {
+    let size = size.into().unwrap_or_else(|| animation.size().to_ceil());
+    let surface = Surface::new_raster_n32_premul(size)?;
+    Some(surface.render_to_image(background.into(), |canvas| {
+        animation.render(canvas, Rect::from_isize(size))
+    }))
+}
+
+/// Renders the current frame of `animation` into a new texture-backed [Image].
+///
+/// Same as [render_to_image], but the frame is rendered on the GPU using `context`.
+#[cfg(feature = "gpu")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+pub fn render_to_texture_image(
+    context: &mut gpu::RecordingContext,
+    animation: &Animation,
+    size: impl Into<Option<ISize>>,
+    background: impl Into<Option<Color>>,
+) -> Option<Image> {
+    let size = size.into().unwrap_or_else(|| animation.size().to_ceil());
+    let surface = Surface::new_render_target(
+        context,
+        Budgeted::Yes,
+        &ImageInfo::new_n32_premul(size, None),
+        None,
+        gpu::SurfaceOrigin::TopLeft,
+        None,
+        None,
+    )?;
+    Some(surface.render_to_image(background.into(), |canvas| {
+        animation.render(canvas, Rect::from_isize(size))
+    }))
+}
+
+/// Renders all the frames of an [Animation] at a fixed frame rate, for example to pipe them into
+/// a video encoder like `ffmpeg`.
+///
+/// The frames are rendered into a surface that is reused, and read back as unpremultiplied
+/// RGBA8888 pixels in the color space the server was created with. Lottie colors are sRGB, so
+/// with the default sRGB color space the pixels can be passed to `ffmpeg` with
+/// `-f rawvideo -pix_fmt rgba -s <width>x<height> -r <frame rate>`.
+///
+/// Frames that did not change since the previous one, according to the [DirtyRegion] of the
+/// animation, are not rendered again, and only the dirty region of frames that did change is
+/// redrawn.
+///
+/// ```rust,no_run
+/// # use skia_safe::animation::{Animation, FrameServer};
+/// # use std::io::Write;
+/// let animation = Animation::open("data/my-animation.json").unwrap();
+/// let mut server = FrameServer::new(animation, (1280, 720), 30.0, None).unwrap();
+/// let mut out = std::io::stdout();
+/// while let Some(frame) = server.next_frame() {
+///     out.write_all(frame.pixels).unwrap();
+/// }
+/// ```
+pub struct FrameServer {
+    animation: Animation,
+    surface: Surface,
+    dst: Rect,
+    background: Color,
+    info: ImageInfo,
+    pixels: Vec<u8>,
+    frame_rate: f64,
+    frame_count: usize,
+    next_frame: usize,
+}
+
+/// A frame rendered by a [FrameServer].
+pub struct Frame<'a> {
+    /// The index of the frame, starting at 0.
+    pub index: usize,
+    /// The time of the frame in the animation, in seconds.
+    pub time: f64,
+    /// `false` if the frame is identical to the previous one.
+    pub changed: bool,
+    /// The format of the pixels, which are unpremultiplied RGBA8888 without any padding
+    /// between the rows.
+    pub info: &'a ImageInfo,
+    /// The pixels of the frame.
+    pub pixels: &'a [u8],
+    surface: &'a Surface,
+}
+
+impl<'a> Frame<'a> {
+    /// Returns the frame as an [Image] with premultiplied alpha.
+    pub fn image(&self) -> Image {
+        self.surface.image_snapshot()
+    }
+}
+
+impl FrameServer {
+    /// Creates a frame server that renders `animation` scaled to fit `size` at `frame_rate`
+    /// frames per second into `color_space`, which defaults to sRGB. Returns [None] if the
+    /// surface could not be created.
+    pub fn new(
+        animation: Animation,
+        size: impl Into<ISize>,
+        frame_rate: f64,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Self> {
+        assert!(frame_rate > 0.0, "The frame rate must be positive");
+        let size = size.into();
+        let color_space = color_space.into().unwrap_or_else(ColorSpace::new_srgb);
+        let surface = Surface::new_raster(
+            &ImageInfo::new(
+                size,
+                ColorType::RGBA8888,
+                AlphaType::Premul,
+                color_space.clone(),
+            ),
+            None,
+            None,
+        )?;
+        let info = ImageInfo::new(size, ColorType::RGBA8888, AlphaType::Unpremul, color_space);
+        let pixels = vec![0; info.compute_min_byte_size()];
+        let frame_count = ((animation.duration() * frame_rate).ceil() as usize).max(1);
+        Some(Self {
+            animation,
+            surface,
+            dst: Rect::from_isize(size),
+            background: Color::TRANSPARENT,
+            info,
+            pixels,
+            frame_rate,
+            frame_count,
+            next_frame: 0,
+        })
+    }
+
+    /// Sets the color the frames are cleared with, which defaults to transparent. Video formats
+    /// without alpha need an opaque background.
+    pub fn with_background(mut self, background: impl Into<Color>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// The number of frames the animation is rendered into.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Seeks the animation to the next frame and renders it if it changed, or returns [None]
+    /// after the last frame.
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        if self.next_frame >= self.frame_count {
+            return None;
+        }
+        let index = self.next_frame;
+        self.next_frame += 1;
+        let time = index as f64 / self.frame_rate;
+        let dirty: DirtyRegion = self.animation.seek_time(time);
+
+        let changed = index == 0 || !dirty.bounds().is_empty();
+        if changed {
+            // Skottie maps the animation into the destination rectangle with
+            // `ScaleToFit::Center`.
+            let matrix = Matrix::from_rect_to_rect(
+                Rect::from_size(self.animation.size()),
+                self.dst,
+                ScaleToFit::Center,
+            )
+            .filter(|_| index != 0);
+            let canvas = self.surface.canvas();
+            canvas.save();
+            if let Some(matrix) = matrix {
+                let dirty: IRect = matrix.map_rect(dirty.bounds()).0.round_out();
+                canvas.clip_rect(Rect::from(dirty.with_outset((1, 1))), None, false);
+            }
+            canvas.clear(self.background);
+            self.animation.render(canvas, self.dst);
+            canvas.restore();
+            let row_bytes = self.info.min_row_bytes();
+            self.surface
+                .read_pixels(&self.info, &mut self.pixels, row_bytes, (0, 0));
+        }
+
+        Some(Frame {
+            index,
+            time,
+            changed,
+            info: &self.info,
+            pixels: &self.pixels,
+            surface: &self.surface,
+        })
+    }
+}
+
+/// How a [Player] continues when it reaches the end of the animation.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LoopMode {
+    /// Stops at the last frame, or at the first frame when playing backwards.
+    Once,
+    /// Starts over from the beginning.
+    Loop,
+    /// Plays backwards to the beginning, and then forwards again.
+    PingPong,
+}
+
+impl Default for LoopMode {
+    fn default() -> Self {
+        LoopMode::Loop
+    }
+}
+
+/// Controls the playback of an [Animation] in real time.
+///
+/// All functions that depend on the playback time take the current time as an [Instant], so
+/// that all animations of a frame can be updated for the same point in time:
+///
+/// ```rust,no_run
+/// # use skia_safe::animation::{Animation, Player};
+/// # use std::time::Instant;
+/// # fn draw(player: &Player) {}
+/// let animation = Animation::open("data/my-animation.json").unwrap();
+/// let mut player = Player::new(animation);
+/// player.play(Instant::now());
+/// loop {
+///     player.update(Instant::now());
+///     draw(&player);
+/// #   break;
+/// }
+/// ```
+pub struct Player {
+    animation: Animation,
+    loop_mode: LoopMode,
+    speed: f64,
+    // the playback position in seconds at `resumed`, or the current one if paused. The
+    // position is not wrapped around the duration, the loop mode is applied by `time()`.
+    position: f64,
+    resumed: Option<Instant>,
+}
+
+impl Player {
+    /// Creates a paused player at the beginning of `animation` that loops at normal speed.
+    pub fn new(animation: Animation) -> Self {
+        Player {
+            animation,
+            loop_mode: LoopMode::default(),
+            speed: 1.0,
+            position: 0.0,
+            resumed: None,
+        }
+    }
+
+    /// The animation that is played.
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    /// Returns the animation and drops the player.
+    pub fn into_animation(self) -> Animation {
+        self.animation
+    }
+
+    /// How playback continues at the end of the animation.
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Sets how playback continues at the end of the animation. Playback continues from the
+    /// current time.
+    pub fn set_loop_mode(&mut self, loop_mode: LoopMode, now: Instant) -> &mut Self {
+        let time = self.time(now);
+        self.loop_mode = loop_mode;
+        self.seek(time, now)
+    }
+
+    /// The playback speed, `1.0` is normal speed, and negative speeds play backwards.
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Sets the playback speed, see [Self::speed()].
+    pub fn set_speed(&mut self, speed: f64, now: Instant) -> &mut Self {
+        self.position = self.position(now);
+        if self.resumed.is_some() {
+            self.resumed = Some(now);
+        }
+        self.speed = speed;
+        self
+    }
+
+    /// Returns `true` if the player is playing, which is also the case if it played the
+    /// animation [LoopMode::Once] and stopped at the end, see [Self::is_finished()].
+    pub fn is_playing(&self) -> bool {
+        self.resumed.is_some()
+    }
+
+    /// Starts or resumes playback. If the animation was played [LoopMode::Once] and finished,
+    /// it starts over.
+    pub fn play(&mut self, now: Instant) -> &mut Self {
+        if self.is_finished(now) {
+            let start = if self.speed < 0.0 {
+                self.duration()
+            } else {
+                0.0
+            };
+            self.seek(start, now);
+        }
+        if self.resumed.is_none() {
+            self.resumed = Some(now);
+        }
+        self
+    }
+
+    /// Pauses playback at the current time.
+    pub fn pause(&mut self, now: Instant) -> &mut Self {
+        self.position = self.position(now);
+        if self.loop_mode == LoopMode::Once {
+            self.position = self.position.max(0.0).min(self.duration());
+        }
+        self.resumed = None;
+        self
+    }
+
+    /// Moves the playback to `time` in seconds, which is wrapped around the duration of the
+    /// animation according to the loop mode.
+    pub fn seek(&mut self, time: f64, now: Instant) -> &mut Self {
+        self.position = time;
+        if self.resumed.is_some() {
+            self.resumed = Some(now);
+        }
+        self
+    }
+
+    /// Returns `true` if the animation is played [LoopMode::Once] and reached its end, or its
+    /// beginning when playing backwards.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        if self.loop_mode != LoopMode::Once {
+            return false;
+        }
+        let position = self.position(now);
+        if self.speed < 0.0 {
+            position <= 0.0
+        } else {
+            position >= self.duration()
+        }
+    }
+
+    /// The time in the animation in seconds, which is between `0` and the duration of the
+    /// animation.
+    pub fn time(&self, now: Instant) -> f64 {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+        let position = self.position(now);
+        match self.loop_mode {
+            LoopMode::Once => position.max(0.0).min(duration),
+            LoopMode::Loop => position.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let time = position.rem_euclid(2.0 * duration);
+                if time > duration {
+                    2.0 * duration - time
+                } else {
+                    time
+                }
+            }
+        }
+    }
+
+    /// Seeks the animation to the time at `now` and returns the region that changed, see
+    /// [Animation::seek_time()].
+    pub fn update(&mut self, now: Instant) -> DirtyRegion {
+        let time = self.time(now);
+        self.animation.seek_time(time)
+    }
+
+    /// Renders the current frame of the animation, see [Animation::render()].
+    pub fn render(&self, canvas: &mut Canvas, dst: impl Into<Option<Rect>>) {
+        self.animation.render(canvas, dst)
+    }
+
+    fn duration(&self) -> f64 {
+        self.animation.duration()
+    }
+
+    fn position(&self, now: Instant) -> f64 {
+        match self.resumed {
+            Some(resumed) => {
+                self.position + now.saturating_duration_since(resumed).as_secs_f64() * self.speed
+            }
+            None => self.position,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, FrameServer, LoopMode, Player};
+    use crate::Color;
+    use std::time::{Duration, Instant};
+
+    const RED_SQUARE: &str = r##"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 30, "w": 10, "h": 10,
+        "layers": [{
+            "ty": 1, "sc": "#ff0000", "sw": 10, "sh": 10, "ip": 0, "op": 30, "ks": {}
+        }]
+    }"##;
+
+    #[test]
+    fn frame_server_skips_unchanged_frames() {
+        let animation = Animation::from_data(RED_SQUARE.as_bytes()).unwrap();
+        let mut server = FrameServer::new(animation, (20, 20), 30.0, None)
+            .unwrap()
+            .with_background(Color::BLUE);
+        assert_eq!(server.frame_count(), 30);
+
+        let frame = server.next_frame().unwrap();
+        assert!(frame.changed);
+        assert_eq!(frame.pixels.len(), 20 * 20 * 4);
+        let center = (10 * 20 + 10) * 4;
+        assert_eq!(&frame.pixels[center..center + 4], &[255, 0, 0, 255]);
+
+        let frame = server.next_frame().unwrap();
+        assert_eq!(frame.index, 1);
+        assert!(!frame.changed);
+
+        let mut remaining = 0;
+        while let Some(frame) = server.next_frame() {
+            assert!(!frame.changed);
+            remaining += 1;
+        }
+        assert_eq!(remaining, 28);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn player_loop_modes() {
+        let animation = Animation::from_data(RED_SQUARE.as_bytes()).unwrap();
+        assert_eq!(animation.duration(), 1.0);
+        let start = Instant::now();
+        let at = |seconds: f64| start + Duration::from_secs_f64(seconds);
+
+        let mut player = Player::new(animation);
+        assert!(!player.is_playing());
+        assert_eq!(player.time(at(0.5)), 0.0);
+
+        player.play(start);
+        assert_eq!(player.time(at(0.25)), 0.25);
+        assert_eq!(player.time(at(1.0)), 0.0);
+        assert_eq!(player.time(at(2.25)), 0.25);
+
+        player.set_loop_mode(LoopMode::PingPong, start);
+        assert_eq!(player.time(at(1.25)), 0.75);
+        assert_eq!(player.time(at(2.25)), 0.25);
+
+        player.set_loop_mode(LoopMode::Once, start);
+        assert_eq!(player.time(at(0.5)), 0.5);
+        assert!(!player.is_finished(at(0.5)));
+        assert_eq!(player.time(at(3.0)), 1.0);
+        assert!(player.is_finished(at(3.0)));
+        player.update(at(3.0));
+
+        // playing a finished animation starts over.
+        player.play(at(3.0));
+        assert_eq!(player.time(at(3.5)), 0.5);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn player_pause_and_speed() {
+        let animation = Animation::from_data(RED_SQUARE.as_bytes()).unwrap();
+        let start = Instant::now();
+        let at = |seconds: f64| start + Duration::from_secs_f64(seconds);
+
+        let mut player = Player::new(animation);
+        player.play(start).pause(at(0.25));
+        assert_eq!(player.time(at(10.0)), 0.25);
+
+        player.play(at(1.0)).set_speed(2.0, at(1.0));
+        assert_eq!(player.time(at(1.125)), 0.5);
+
+        player.set_speed(-1.0, at(1.125));
+        assert_eq!(player.time(at(1.375)), 0.25);
+        assert_eq!(player.time(at(1.875)), 0.75);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::prelude::*;
-use crate::{Data, EncodedImageFormat, EncodedOrigin, IRect, ISize, ImageInfo, Pixmap};
+use crate::{
+    AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, EncodedOrigin, IRect,
+    ISize, Image, ImageInfo, Pixmap,
+};
 use debug_stub_derive::DebugStub;
 use ffi::CStr;
 use skia_bindings as sb;
@@ -32,6 +35,25 @@ fn test_zero_initialized_naming() {
     let _ = ZeroInitialized::Yes;
 }
 
+/// Reports the progress of a long running decode and decides whether it continues, see
+/// [Codec::get_pixels_with_progress()].
+///
+/// Closures with the signature of [DecodeProgress::progress()] implement this trait.
+pub trait DecodeProgress {
+    /// Called after `completed` of `total` rows are decoded. Returning `false` cancels the
+    /// decode.
+    fn progress(&mut self, completed: usize, total: usize) -> bool;
+}
+
+impl<F: FnMut(usize, usize) -> bool> DecodeProgress for F {
+    fn progress(&mut self, completed: usize, total: usize) -> bool {
+        self(completed, total)
+    }
+}
+
+/// The number of rows that are decoded between two progress reports.
+const ROWS_PER_PROGRESS: usize = 16;
+
 #[derive(Clone, PartialEq, Eq, DebugStub)]
 pub struct Options {
     pub zero_initialized: ZeroInitialized,
@@ -49,6 +71,20 @@ impl NativeRefCountedBase for SkCodec {
 }
 
 impl Codec {
+    /// Returns `true` if images of `format` can be decoded by this build of Skia.
+    ///
+    /// Support for WEBP depends on the feature `webp-decode`. [EncodedImageFormat::HEIF] and
+    /// [EncodedImageFormat::AVIF] images can not be decoded, because the HEIF codec of Skia m87
+    /// depends on Android's HEIF decoder and there is no AVIF codec yet.
+    pub fn supports_decoding(format: EncodedImageFormat) -> bool {
+        use EncodedImageFormat::*;
+        match format {
+            BMP | GIF | ICO | JPEG | PNG | WBMP => true,
+            WEBP => cfg!(feature = "webp-decode"),
+            _ => false,
+        }
+    }
+
     // TODO: wrap MakeFromStream
     // TODO: wrap from_data with SkPngChunkReader
 
@@ -139,6 +175,124 @@ impl Codec {
         )
     }
 
+    /// Decodes the image like [Self::get_pixels_with_options()], but reports the progress to
+    /// `progress` and stops when it returns `false`, for example because the user navigated
+    /// away from a huge image that is still loading.
+    ///
+    /// Returns `None` if the decode was cancelled, in which case the content of `pixels` is
+    /// undefined.
+    ///
+    /// Formats that can be decoded row by row from the top, like PNG and JPEG, are decoded in
+    /// steps of a few rows, and the progress is reported after every step. All other images are
+    /// decoded at once, so the decode can only be cancelled before it starts.
+    pub fn get_pixels_with_progress(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: Option<&Options>,
+        mut progress: impl DecodeProgress,
+    ) -> Option<Result> {
+        assert_eq!(pixels.len(), info.compute_byte_size(row_bytes));
+        let total: usize = info.height().try_into().unwrap_or_default();
+        if !progress.progress(0, total) {
+            return None;
+        }
+
+        let native_options = options.map(|options| unsafe { Self::native_options(options) });
+        let started = unsafe {
+            sb::C_SkCodec_startScanlineDecode(
+                self.native_mut(),
+                info.native(),
+                native_options.as_ptr_or_null(),
+            )
+        };
+        if started != Result::Success
+            || !unsafe { sb::C_SkCodec_isScanlineOrderTopDown(self.native()) }
+        {
+            let result = self.get_pixels_with_options(info, pixels, row_bytes, options);
+            progress.progress(total, total);
+            return Some(result);
+        }
+
+        let mut result = Result::Success;
+        let mut row = 0;
+        while row < total {
+            let count = ROWS_PER_PROGRESS.min(total - row);
+            let decoded = unsafe {
+                sb::C_SkCodec_getScanlines(
+                    self.native_mut(),
+                    pixels[row * row_bytes..].as_mut_ptr() as _,
+                    count.try_into().unwrap(),
+                    row_bytes,
+                )
+            };
+            // Skia fills the rows that could not be decoded.
+            if (decoded as usize) < count {
+                result = Result::IncompleteInput;
+            }
+            row += count;
+            if !progress.progress(row, total) && row < total {
+                return None;
+            }
+        }
+        Some(result)
+    }
+
+    /// Decodes the image into a new raster image with the dimensions, color type, alpha type,
+    /// and color space of `info`, or of [Self::info()] if `info` is `None`.
+    ///
+    /// The pixels are converted while they are decoded, so decoding for example directly to
+    /// [ColorType::RGBAF16] in a wide gamut color space avoids a second pass over the pixels.
+    pub fn get_image(
+        &mut self,
+        info: impl Into<Option<ImageInfo>>,
+        options: Option<&Options>,
+    ) -> std::result::Result<Image, Result> {
+        let info = info.into().unwrap_or_else(|| self.info());
+        let row_bytes = info.min_row_bytes();
+        let size = info.compute_byte_size(row_bytes);
+        // `compute_byte_size()` returns `usize::MAX` if the size overflows.
+        if size == 0 || size == usize::MAX {
+            return Err(Result::InvalidParameters);
+        }
+        let mut bitmap = Bitmap::new();
+        if !bitmap.try_alloc_pixels_info(&info, row_bytes) {
+            return Err(Result::InternalError);
+        }
+        // the pixels are allocated and owned by the bitmap, nothing else refers to them yet.
+        let pixels = unsafe { std::slice::from_raw_parts_mut(bitmap.pixels() as *mut u8, size) };
+        match self.get_pixels_with_options(&info, pixels, row_bytes, options) {
+            Result::Success => {
+                // an immutable bitmap shares its pixels with the image instead of copying them.
+                bitmap.set_immutable();
+                Image::from_bitmap(&bitmap).ok_or(Result::InvalidConversion)
+            }
+            result => Err(result),
+        }
+    }
+
+    /// Decodes the image into a new raster image with `color_type` and `color_space`, see
+    /// [Self::get_image()].
+    ///
+    /// Opaque images stay opaque, all others are decoded to premultiplied pixels.
+    pub fn get_image_with_color(
+        &mut self,
+        color_type: ColorType,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> std::result::Result<Image, Result> {
+        let info = self.info();
+        let alpha_type = match info.alpha_type() {
+            AlphaType::Opaque => AlphaType::Opaque,
+            _ => AlphaType::Premul,
+        };
+        let info = info
+            .with_color_type(color_type)
+            .with_alpha_type(alpha_type)
+            .with_color_space(color_space);
+        self.get_image(info, None)
+    }
+
     unsafe fn native_options(options: &Options) -> SkCodec_Options {
         SkCodec_Options {
             fZeroInitialized: options.zero_initialized,
@@ -152,11 +306,8 @@ impl Codec {
     // TODO: getYUVAPlanes
     // TODO: startIncrementalDecode
     // TODO: incrementalDecode
-    // TODO: startScanlineDecode
-    // TODO: getScanlines
     // TODO: skipScanlines
     // TODO: ScanlineOrder
-    // TODO: getScanlineOrder
     // TODO: nextScanline
     // TODO: outputScanline
     // TODO: getFrameCount
@@ -167,3 +318,62 @@ impl Codec {
     // TODO: getRepetitionCount
     // TODO: Register
 }
+
+#[test]
+fn test_get_image_with_color() {
+    use crate::{Color, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((3, 2)).unwrap();
+    surface.canvas().clear(Color::RED);
+    let encoded = surface
+        .image_snapshot()
+        .encode_to_data(EncodedImageFormat::PNG)
+        .unwrap();
+
+    let mut codec = Codec::from_data(encoded).unwrap();
+    let color_space = ColorSpace::new_srgb_linear();
+    let image = codec
+        .get_image_with_color(ColorType::RGBAF16, color_space.clone())
+        .unwrap();
+    assert_eq!(image.dimensions(), ISize::new(3, 2));
+    assert_eq!(image.color_type(), ColorType::RGBAF16);
+    assert_eq!(image.color_space(), color_space);
+    assert!(!image.is_lazy_generated());
+
+    let unsupported = codec.info().with_dimensions((1, 1));
+    assert!(codec.get_image(unsupported, None).is_err());
+}
+
+#[test]
+fn test_get_pixels_with_progress() {
+    use crate::{Color, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((4, 40)).unwrap();
+    surface.canvas().clear(Color::RED);
+    let encoded = surface
+        .image_snapshot()
+        .encode_to_data(EncodedImageFormat::PNG)
+        .unwrap();
+
+    let mut codec = Codec::from_data(encoded).unwrap();
+    let info = codec.info();
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+
+    let mut reports = Vec::new();
+    let result =
+        codec.get_pixels_with_progress(&info, &mut pixels, row_bytes, None, |completed, total| {
+            reports.push((completed, total));
+            true
+        });
+    assert_eq!(result, Some(Result::Success));
+    assert_eq!(reports.first(), Some(&(0, 40)));
+    assert_eq!(reports.last(), Some(&(40, 40)));
+    assert!(reports.len() > 2);
+
+    let result =
+        codec.get_pixels_with_progress(&info, &mut pixels, row_bytes, None, |completed, _| {
+            completed < 16
+        });
+    assert_eq!(result, None);
+}
@@ -28,6 +28,9 @@ pub use color_filter::{color_filters, ColorFilter};
 mod color_space;
 pub use color_space::*;
 
+mod color_space_xform;
+pub use color_space_xform::*;
+
 pub mod contour_measure;
 pub use contour_measure::{ContourMeasure, ContourMeasureIter};
 
@@ -57,7 +60,7 @@ pub mod draw_looper;
 pub use draw_looper::DrawLooper;
 
 pub mod drawable;
-pub use drawable::Drawable;
+pub use drawable::{CustomDrawable, Drawable};
 
 mod encoded_image_format;
 pub use encoded_image_format::*;
@@ -99,6 +102,13 @@ pub mod graphics;
 pub mod image;
 pub use image::{Image, MipmapMode};
 
+#[cfg(feature = "image")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "image")))]
+mod image_crate;
+#[cfg(feature = "image")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "image")))]
+pub use image_crate::*;
+
 mod image_encoder;
 pub use image_encoder::*;
 
@@ -185,6 +195,9 @@ pub use rrect::RRect;
 mod rsxform;
 pub use rsxform::*;
 
+mod sampling_options;
+pub use sampling_options::*;
+
 mod scalar_;
 pub use scalar_::*;
 
@@ -194,6 +207,9 @@ pub use shader::{shaders, Shader};
 mod size;
 pub use size::*;
 
+mod skia_error;
+pub use skia_error::*;
+
 pub mod stroke_rec;
 pub use stroke_rec::StrokeRec;
 
@@ -1,3 +1,5 @@
+use crate::{AlphaType, Color4f, ColorType, ImageInfo, Surface};
+
 pub use skia_bindings::SkBlendMode as BlendMode;
 #[test]
 pub fn test_blend_mode_naming() {
@@ -9,3 +11,56 @@ pub use skia_bindings::SkBlendModeCoeff as BlendModeCoeff;
 pub fn test_blend_mode_coeff_naming() {
     let _ = BlendModeCoeff::IDA;
 }
+
+/// Blends the unpremultiplied color `src` onto `dst` with `mode` on the CPU and returns the
+/// unpremultiplied result, for example to preview the result of a blend mode without rendering.
+///
+/// The colors are blended by Skia's raster backend in floating point without color space
+/// conversion, so the result is the same as drawing `src` with `mode` onto a pixel of the color
+/// `dst` of a surface without a color space.
+pub fn blend_colors(mode: BlendMode, src: impl Into<Color4f>, dst: impl Into<Color4f>) -> Color4f {
+    let info = ImageInfo::new((1, 1), ColorType::RGBAF32, AlphaType::Premul, None);
+    let mut surface = Surface::new_raster(&info, None, None).unwrap();
+    let canvas = surface.canvas();
+    canvas.draw_color(dst, BlendMode::Src);
+    canvas.draw_color(src, mode);
+
+    let mut pixel = [0u8; 16];
+    let unpremul = info.with_alpha_type(AlphaType::Unpremul);
+    assert!(surface.read_pixels(&unpremul, &mut pixel, 16, (0, 0)));
+    let component = |i: usize| {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&pixel[i * 4..i * 4 + 4]);
+        f32::from_ne_bytes(bytes)
+    };
+    Color4f::new(component(0), component(1), component(2), component(3))
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_blend_colors() {
+    let red = Color4f::new(1.0, 0.0, 0.0, 1.0);
+    let half_blue = Color4f::new(0.0, 0.0, 1.0, 0.5);
+    let gray = Color4f::new(0.5, 0.5, 0.5, 1.0);
+
+    assert_eq!(blend_colors(BlendMode::SrcOver, red, gray), red);
+    assert_eq!(blend_colors(BlendMode::Dst, red, gray), gray);
+    assert_eq!(
+        blend_colors(BlendMode::SrcOver, half_blue, red),
+        Color4f::new(0.5, 0.0, 0.5, 1.0)
+    );
+    assert_eq!(
+        blend_colors(BlendMode::Multiply, red, gray),
+        Color4f::new(0.5, 0.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn test_name_and_coeff() {
+    assert_eq!(BlendMode::SrcOver.name(), "SrcOver");
+    assert_eq!(
+        BlendMode::SrcOver.as_coeff(),
+        Some((BlendModeCoeff::One, BlendModeCoeff::ISA))
+    );
+    assert_eq!(BlendMode::Multiply.as_coeff(), None);
+}
@@ -1,10 +1,12 @@
 #[cfg(feature = "gpu")]
 use crate::gpu;
+use crate::matrix::ScaleToFit;
 use crate::prelude::*;
+use crate::region::RegionOp;
 use crate::{
     scalar, Bitmap, BlendMode, ClipOp, Color, Color4f, Data, Font, IPoint, IRect, ISize, Image,
     ImageFilter, ImageInfo, Matrix, Paint, Path, Picture, Point, QuickReject, RRect, Rect, Region,
-    Shader, Surface, SurfaceProps, TextBlob, TextEncoding, Vector, Vertices, M44,
+    RoundOut, Shader, Surface, SurfaceProps, TextBlob, TextEncoding, Vector, Vertices, M44, V3,
 };
 use crate::{u8cpu, Drawable, Pixmap};
 use skia_bindings as sb;
@@ -124,9 +126,34 @@ pub struct TopLayerPixels<'a> {
     pub origin: IPoint,
 }
 
+/// The transformation and the clip of a [Canvas], captured with [Canvas::capture_state()].
+///
+/// External renderers that draw into the same target between Skia passes can use the state
+/// to transform and clip their drawing exactly like Skia does.
+#[derive(Clone)]
+pub struct State {
+    /// The transformation from local coordinates to device coordinates.
+    pub local_to_device: M44,
+    /// The clip in device coordinates. Anti-aliased clips are approximated by the pixels they
+    /// touch.
+    pub device_clip: Region,
+}
+
+impl State {
+    /// Returns the outline of the device clip as a path, which is empty if nothing is visible.
+    pub fn device_clip_path(&self) -> Path {
+        let mut path = Path::default();
+        self.device_clip.get_boundary_path(&mut path);
+        path
+    }
+}
+
 /// The canvas type that is returned when it is owned by another instance,
 /// like Surface, for example. For these cases, the Canvas' reference that is
 /// returned is bound to the lifetime of the owner.
+///
+/// A canvas is neither [Send] nor [Sync], so functions that only query or read from it, like
+/// the `read_pixels` variants, take `&self`, even though Skia may update internal state.
 #[repr(transparent)]
 pub struct Canvas(SkCanvas);
 
@@ -222,6 +249,35 @@ impl Canvas {
         Self::from_raster_direct(&info, pixels_u8, row_bytes, None)
     }
 
+    /// Creates a canvas that draws directly into the pixels of `pixmap`, without the overhead of
+    /// a [Surface], for example to rasterize a cursor into an existing buffer. Use
+    /// [Canvas::from_raster_direct()] to draw into a `&mut [u8]`.
+    ///
+    /// Returns [None] if the pixmap has no pixels, or if its [ImageInfo] is not supported by
+    /// raster canvases.
+    ///
+    /// # Safety
+    ///
+    /// A [Pixmap] does not track whether its pixels may be written. The caller must ensure that
+    /// they are writable and that nothing else reads or writes them while the canvas exists.
+    /// This is not the case for pixmaps over shared slices or of [crate::Image]s.
+    pub unsafe fn from_pixmap<'pixels>(
+        pixmap: &'pixels mut Pixmap,
+        props: Option<&SurfaceProps>,
+    ) -> Option<OwnedCanvas<'pixels>> {
+        let pixels = pixmap.native().fPixels;
+        if pixels.is_null() {
+            return None;
+        }
+        let ptr = sb::C_SkCanvas_MakeRasterDirect(
+            pixmap.info().native(),
+            pixels as _,
+            pixmap.row_bytes(),
+            props.native_ptr_or_null(),
+        );
+        Self::own_from_native_ptr(ptr)
+    }
+
     #[allow(clippy::new_ret_no_self)]
     // Decided to call this variant new, because it seems to be the simplest reasonable one.
     // TODO: Support impl Into<Option<&'a SurfaceProps>>?
@@ -244,6 +300,8 @@ impl Canvas {
         }
     }
 
+    /// Creates a canvas that draws into the pixels of `bitmap`. The canvas shares the pixels
+    /// with the bitmap and keeps them alive.
     // TODO: Support impl Into<Option<&'a SurfaceProps>>?
     pub fn from_bitmap<'lt>(bitmap: &Bitmap, props: Option<&SurfaceProps>) -> OwnedCanvas<'lt> {
         let props_ptr = props.native_ptr_or_null();
@@ -304,9 +362,9 @@ impl Canvas {
 
     #[cfg(feature = "gpu")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
-    pub fn recording_context(&mut self) -> Option<gpu::RecordingContext> {
+    pub fn recording_context(&self) -> Option<gpu::RecordingContext> {
         gpu::RecordingContext::from_unshared_ptr(unsafe {
-            sb::C_SkCanvas_recordingContext(self.native_mut())
+            sb::C_SkCanvas_recordingContext(self.native_mut_force())
         })
     }
 
@@ -357,7 +415,7 @@ impl Canvas {
 
     #[must_use]
     pub fn read_pixels(
-        &mut self,
+        &self,
         info: &ImageInfo,
         dst_pixels: &mut [u8],
         dst_row_bytes: usize,
@@ -367,7 +425,7 @@ impl Canvas {
         let required_size = info.compute_byte_size(dst_row_bytes);
         (dst_pixels.len() >= required_size)
             && unsafe {
-                self.native_mut().readPixels(
+                (*self.native_mut_force()).readPixels(
                     info.native(),
                     dst_pixels.as_mut_ptr() as _,
                     dst_row_bytes,
@@ -378,15 +436,15 @@ impl Canvas {
     }
 
     #[must_use]
-    pub fn read_pixels_to_pixmap(&mut self, pixmap: &mut Pixmap, src: impl Into<IPoint>) -> bool {
+    pub fn read_pixels_to_pixmap(&self, pixmap: &mut Pixmap, src: impl Into<IPoint>) -> bool {
         let src = src.into();
-        unsafe { self.native_mut().readPixels1(pixmap.native(), src.x, src.y) }
+        unsafe { (*self.native_mut_force()).readPixels1(pixmap.native(), src.x, src.y) }
     }
 
     #[must_use]
-    pub fn read_pixels_to_bitmap(&mut self, bitmap: &mut Bitmap, src: impl Into<IPoint>) -> bool {
+    pub fn read_pixels_to_bitmap(&self, bitmap: &mut Bitmap, src: impl Into<IPoint>) -> bool {
         let src = src.into();
-        unsafe { self.native_mut().readPixels2(bitmap.native(), src.x, src.y) }
+        unsafe { (*self.native_mut_force()).readPixels2(bitmap.native(), src.x, src.y) }
     }
 
     // TODO: that (pixels, row_bytes) pair is probably worth abstracting over.
@@ -448,6 +506,20 @@ impl Canvas {
         self
     }
 
+    /// Saves the canvas state and returns a guard that restores it when it is dropped, so that
+    /// early returns can not leave the save and restore calls unbalanced.
+    pub fn auto_save(&mut self) -> AutoRestoredCanvas {
+        AutoCanvasRestore::guard(self, true)
+    }
+
+    /// Saves the canvas state and allocates a layer like [Self::save_layer()] and returns a guard
+    /// that draws the layer and restores the state when it is dropped.
+    pub fn auto_save_layer(&mut self, layer_rec: &SaveLayerRec) -> AutoRestoredCanvas {
+        let mut canvas = AutoCanvasRestore::guard(self, false);
+        canvas.save_layer(layer_rec);
+        canvas
+    }
+
     pub fn save_count(&self) -> usize {
         unsafe { self.native().getSaveCount() }.try_into().unwrap()
     }
@@ -482,6 +554,8 @@ impl Canvas {
         self
     }
 
+    /// Pre-concatenates a 3x3 matrix. Skia tracks the matrix of a canvas as [M44], prefer
+    /// [Self::concat_44()] for new code.
     pub fn concat(&mut self, matrix: &Matrix) -> &mut Self {
         unsafe { self.native_mut().concat(matrix.native()) }
         self
@@ -492,6 +566,23 @@ impl Canvas {
         self
     }
 
+    /// Translates by `x`, `y` and `z`, the [M44] equivalent of [Self::translate()].
+    pub fn translate_3d(&mut self, (x, y, z): (scalar, scalar, scalar)) -> &mut Self {
+        self.concat_44(&M44::translate(x, y, z))
+    }
+
+    /// Scales by `x`, `y` and `z`, the [M44] equivalent of [Self::scale()].
+    pub fn scale_3d(&mut self, (x, y, z): (scalar, scalar, scalar)) -> &mut Self {
+        self.concat_44(&M44::scale(x, y, z))
+    }
+
+    /// Rotates by `radians` around `axis`, which must not be zero. Rotating around the z axis
+    /// is equivalent to [Self::rotate()], which takes degrees.
+    pub fn rotate_3d(&mut self, axis: V3, radians: scalar) -> &mut Self {
+        self.concat_44(&M44::rotate(axis, radians))
+    }
+
+    /// Replaces the current matrix. A 3x3 [Matrix] can be passed with `&M44::from(matrix)`.
     pub fn set_matrix(&mut self, matrix: &M44) -> &mut Self {
         unsafe { self.native_mut().setMatrix(matrix.native()) }
         self
@@ -585,6 +676,10 @@ impl Canvas {
         r.is_empty().if_false_some(r)
     }
 
+    /// Fills the clip with `color`, blended with `mode`, which defaults to
+    /// [BlendMode::SrcOver]. The matrix, and the shader, mask filter, and image filter of a
+    /// paint do not apply, so this is the cheapest way to fill or tint the whole canvas, for
+    /// example with [BlendMode::Modulate] or [BlendMode::Multiply].
     pub fn draw_color(
         &mut self,
         color: impl Into<Color4f>,
@@ -597,6 +692,8 @@ impl Canvas {
         self
     }
 
+    /// Replaces the pixels in the clip with `color`, same as [Self::draw_color()] with
+    /// [BlendMode::Src].
     pub fn clear(&mut self, color: impl Into<Color4f>) -> &mut Self {
         self.draw_color(color, BlendMode::Src)
     }
@@ -606,6 +703,11 @@ impl Canvas {
         self
     }
 
+    /// Fills the clip with `paint`, including its shader and blend mode, as if an infinite
+    /// rectangle was drawn. Unlike drawing a rectangle of the size of the canvas, this does
+    /// not need to know the size, and also fills the clip if the matrix is rotated.
+    ///
+    /// The style and the mask filter of the paint are ignored.
     pub fn draw_paint(&mut self, paint: &Paint) -> &mut Self {
         unsafe { self.native_mut().drawPaint(paint.native()) }
         self
@@ -659,6 +761,7 @@ impl Canvas {
         self
     }
 
+    /// Draws the ellipse that is inscribed in `oval`. Nothing is drawn if `oval` is empty.
     pub fn draw_oval(&mut self, oval: impl AsRef<Rect>, paint: &Paint) -> &mut Self {
         unsafe {
             self.native_mut()
@@ -705,6 +808,16 @@ impl Canvas {
         self
     }
 
+    /// Draws an arc of the ellipse that is inscribed in `oval`.
+    ///
+    /// The angles are in degrees. A `start_angle` of zero is at the right middle edge of `oval`,
+    /// positive `sweep_angle`s run clockwise, negative ones counterclockwise. Sweeps of 360
+    /// degrees or more draw the complete ellipse once, they do not wrap around. Nothing is drawn
+    /// if `oval` is empty or `sweep_angle` is zero.
+    ///
+    /// If `use_center` is `true`, the lines from the center of `oval` to the end points of the
+    /// arc are included, which draws a wedge, see [Self::draw_pie()]. Otherwise a filled arc
+    /// is closed by the chord between its end points.
     pub fn draw_arc(
         &mut self,
         oval: impl AsRef<Rect>,
@@ -725,6 +838,20 @@ impl Canvas {
         self
     }
 
+    /// Draws a wedge of the ellipse inscribed in `oval`, which is [Self::draw_arc()] with
+    /// `use_center` set to `true`.
+    pub fn draw_pie(
+        &mut self,
+        oval: impl AsRef<Rect>,
+        start_angle: scalar,
+        sweep_angle: scalar,
+        paint: &Paint,
+    ) -> &mut Self {
+        self.draw_arc(oval, start_angle, sweep_angle, true, paint)
+    }
+
+    /// Draws `rect` with corners that are rounded with the radii `rx` and `ry`. Radii that are
+    /// too large for `rect` are scaled down, radii that are zero or negative draw a rectangle.
     pub fn draw_round_rect(
         &mut self,
         rect: impl AsRef<Rect>,
@@ -739,6 +866,17 @@ impl Canvas {
         self
     }
 
+    /// Draws `rect` with individually rounded corners. `radii` contains the x and y radii of the
+    /// upper left, upper right, lower right, and lower left corner, see [RRect::new_rect_radii()].
+    pub fn draw_round_rect_radii(
+        &mut self,
+        rect: impl AsRef<Rect>,
+        radii: &[Vector; 4],
+        paint: &Paint,
+    ) -> &mut Self {
+        self.draw_rrect(RRect::new_rect_radii(rect, radii), paint)
+    }
+
     pub fn draw_path(&mut self, path: &Path, paint: &Paint) -> &mut Self {
         unsafe { self.native_mut().drawPath(path.native(), paint.native()) }
         self
@@ -790,6 +928,103 @@ impl Canvas {
         self
     }
 
+    /// Draws the `src` area of `image` to `dst` like [Self::draw_image_rect()], but splits the
+    /// image into tiles that are not larger than `max_tile_size` pixels in each dimension, for
+    /// example for images that are larger than the maximum texture size of the GPU.
+    ///
+    /// `max_tile_size` defaults to the maximum texture size of the canvas' GPU context. If there
+    /// is none, or the image fits into one tile, the image is drawn without tiling.
+    ///
+    /// Only the tiles that intersect the clip are drawn. Each tile is cut from the image with a
+    /// margin, so that filtering samples across the tile edges like it would for the whole image.
+    /// The tiles are drawn without anti-aliasing, because anti-aliased tile edges would leave
+    /// visible seams under fractional or rotated matrices.
+    pub fn draw_image_rect_tiled(
+        &mut self,
+        image: impl AsRef<Image>,
+        src: Option<&Rect>,
+        dst: impl AsRef<Rect>,
+        paint: &Paint,
+        max_tile_size: impl Into<Option<i32>>,
+    ) -> &mut Self {
+        // covers the filter kernels of bilinear and bicubic sampling.
+        const MARGIN: i32 = 2;
+
+        let image = image.as_ref();
+        let image_bounds = image.bounds();
+        let src = match src {
+            Some(src) => *src,
+            None => Rect::from(image_bounds),
+        };
+        let src_bounds: IRect = src.round_out();
+        let tile_size = max_tile_size
+            .into()
+            .or_else(|| self.max_texture_size())
+            .filter(|&tile_size| {
+                tile_size > 2 * MARGIN
+                    && (src_bounds.width() > tile_size || src_bounds.height() > tile_size)
+            });
+        let matrix = Matrix::from_rect_to_rect(src, &dst, ScaleToFit::Fill);
+        let (tile_size, matrix) = match (tile_size, matrix) {
+            (Some(tile_size), Some(matrix)) => (tile_size, matrix),
+            _ => {
+                let src = Some((&src, SrcRectConstraint::Strict));
+                return self.draw_image_rect(image, src, dst, paint);
+            }
+        };
+
+        let mut paint = paint.clone();
+        paint.set_anti_alias(false);
+        let step = tile_size - 2 * MARGIN;
+        let count = self.save();
+        self.concat(&matrix);
+        let clip = self.local_clip_bounds();
+        for y in (src_bounds.top..src_bounds.bottom).step_by(step as usize) {
+            for x in (src_bounds.left..src_bounds.right).step_by(step as usize) {
+                let mut tile_src = Rect::from(IRect::from_xywh(x, y, step, step));
+                if !tile_src.intersect(src) {
+                    continue;
+                }
+                if let Some(clip) = &clip {
+                    if !clip.intersects(tile_src) {
+                        continue;
+                    }
+                }
+                let subset_bounds = match IRect::intersect(
+                    &IRect::from_xywh(x, y, step, step).with_outset((MARGIN, MARGIN)),
+                    &image_bounds,
+                ) {
+                    Some(subset_bounds) => subset_bounds,
+                    None => continue,
+                };
+                if let Some(subset) = image.new_subset(subset_bounds) {
+                    let offset =
+                        Vector::new(-subset_bounds.left as scalar, -subset_bounds.top as scalar);
+                    let subset_src = tile_src.with_offset(offset);
+                    self.draw_image_rect(
+                        subset,
+                        Some((&subset_src, SrcRectConstraint::Fast)),
+                        tile_src,
+                        &paint,
+                    );
+                }
+            }
+        }
+        self.restore_to_count(count);
+        self
+    }
+
+    #[cfg(feature = "gpu")]
+    fn max_texture_size(&mut self) -> Option<i32> {
+        self.recording_context()
+            .map(|context| context.max_texture_size())
+    }
+
+    #[cfg(not(feature = "gpu"))]
+    fn max_texture_size(&mut self) -> Option<i32> {
+        None
+    }
+
     pub fn draw_image_nine(
         &mut self,
         image: impl AsRef<Image>,
@@ -952,6 +1187,27 @@ impl Canvas {
         self
     }
 
+    /// Draws `image` warped onto a mesh of `columns` x `rows` cells, whose corners are moved to
+    /// the points in `dst`, see [Vertices::new_image_mesh()].
+    ///
+    /// The image is sampled with the filter quality of `paint`, and drawn with its other
+    /// settings, except for the shader, which is replaced by the image.
+    pub fn draw_image_mesh(
+        &mut self,
+        image: impl AsRef<Image>,
+        (columns, rows): (usize, usize),
+        dst: &[Point],
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let image = image.as_ref();
+        if let Some(vertices) = Vertices::new_image_mesh(image.dimensions(), (columns, rows), dst) {
+            let mut paint = paint.cloned().unwrap_or_default();
+            paint.set_shader(image.to_shader_with_quality(None, None, paint.filter_quality()));
+            self.draw_vertices(&vertices, None, &paint);
+        }
+        self
+    }
+
     pub fn draw_patch(
         &mut self,
         cubics: &[Point; 12],
@@ -1018,6 +1274,7 @@ impl Canvas {
         M44::construct(|m| unsafe { sb::C_SkCanvas_getLocalToDevice(self.native(), m) })
     }
 
+    /// The current matrix reduced to 3x3, see [Self::local_to_device()] for the full [M44].
     pub fn total_matrix(&self) -> Matrix {
         let mut matrix = Matrix::default();
         // TODO: why is Matrix not safe to return from getTotalMatrix()
@@ -1026,6 +1283,54 @@ impl Canvas {
         matrix
     }
 
+    /// Returns the clip in device coordinates. Anti-aliased clips are approximated by the
+    /// pixels they touch.
+    ///
+    /// This is cheap if [Self::is_clip_rect()] returns `true`, otherwise the region is
+    /// computed from the clip stack. Use [Self::device_clip_bounds()] if the bounds are
+    /// sufficient.
+    pub fn device_clip_region(&self) -> Region {
+        let mut region = Region::new();
+        unsafe { (*self.native_mut_force()).temporary_internal_getRgnClip(region.native_mut()) }
+        region
+    }
+
+    /// Returns the device bounds of the pixels `rect` may cover when it is drawn with the
+    /// current matrix and clip, or `None` if it is clipped out entirely.
+    ///
+    /// Accessibility frameworks and hit testing can use this to get the on-screen bounds of
+    /// a clipped element.
+    pub fn visible_device_bounds(&self, rect: impl AsRef<Rect>) -> Option<IRect> {
+        let (mapped, _) = self.total_matrix().map_rect(rect);
+        let bounds: IRect = mapped.round_out();
+        let mut visible = self.device_clip_region();
+        visible.op_rect(bounds, RegionOp::Intersect);
+        if visible.is_empty() {
+            return None;
+        }
+        Some(*visible.bounds())
+    }
+
+    /// Captures the current matrix and the device clip.
+    pub fn capture_state(&self) -> State {
+        State {
+            local_to_device: self.local_to_device(),
+            device_clip: self.device_clip_region(),
+        }
+    }
+
+    /// Replaces the current matrix with the one of `state` and intersects the clip with its
+    /// device clip.
+    ///
+    /// A clip can only be reduced, so to apply a state that has a larger clip than the current
+    /// one, call [Self::restore()] or [Self::restore_to_count()] first to get back to a save
+    /// level with a clip that contains it. Wrap the call in [Self::save()] and
+    /// [Self::restore()] to undo it later.
+    pub fn restore_state(&mut self, state: &State) -> &mut Self {
+        self.set_matrix(&state.local_to_device)
+            .clip_region(&state.device_clip, ClipOp::Intersect)
+    }
+
     //
     // internal helper
     //
@@ -1169,7 +1474,7 @@ impl<'a> AutoRestoredCanvas<'a> {
 pub enum AutoCanvasRestore {}
 
 impl AutoCanvasRestore {
-    // TODO: rename to save(), add a method to Canvas, perhaps named auto_restored()?
+    // TODO: rename to save()?
     pub fn guard(canvas: &mut Canvas, do_save: bool) -> AutoRestoredCanvas {
         let restore = construct(|acr| unsafe {
             sb::C_SkAutoCanvasRestore_Construct(acr, canvas.native_mut(), do_save)
@@ -1183,9 +1488,107 @@ impl AutoCanvasRestore {
 mod tests {
     use crate::{
         canvas::SaveLayerFlags, canvas::SaveLayerRec, AlphaType, Canvas, ClipOp, Color, ColorType,
-        ImageInfo, Matrix, OwnedCanvas, Rect,
+        ImageInfo, Matrix, OwnedCanvas, Pixmap, Rect, M44, V3,
     };
 
+    #[test]
+    fn test_capture_and_restore_state() {
+        let mut surface = crate::Surface::new_raster_n32_premul((100, 100)).unwrap();
+        let canvas = surface.canvas();
+        canvas.save();
+        canvas.translate((10.0, 20.0));
+        canvas.clip_rect(Rect::new(0.0, 0.0, 30.0, 40.0), None, None);
+        let state = canvas.capture_state();
+        assert!(state.local_to_device == canvas.local_to_device());
+        assert_eq!(
+            *state.device_clip.bounds(),
+            crate::IRect::new(10, 20, 40, 60)
+        );
+        assert_eq!(
+            state.device_clip_path().bounds(),
+            &Rect::new(10.0, 20.0, 40.0, 60.0)
+        );
+        canvas.restore();
+
+        canvas.save();
+        canvas.restore_state(&state);
+        assert!(canvas.local_to_device() == state.local_to_device);
+        assert_eq!(
+            canvas.device_clip_bounds(),
+            Some(crate::IRect::new(10, 20, 40, 60))
+        );
+        canvas.restore();
+    }
+
+    #[test]
+    fn test_visible_device_bounds() {
+        let mut surface = crate::Surface::new_raster_n32_premul((100, 100)).unwrap();
+        let canvas = surface.canvas();
+        canvas.translate((10.0, 10.0));
+        canvas.clip_rect(Rect::new(0.0, 0.0, 50.0, 50.0), None, None);
+        assert!(canvas.is_clip_rect());
+        assert_eq!(
+            *canvas.device_clip_region().bounds(),
+            crate::IRect::new(10, 10, 60, 60)
+        );
+        assert_eq!(
+            canvas.visible_device_bounds(Rect::new(40.0, -5.0, 80.0, 20.5)),
+            Some(crate::IRect::new(50, 10, 60, 31))
+        );
+        assert_eq!(
+            canvas.visible_device_bounds(Rect::new(60.0, 0.0, 70.0, 10.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_draw_color_and_draw_paint_fill_the_clip() {
+        use crate::{BlendMode, Paint, Shader};
+
+        let mut surface = crate::Surface::new_raster_n32_premul((10, 10)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.save();
+        canvas.clip_rect(Rect::new(0.0, 0.0, 5.0, 10.0), None, None);
+        canvas.rotate(30.0, None);
+        canvas.draw_color(Color::from_rgb(255, 0, 0), BlendMode::Modulate);
+        canvas.restore();
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((0, 9)), Color::RED);
+        assert_eq!(pixmap.get_color((4, 0)), Color::RED);
+        assert_eq!(pixmap.get_color((5, 0)), Color::WHITE);
+        drop(pixmap);
+
+        let canvas = surface.canvas();
+        canvas.save();
+        canvas.clip_rect(Rect::new(5.0, 0.0, 10.0, 10.0), None, None);
+        let mut paint = Paint::default();
+        paint.set_shader(Shader::color(Color::BLUE));
+        canvas.draw_paint(&paint);
+        canvas.restore();
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((4, 0)), Color::RED);
+        assert_eq!(pixmap.get_color((9, 9)), Color::BLUE);
+    }
+
+    #[test]
+    fn test_from_pixmap() {
+        use std::slice;
+
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        {
+            // the pixmap is created from the mutable buffer, which is not accessed otherwise
+            // while the canvas exists.
+            let pixels = unsafe { slice::from_raw_parts(pixels.as_mut_ptr(), pixels.len()) };
+            let mut pixmap = Pixmap::new(&info, pixels, info.min_row_bytes());
+            let mut canvas = unsafe { Canvas::from_pixmap(&mut pixmap, None) }.unwrap();
+            canvas.clear(Color::RED);
+        }
+        assert_eq!(pixels[60..], [0xff, 0, 0, 0xff]);
+        assert!(unsafe { Canvas::from_pixmap(&mut Pixmap::default(), None) }.is_none());
+    }
+
     #[test]
     fn test_raster_direct_creation_and_clear_in_memory() {
         let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
@@ -1230,6 +1633,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_auto_save() {
+        let mut canvas = Canvas::new((16, 16), None).unwrap();
+        assert_eq!(canvas.save_count(), 1);
+        {
+            let mut canvas = canvas.auto_save();
+            canvas.translate((4.0, 4.0));
+            assert_eq!(canvas.save_count(), 2);
+        }
+        assert_eq!(canvas.save_count(), 1);
+        assert!(canvas.total_matrix().is_identity());
+
+        let bounds = Rect::from_wh(8.0, 8.0);
+        {
+            let mut canvas = canvas.auto_save_layer(&SaveLayerRec::default().bounds(&bounds));
+            canvas.save();
+            canvas.clear(Color::RED);
+            assert_eq!(canvas.save_count(), 3);
+        }
+        assert_eq!(canvas.save_count(), 1);
+    }
+
     #[test]
     fn test_total_matrix() {
         let mut c = Canvas::new((2, 2), None).unwrap();
@@ -1240,6 +1665,20 @@ mod tests {
         assert_ne!(Matrix::default(), total);
     }
 
+    #[test]
+    fn test_3d_transforms() {
+        let mut c = Canvas::new((2, 2), None).unwrap();
+        c.translate_3d((1.0, 2.0, 3.0)).scale_3d((2.0, 2.0, 2.0));
+        assert_eq!(
+            c.local_to_device(),
+            &M44::translate(1.0, 2.0, 3.0) * &M44::scale(2.0, 2.0, 2.0)
+        );
+        c.reset_matrix()
+            .rotate_3d(V3::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2);
+        let mapped = c.total_matrix().map_xy(1.0, 0.0);
+        assert!(mapped.x.abs() < 1e-6 && (mapped.y - 1.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_make_surface() {
         let mut pixels: [u32; 4] = Default::default();
@@ -1263,6 +1702,97 @@ mod tests {
         c.clip_rect(Rect::default(), ClipOp::Difference, true);
     }
 
+    #[test]
+    fn test_draw_image_rect_tiled() {
+        use crate::{Paint, Surface};
+
+        let mut source = Surface::new_raster_n32_premul((50, 30)).unwrap();
+        for i in 0..50 {
+            let color = Color::from_rgb((i * 5) as u8, 255 - (i * 5) as u8, (i * 13) as u8);
+            let rect = Rect::from_xywh(i as f32, (i % 7) as f32, 1.0, 30.0);
+            let mut paint = Paint::default();
+            paint.set_color(color);
+            source.canvas().draw_rect(rect, &paint);
+        }
+        let image = source.image_snapshot();
+
+        let draw = |tile_size: i32| {
+            let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+            let dst = Rect::from_xywh(3.0, 5.0, 40.0, 24.0);
+            let src = Rect::from_xywh(5.0, 3.0, 40.0, 24.0);
+            surface.canvas().draw_image_rect_tiled(
+                &image,
+                Some(&src),
+                dst,
+                &Paint::default(),
+                tile_size,
+            );
+            let mut pixels = vec![0u8; 64 * 64 * 4];
+            let info = surface.image_info();
+            assert!(surface.read_pixels(&info, &mut pixels, info.min_row_bytes(), (0, 0)));
+            pixels
+        };
+
+        assert_eq!(draw(8), draw(4096));
+    }
+
+    #[test]
+    fn test_draw_pie_and_round_rect_radii() {
+        use crate::{Paint, Surface, Vector};
+
+        let mut surface = Surface::new_raster_n32_premul((40, 40)).unwrap();
+        let mut paint = Paint::default();
+        paint.set_color(Color::BLACK);
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        // the lower right quarter.
+        canvas.draw_pie(Rect::from_wh(40.0, 40.0), 0.0, 90.0, &paint);
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((25, 25)), Color::BLACK);
+        assert_eq!(pixmap.get_color((15, 15)), Color::WHITE);
+        assert_eq!(pixmap.get_color((25, 15)), Color::WHITE);
+
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let radii = [
+            Vector::new(20.0, 20.0),
+            Vector::default(),
+            Vector::default(),
+            Vector::default(),
+        ];
+        canvas.draw_round_rect_radii(Rect::from_wh(40.0, 40.0), &radii, &paint);
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((1, 1)), Color::WHITE);
+        assert_eq!(pixmap.get_color((38, 1)), Color::BLACK);
+        assert_eq!(pixmap.get_color((1, 38)), Color::BLACK);
+    }
+
+    #[test]
+    fn test_draw_image_mesh() {
+        use crate::{Point, Surface};
+
+        let mut source = Surface::new_raster_n32_premul((2, 2)).unwrap();
+        source.canvas().clear(Color::BLUE);
+        let image = source.image_snapshot();
+
+        let mut surface = Surface::new_raster_n32_premul((40, 40)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        // a triangle-like quad, the lower right corner is moved to the lower left.
+        let dst = [
+            Point::new(0.0, 0.0),
+            Point::new(40.0, 0.0),
+            Point::new(0.0, 40.0),
+            Point::new(0.0, 40.0),
+        ];
+        surface.canvas().draw_image_mesh(&image, (1, 1), &dst, None);
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::BLUE);
+        assert_eq!(pixmap.get_color((35, 35)), Color::WHITE);
+    }
+
     /// Regression test for: https://github.com/rust-skia/rust-skia/issues/427
     #[test]
     fn test_local_and_device_clip_bounds() {
@@ -14,6 +14,7 @@ use std::ops::{BitAnd, BitOr, Index, IndexMut, Mul};
 // argb fields.
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
 #[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color(SkColor);
 
 impl NativeTransmutable<SkColor> for Color {}
@@ -126,9 +127,20 @@ impl Color {
     pub fn to_hsv(self) -> HSV {
         self.to_rgb().to_hsv()
     }
+
+    pub fn to_hsl(self) -> HSL {
+        Color4f::from(self).to_hsl()
+    }
+
+    /// The contrast ratio between this and `other` as defined by WCAG 2, see
+    /// [Color4f::contrast_ratio()].
+    pub fn contrast_ratio(self, other: impl Into<Color4f>) -> f32 {
+        Color4f::from(self).contrast_ratio(&other.into())
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RGB {
     pub r: u8,
     pub g: u8,
@@ -161,6 +173,7 @@ impl RGB {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HSV {
     pub h: f32,
     pub s: f32,
@@ -179,6 +192,60 @@ impl HSV {
             SkHSVToColor(alpha.into(), [self.h, self.s, self.v].as_ptr())
         })
     }
+
+    /// Converts to an unpremultiplied [Color4f] without the precision loss of [Self::to_color()].
+    pub fn to_color4f(&self, alpha: f32) -> Color4f {
+        let (r, g, b) = hue_to_rgb(self.h, self.s * self.v, self.v - self.s * self.v);
+        Color4f::new(r, g, b, alpha)
+    }
+}
+
+/// A color in the HSL color model, with a hue in degrees in the range `0..360`, and saturation
+/// and lightness in the range `0..=1`, like [HSV].
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HSL {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl From<(f32, f32, f32)> for HSL {
+    fn from((h, s, l): (f32, f32, f32)) -> Self {
+        Self { h, s, l }
+    }
+}
+
+impl HSL {
+    pub fn to_color(&self, alpha: u8) -> Color {
+        fn c(f: f32) -> u8 {
+            (f.max(0.0).min(1.0) * 255.0).round() as u8
+        }
+        let rgb = self.to_color4f(1.0);
+        Color::from_argb(alpha, c(rgb.r), c(rgb.g), c(rgb.b))
+    }
+
+    pub fn to_color4f(&self, alpha: f32) -> Color4f {
+        let chroma = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let (r, g, b) = hue_to_rgb(self.h, chroma, self.l - chroma / 2.0);
+        Color4f::new(r, g, b, alpha)
+    }
+}
+
+/// Returns the RGB components for a hue in degrees, a chroma, and the value of the smallest
+/// component.
+fn hue_to_rgb(hue: f32, chroma: f32, min: f32) -> (f32, f32, f32) {
+    let h = (hue.rem_euclid(360.0) / 60.0).min(6.0);
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    (r + min, g + min, b + min)
 }
 
 pub type PMColor = SkPMColor;
@@ -215,6 +282,7 @@ bitflags! {
 
 #[derive(Clone, PartialEq, Debug)]
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color4f {
     pub r: f32,
     pub g: f32,
@@ -349,8 +417,84 @@ impl Color4f {
     }
 
     // TODO: FromPMColor
-    // TODO: premul()
-    // TODO: unpremul()
+
+    /// Returns the color with its color components multiplied by alpha.
+    #[must_use]
+    pub fn premul(&self) -> Self {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// Returns the color with its color components divided by alpha, or transparent black if
+    /// alpha is zero.
+    #[must_use]
+    #[allow(clippy::float_cmp)]
+    pub fn unpremul(&self) -> Self {
+        if self.a == 0.0 {
+            Self::new(0.0, 0.0, 0.0, 0.0)
+        } else {
+            let inv_a = 1.0 / self.a;
+            Self::new(self.r * inv_a, self.g * inv_a, self.b * inv_a, self.a)
+        }
+    }
+
+    /// Converts the unpremultiplied color components to [HSV], with the same conversion as
+    /// [RGB::to_hsv()].
+    pub fn to_hsv(&self) -> HSV {
+        let (max, delta, hue) = self.max_delta_hue();
+        let s = if max > 0.0 { delta / max } else { 0.0 };
+        HSV { h: hue, s, v: max }
+    }
+
+    /// Converts the unpremultiplied color components to [HSL].
+    pub fn to_hsl(&self) -> HSL {
+        let (max, delta, hue) = self.max_delta_hue();
+        let l = max - delta / 2.0;
+        let s = if l > 0.0 && l < 1.0 {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        } else {
+            0.0
+        };
+        HSL { h: hue, s, l }
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn max_delta_hue(&self) -> (f32, f32, f32) {
+        let (r, g, b) = (self.r, self.g, self.b);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        if delta <= 0.0 {
+            return (max, 0.0, 0.0);
+        }
+        let h = if r == max {
+            (g - b) / delta
+        } else if g == max {
+            2.0 + (b - r) / delta
+        } else {
+            4.0 + (r - g) / delta
+        };
+        let h = h * 60.0;
+        (max, delta, if h < 0.0 { h + 360.0 } else { h })
+    }
+
+    /// The relative luminance of the unpremultiplied color components as defined by WCAG 2,
+    /// which treats them as sRGB encoded and ignores alpha.
+    pub fn luminance(&self) -> f32 {
+        fn linear(c: f32) -> f32 {
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// The contrast ratio between this and `other` as defined by WCAG 2, in the range `1..=21`.
+    pub fn contrast_ratio(&self, other: &Color4f) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
     // TODO: toBytes_RGBA()
     // TODO: FromBytes_RGBA
 
@@ -399,3 +543,57 @@ pub fn color_color4f_conversion() {
     let c2 = cf.to_color();
     assert_eq!(c, c2);
 }
+
+#[test]
+#[allow(clippy::float_cmp)]
+pub fn color4f_premul() {
+    let c = Color4f::new(0.5, 1.0, 0.0, 0.5);
+    assert_eq!(c.premul(), Color4f::new(0.25, 0.5, 0.0, 0.5));
+    assert_eq!(c.premul().unpremul(), c);
+    assert_eq!(
+        Color4f::new(1.0, 1.0, 1.0, 0.0).unpremul(),
+        colors::TRANSPARENT
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+pub fn hsv_hsl_conversions() {
+    fn assert_close(a: &Color4f, b: &Color4f) {
+        for i in 0..4 {
+            assert!((a[i] - b[i]).abs() < 1e-5, "{:?} != {:?}", a, b);
+        }
+    }
+
+    for &color in &[
+        Color::RED,
+        Color::GREEN,
+        Color::BLUE,
+        Color::YELLOW,
+        Color::CYAN,
+        Color::MAGENTA,
+        Color::GRAY,
+        Color::from_rgb(12, 200, 99),
+    ] {
+        let c4f = Color4f::from(color);
+        let hsv = color.to_hsv();
+        let hsv4f = c4f.to_hsv();
+        assert!((hsv.h - hsv4f.h).abs() < 1e-3);
+        assert!((hsv.s - hsv4f.s).abs() < 1e-5);
+        assert!((hsv.v - hsv4f.v).abs() < 1e-5);
+        assert_close(&hsv4f.to_color4f(1.0), &c4f);
+        assert_close(&c4f.to_hsl().to_color4f(1.0), &c4f);
+        assert_eq!(color.to_hsl().to_color(0xff), color);
+    }
+
+    let hsl = Color::RED.to_hsl();
+    assert_eq!((hsl.h, hsl.s, hsl.l), (0.0, 1.0, 0.5));
+}
+
+#[test]
+pub fn contrast_ratio() {
+    assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 1e-3);
+    assert!((Color::WHITE.contrast_ratio(Color::WHITE) - 1.0).abs() < 1e-6);
+    let gray = Color::from_rgb(0x76, 0x76, 0x76);
+    assert!(gray.contrast_ratio(Color::WHITE) > 4.5);
+}
@@ -32,6 +32,8 @@ impl NativeFlattenable for SkColorFilter {
 }
 
 impl ColorFilter {
+    /// Returns the color and blend mode if the filter blends a constant color into its input,
+    /// like the filters created with [color_filters::blend()].
     pub fn to_a_color_mode(&self) -> Option<(Color, BlendMode)> {
         let mut color: Color = 0.into();
         let mut mode: BlendMode = Default::default();
@@ -39,6 +41,8 @@ impl ColorFilter {
             .if_true_some((color, mode))
     }
 
+    /// Returns the row major 4x5 matrix if the filter can be represented as a color matrix, for
+    /// example to fold it into other matrices or to apply it in a custom shader.
     pub fn to_a_color_matrix(&self) -> Option<[scalar; 20]> {
         let mut matrix: [scalar; 20] = Default::default();
         unsafe { self.native().asAColorMatrix(&mut matrix[0]) }.if_true_some(matrix)
@@ -55,11 +59,15 @@ impl ColorFilter {
         unsafe { self.native().isAlphaUnchanged() }
     }
 
+    /// Applies the filter to a single color, for example to precompute the effect of a theme's
+    /// filter on its key colors.
     pub fn filter_color(&self, color: impl Into<Color>) -> Color {
         // Color resolves to u32, so the C++ ABI can be used.
         Color::from_native_c(unsafe { self.native().filterColor(color.into().into_native()) })
     }
 
+    /// Applies the filter to a single color that is in `src_color_space` and converts the result
+    /// to `dst_color_space`, or to `src_color_space` if [None].
     pub fn filter_color4f(
         &self,
         color: impl AsRef<Color4f>,
@@ -82,8 +90,13 @@ impl ColorFilter {
         })
     }
 
+    /// Returns `true` if the filter changes transparent black, which means that it also affects
+    /// the areas that were not drawn into, for example when it is applied to a layer.
+    pub fn affects_transparent_black(&self) -> bool {
+        self.filter_color(Color::TRANSPARENT) != Color::TRANSPARENT
+    }
+
     // TODO: asFragmentProcessor()
-    // TODO: affectsTransparentBlack()
 }
 
 pub mod color_filters {
@@ -176,3 +189,21 @@ fn filter_color() {
         None,
     );
 }
+
+#[test]
+fn color_matrix_introspection() {
+    let mut row_major = [0.0; 20];
+    // swap red and blue.
+    row_major[2] = 1.0;
+    row_major[6] = 1.0;
+    row_major[10] = 1.0;
+    row_major[18] = 1.0;
+    let cf = color_filters::matrix_row_major(&row_major);
+    assert_eq!(cf.to_a_color_matrix(), Some(row_major));
+    assert_eq!(cf.filter_color(Color::RED), Color::BLUE);
+    assert!(!cf.affects_transparent_black());
+
+    let cf = color_filters::blend(Color::RED, BlendMode::Src).unwrap();
+    assert_eq!(cf.to_a_color_matrix(), None);
+    assert!(cf.affects_transparent_black());
+}
@@ -3,17 +3,19 @@ use crate::prelude::*;
 use skia_bindings as sb;
 use skia_bindings::{SkColorSpace, SkColorSpacePrimaries};
 
-#[derive(Clone, PartialEq, Debug)]
+/// The CIE xy chromaticities of the red, green and blue primaries and of the white point of a
+/// gamut, see [named_primaries].
+#[derive(Copy, Clone, PartialEq, Debug)]
 #[repr(C)]
 pub struct ColorSpacePrimaries {
-    rx: f32,
-    ry: f32,
-    gx: f32,
-    gy: f32,
-    bx: f32,
-    by: f32,
-    wx: f32,
-    wy: f32,
+    pub rx: f32,
+    pub ry: f32,
+    pub gx: f32,
+    pub gy: f32,
+    pub bx: f32,
+    pub by: f32,
+    pub wx: f32,
+    pub wy: f32,
 }
 
 impl NativeTransmutable<SkColorSpacePrimaries> for ColorSpacePrimaries {}
@@ -22,7 +24,34 @@ fn test_color_space_primaries_layout() {
     ColorSpacePrimaries::test_layout()
 }
 
+impl ColorSpacePrimaries {
+    pub const fn new(r: (f32, f32), g: (f32, f32), b: (f32, f32), white: (f32, f32)) -> Self {
+        Self {
+            rx: r.0,
+            ry: r.1,
+            gx: g.0,
+            gy: g.1,
+            bx: b.0,
+            by: b.1,
+            wx: white.0,
+            wy: white.1,
+        }
+    }
+
+    /// Computes the matrix that converts linear RGB of this gamut to XYZ D50, which can be
+    /// passed to [ColorSpace::new_rgb()]. Returns [None] if the primaries do not span a gamut,
+    /// for example because two of them are equal.
+    pub fn to_xyzd50(&self) -> Option<[[f32; 3]; 3]> {
+        let mut to_xyzd50 = sb::skcms_Matrix3x3 {
+            vals: Default::default(),
+        };
+        unsafe { sb::C_SkColorSpacePrimaries_toXYZD50(self.native(), &mut to_xyzd50) }
+            .if_true_some(to_xyzd50.vals)
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
+#[repr(C)]
 pub struct ColorSpaceTransferFn {
     pub g: f32,
     pub a: f32,
@@ -33,6 +62,27 @@ pub struct ColorSpaceTransferFn {
     pub f: f32,
 }
 
+impl NativeTransmutable<sb::skcms_TransferFunction> for ColorSpaceTransferFn {}
+#[test]
+fn test_color_space_transfer_fn_layout() {
+    ColorSpaceTransferFn::test_layout()
+}
+
+impl ColorSpaceTransferFn {
+    /// A pure power curve `y = x^gamma`, like [named_transfer_fn::DOT22] for a `gamma` of 2.2.
+    pub const fn gamma(gamma: f32) -> Self {
+        Self {
+            g: gamma,
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+}
+
 // TODO: Make the binding generator provide all these constants.
 pub mod named_transfer_fn {
     use crate::ColorSpaceTransferFn;
@@ -99,6 +149,56 @@ pub mod named_transfer_fn {
     };
 }
 
+/// The primaries of common gamuts, for use with [ColorSpace::new_rgb_with_primaries()].
+pub mod named_primaries {
+    use crate::ColorSpacePrimaries;
+
+    const D65: (f32, f32) = (0.3127, 0.3290);
+
+    pub const SRGB: ColorSpacePrimaries =
+        ColorSpacePrimaries::new((0.64, 0.33), (0.30, 0.60), (0.15, 0.06), D65);
+
+    pub const ADOBE_RGB: ColorSpacePrimaries =
+        ColorSpacePrimaries::new((0.64, 0.33), (0.21, 0.71), (0.15, 0.06), D65);
+
+    pub const DISPLAY_P3: ColorSpacePrimaries =
+        ColorSpacePrimaries::new((0.680, 0.320), (0.265, 0.690), (0.150, 0.060), D65);
+
+    pub const REC2020: ColorSpacePrimaries =
+        ColorSpacePrimaries::new((0.708, 0.292), (0.170, 0.797), (0.131, 0.046), D65);
+}
+
+/// Matrices that convert linear RGB of a gamut to XYZ D50, for use with [ColorSpace::new_rgb()].
+#[allow(clippy::excessive_precision)]
+pub mod named_gamut {
+    pub const SRGB: [[f32; 3]; 3] = [
+        [0.436_066, 0.385_147, 0.143_066],
+        [0.222_488, 0.716_873, 0.060_608],
+        [0.013_916, 0.097_076, 0.714_096],
+    ];
+
+    pub const ADOBE_RGB: [[f32; 3]; 3] = [
+        [0.609_741, 0.205_276, 0.149_185],
+        [0.311_111, 0.625_671, 0.063_217],
+        [0.019_470, 0.060_867, 0.744_568],
+    ];
+
+    /// The gamut of Display P3, which uses the sRGB transfer function.
+    pub const DISPLAY_P3: [[f32; 3]; 3] = [
+        [0.515_102, 0.291_965, 0.157_153],
+        [0.241_182, 0.692_236, 0.066_581_9],
+        [-0.001_049_41, 0.041_881_8, 0.784_378],
+    ];
+
+    pub const REC2020: [[f32; 3]; 3] = [
+        [0.673_459, 0.165_661, 0.125_100],
+        [0.279_033, 0.675_338, 0.045_628_8],
+        [-0.001_931_39, 0.029_979_4, 0.797_162],
+    ];
+
+    pub const XYZ: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+}
+
 pub type ColorSpace = RCHandle<SkColorSpace>;
 unsafe impl Send for ColorSpace {}
 unsafe impl Sync for ColorSpace {}
@@ -132,6 +232,39 @@ impl ColorSpace {
         ColorSpace::from_ptr(unsafe { sb::C_SkColorSpace_MakeSRGBLinear() }).unwrap()
     }
 
+    /// Creates a color space from a transfer function and a matrix that converts the linear
+    /// gamut to XYZ D50, see [named_transfer_fn] and [named_gamut]. Display P3 for example is
+    /// `ColorSpace::new_rgb(&named_transfer_fn::SRGB, &named_gamut::DISPLAY_P3)`.
+    pub fn new_rgb(
+        transfer_fn: &ColorSpaceTransferFn,
+        to_xyzd50: &[[f32; 3]; 3],
+    ) -> Option<ColorSpace> {
+        let to_xyzd50 = sb::skcms_Matrix3x3 { vals: *to_xyzd50 };
+        ColorSpace::from_ptr(unsafe {
+            sb::C_SkColorSpace_MakeRGB(transfer_fn.native(), &to_xyzd50)
+        })
+    }
+
+    /// Creates a color space from a transfer function and the primaries of its gamut. Use
+    /// [ColorSpaceTransferFn::gamma()] for a transfer function that is a plain gamma value:
+    ///
+    /// ```
+    /// use skia_safe::{named_primaries, ColorSpace, ColorSpaceTransferFn};
+    /// let cs = ColorSpace::new_rgb_with_primaries(
+    ///     &ColorSpaceTransferFn::gamma(2.2),
+    ///     &named_primaries::DISPLAY_P3,
+    /// );
+    /// assert!(cs.is_some());
+    /// ```
+    ///
+    /// Returns [None] if the primaries do not span a gamut or the transfer function is invalid.
+    pub fn new_rgb_with_primaries(
+        transfer_fn: &ColorSpaceTransferFn,
+        primaries: &ColorSpacePrimaries,
+    ) -> Option<ColorSpace> {
+        Self::new_rgb(transfer_fn, &primaries.to_xyzd50()?)
+    }
+
     pub fn to_xyzd50_hash(&self) -> XYZD50Hash {
         XYZD50Hash(self.native().fToXYZD50Hash)
     }
@@ -194,6 +327,32 @@ pub fn create_and_clone_colorspaces() {
     let _r = x.clone();
 }
 
+#[test]
+fn new_rgb_with_primaries() {
+    let srgb = ColorSpace::new_rgb_with_primaries(&named_transfer_fn::SRGB, &named_primaries::SRGB)
+        .unwrap();
+    assert!(srgb.is_srgb());
+
+    let to_xyzd50 = named_primaries::DISPLAY_P3.to_xyzd50().unwrap();
+    for (row, expected) in to_xyzd50.iter().zip(named_gamut::DISPLAY_P3.iter()) {
+        for (v, e) in row.iter().zip(expected.iter()) {
+            assert!((v - e).abs() < 0.001);
+        }
+    }
+
+    let p3_dot22 = ColorSpace::new_rgb_with_primaries(
+        &ColorSpaceTransferFn::gamma(2.2),
+        &named_primaries::DISPLAY_P3,
+    )
+    .unwrap();
+    assert!(!p3_dot22.is_srgb());
+    assert!(p3_dot22 == ColorSpace::new_rgb(&named_transfer_fn::DOT22, &to_xyzd50).unwrap());
+
+    let degenerate =
+        ColorSpacePrimaries::new((0.3, 0.3), (0.3, 0.3), (0.15, 0.06), (0.3127, 0.3290));
+    assert!(degenerate.to_xyzd50().is_none());
+}
+
 #[test]
 pub fn serialize_and_deserialize() {
     // TODO: it seems that the deserializer deduplicates the
@@ -0,0 +1,100 @@
+use crate::{AlphaType, Color4f, ColorSpace, ColorType, ImageInfo, Pixmap};
+use std::slice;
+
+/// Converts colors between two color spaces on the CPU, for example from sRGB to Display P3 or
+/// to a linear color space.
+///
+/// Conversions are done with Skia's raster pipeline, the same way [Pixmap::read_pixels()] converts
+/// pixels between [ImageInfo]s that differ in their color spaces. The results are not clamped,
+/// so colors that are outside the gamut of the destination have components below `0` or above
+/// `1`.
+#[derive(Clone)]
+pub struct ColorSpaceXform {
+    src: ColorSpace,
+    dst: ColorSpace,
+}
+
+impl ColorSpaceXform {
+    pub fn new(src: &ColorSpace, dst: &ColorSpace) -> Self {
+        ColorSpaceXform {
+            src: src.clone(),
+            dst: dst.clone(),
+        }
+    }
+
+    pub fn src(&self) -> &ColorSpace {
+        &self.src
+    }
+
+    pub fn dst(&self) -> &ColorSpace {
+        &self.dst
+    }
+
+    /// Converts an unpremultiplied color.
+    pub fn apply(&self, color: impl Into<Color4f>) -> Color4f {
+        let mut colors = [color.into()];
+        let converted = self.apply_to_slice(&mut colors, AlphaType::Unpremul);
+        debug_assert!(converted);
+        colors[0].clone()
+    }
+
+    /// Converts colors in place. With [AlphaType::Premul], the colors are unpremultiplied before
+    /// and premultiplied again after the conversion. Returns `false` if the colors could not be
+    /// converted.
+    pub fn apply_to_slice(&self, colors: &mut [Color4f], alpha_type: AlphaType) -> bool {
+        // limits the size of the temporary copy of the source colors.
+        const CHUNK_SIZE: usize = 1024;
+        colors
+            .chunks_mut(CHUNK_SIZE)
+            .all(|chunk| self.apply_to_chunk(chunk, alpha_type))
+    }
+
+    fn apply_to_chunk(&self, colors: &mut [Color4f], alpha_type: AlphaType) -> bool {
+        let src_info = ImageInfo::new(
+            (colors.len() as i32, 1),
+            ColorType::RGBAF32,
+            alpha_type,
+            self.src.clone(),
+        );
+        let dst_info = src_info.with_color_space(self.dst.clone());
+        let row_bytes = src_info.min_row_bytes();
+        let src_colors = colors.to_vec();
+        let src_pixels =
+            unsafe { slice::from_raw_parts(src_colors.as_ptr() as *const u8, row_bytes) };
+        let src = Pixmap::new(&src_info, src_pixels, row_bytes);
+        src.read_pixels(&dst_info, colors, row_bytes, (0, 0))
+    }
+
+    /// Returns the conversion in the opposite direction.
+    #[must_use]
+    pub fn inverted(&self) -> Self {
+        ColorSpaceXform::new(&self.dst, &self.src)
+    }
+}
+
+#[test]
+fn test_srgb_to_display_p3() {
+    use crate::{named_gamut, named_transfer_fn};
+
+    let srgb = ColorSpace::new_srgb();
+    let p3 = ColorSpace::new_rgb(&named_transfer_fn::SRGB, &named_gamut::DISPLAY_P3).unwrap();
+    let xform = ColorSpaceXform::new(&srgb, &p3);
+
+    let red = xform.apply(Color4f::new(1.0, 0.0, 0.0, 1.0));
+    assert!((red.r - 0.9175).abs() < 0.001, "{:?}", red);
+    assert!((red.g - 0.2003).abs() < 0.001, "{:?}", red);
+    assert!((red.b - 0.1386).abs() < 0.001, "{:?}", red);
+
+    let back = xform.inverted().apply(red);
+    assert!((back.r - 1.0).abs() < 0.001 && back.g.abs() < 0.001 && back.b.abs() < 0.001);
+}
+
+#[test]
+fn test_premul_to_linear() {
+    let xform = ColorSpaceXform::new(&ColorSpace::new_srgb(), &ColorSpace::new_srgb_linear());
+    let mut colors = [Color4f::new(0.25, 0.25, 0.25, 0.5)];
+    assert!(xform.apply_to_slice(&mut colors, AlphaType::Premul));
+    // unpremultiplied 0.5 in sRGB is 0.214 in linear sRGB.
+    assert!((colors[0].r - 0.107).abs() < 0.001, "{:?}", colors[0]);
+    assert_eq!(colors[0].a, 0.5);
+}
@@ -4,7 +4,10 @@ use crate::gpu;
 use crate::prelude::*;
 use crate::{Canvas, Matrix, NativeFlattenable, Point, Rect};
 use skia_bindings as sb;
-use skia_bindings::{SkDrawable, SkFlattenable, SkRefCntBase};
+use skia_bindings::{SkCanvas, SkDrawable, SkFlattenable, SkRect, SkRefCntBase};
+use std::os::raw;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
 
 pub type Drawable = RCHandle<SkDrawable>;
 
@@ -23,6 +26,25 @@ impl NativeFlattenable for SkDrawable {
 }
 
 impl Drawable {
+    /// Creates a drawable that is drawn by `drawable`. Pictures that are recorded with
+    /// [crate::PictureRecorder::finish_recording_as_drawable()] keep referring to it, so the
+    /// content can be changed without recording the picture again.
+    ///
+    /// To change the content, share the state with the [CustomDrawable], for example with an
+    /// `Arc<Mutex<_>>`, and call [Self::notify_drawing_changed()] afterwards.
+    pub fn new_custom(drawable: impl CustomDrawable + 'static) -> Self {
+        let drawable: Box<Mutex<Box<dyn CustomDrawable>>> =
+            Box::new(Mutex::new(Box::new(drawable)));
+        let param = sb::RustDrawable_Param {
+            // note: the native drawable owns the boxed trait object and drops it in its destructor.
+            drawable: Box::into_raw(drawable) as *mut raw::c_void,
+            drop: Some(drop_trampoline),
+            draw: Some(draw_trampoline),
+            bounds: Some(bounds_trampoline),
+        };
+        Drawable::from_ptr(unsafe { sb::C_RustDrawable_new(&param) }).unwrap()
+    }
+
     pub fn draw(&mut self, canvas: &mut Canvas, matrix: Option<&Matrix>) {
         unsafe {
             self.native_mut()
@@ -78,6 +100,50 @@ impl Drawable {
     }
 }
 
+/// The content of a [Drawable] that is implemented in Rust, see [Drawable::new_custom()].
+pub trait CustomDrawable: Send {
+    /// Draws the content. The canvas is already transformed to the drawable's coordinate space.
+    fn draw(&mut self, canvas: &mut Canvas);
+
+    /// Returns the conservative bounds of what [Self::draw()] draws. The bounds are used to cull
+    /// the drawable and the pictures it is recorded into, so they must change only after
+    /// [Drawable::notify_drawing_changed()] is called.
+    fn bounds(&mut self) -> Rect;
+}
+
+type BoxedCustomDrawable = Mutex<Box<dyn CustomDrawable>>;
+
+unsafe extern "C" fn drop_trampoline(drawable: *mut raw::c_void) {
+    drop(Box::from_raw(drawable as *mut BoxedCustomDrawable))
+}
+
+// Panics must not unwind into Skia. A drawable that panicked poisons its mutex and is not
+// called anymore. `try_lock()` skips drawables that draw themselves again, directly or through
+// a picture, instead of deadlocking.
+
+unsafe extern "C" fn draw_trampoline(drawable: *mut raw::c_void, canvas: *mut SkCanvas) {
+    let drawable = &*(drawable as *const BoxedCustomDrawable);
+    let canvas = Canvas::borrow_from_native(&mut *canvas);
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(mut drawable) = drawable.try_lock() {
+            drawable.draw(canvas)
+        }
+    }));
+}
+
+unsafe extern "C" fn bounds_trampoline(drawable: *mut raw::c_void, bounds: *mut SkRect) {
+    let drawable = &*(drawable as *const BoxedCustomDrawable);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        drawable
+            .try_lock()
+            .ok()
+            .map(|mut drawable| drawable.bounds())
+    }));
+    if let Ok(Some(rect)) = result {
+        *bounds = rect.into_native()
+    }
+}
+
 #[cfg(feature = "gpu")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
 pub use gpu_draw_handler::*;
@@ -106,3 +172,69 @@ pub mod gpu_draw_handler {
         }
     }
 }
+
+#[test]
+fn test_custom_drawable() {
+    use crate::{Color, Paint, Surface};
+    use std::sync::Arc;
+
+    struct Square(Arc<Mutex<Rect>>);
+
+    impl CustomDrawable for Square {
+        fn draw(&mut self, canvas: &mut Canvas) {
+            let rect = *self.0.lock().unwrap();
+            canvas.draw_rect(rect, Paint::default().set_color(Color::RED));
+        }
+
+        fn bounds(&mut self) -> Rect {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    let rect = Arc::new(Mutex::new(Rect::from_xywh(0.0, 0.0, 4.0, 4.0)));
+    let mut drawable = Drawable::new_custom(Square(rect.clone()));
+    assert_eq!(drawable.bounds(), Rect::from_xywh(0.0, 0.0, 4.0, 4.0));
+
+    let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+    surface.canvas().draw_drawable_at(&mut drawable, (2.0, 2.0));
+    let pixmap = surface.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((3, 3)), Color::RED);
+    assert_eq!(pixmap.get_color((7, 7)), Color::TRANSPARENT);
+
+    let generation_id = drawable.generation_id();
+    *rect.lock().unwrap() = Rect::from_xywh(0.0, 0.0, 8.0, 8.0);
+    drawable.notify_drawing_changed();
+    assert_ne!(drawable.generation_id(), generation_id);
+    assert_eq!(drawable.bounds(), Rect::from_xywh(0.0, 0.0, 8.0, 8.0));
+
+    // the drawable owns its state.
+    drop(drawable);
+    assert_eq!(Arc::strong_count(&rect), 1);
+}
+
+#[test]
+fn test_panicking_custom_drawable() {
+    use crate::{Color, Surface};
+
+    struct Panics;
+
+    impl CustomDrawable for Panics {
+        fn draw(&mut self, _canvas: &mut Canvas) {
+            panic!("draw")
+        }
+
+        fn bounds(&mut self) -> Rect {
+            panic!("bounds")
+        }
+    }
+
+    let mut drawable = Drawable::new_custom(Panics);
+    assert!(drawable.bounds().is_empty());
+    let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+    surface.canvas().draw_drawable_at(&mut drawable, (2.0, 2.0));
+    assert_eq!(
+        surface.peek_pixels().unwrap().get_color((3, 3)),
+        Color::WHITE
+    );
+}
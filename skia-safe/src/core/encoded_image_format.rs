@@ -1,5 +1,120 @@
+use std::io;
+
 pub use skia_bindings::SkEncodedImageFormat as EncodedImageFormat;
 #[test]
 fn test_encoded_image_format_naming() {
     let _ = EncodedImageFormat::BMP;
 }
+
+/// The number of leading bytes [detect_encoded_image_format()] needs to detect all supported
+/// formats.
+pub const ENCODED_IMAGE_FORMAT_SNIFF_BYTES: usize = 32;
+
+/// Detects the format of encoded image data by inspecting its leading bytes only, without
+/// creating a [crate::Codec] or decoding anything.
+///
+/// Detects PNG, JPEG, GIF, WebP, BMP, ICO (and CUR), KTX, ASTC, HEIF, and AVIF. HEIF and AVIF
+/// files share the same container format and are distinguished by their major brand.
+///
+/// Returns [None] if the format is not recognized.
+pub fn detect_encoded_image_format(data: &[u8]) -> Option<EncodedImageFormat> {
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n";
+    const JPEG: &[u8] = b"\xFF\xD8\xFF";
+    const KTX: &[u8] = b"\xABKTX 11\xBB\r\n\x1A\n";
+    const ASTC: &[u8] = b"\x13\xAB\xA1\x5C";
+    const HEIF_BRANDS: &[&[u8]] = &[
+        b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+    ];
+    const AVIF_BRANDS: &[&[u8]] = &[b"avif", b"avis"];
+
+    // the major brand of ISO base media files.
+    let brand = if data.len() >= 12 && &data[4..8] == b"ftyp" {
+        Some(&data[8..12])
+    } else {
+        None
+    };
+
+    let format = if data.starts_with(PNG) {
+        EncodedImageFormat::PNG
+    } else if data.starts_with(JPEG) {
+        EncodedImageFormat::JPEG
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        EncodedImageFormat::GIF
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        EncodedImageFormat::WEBP
+    } else if data.starts_with(b"BM") {
+        EncodedImageFormat::BMP
+    } else if data.starts_with(b"\x00\x00\x01\x00") || data.starts_with(b"\x00\x00\x02\x00") {
+        EncodedImageFormat::ICO
+    } else if data.starts_with(KTX) {
+        EncodedImageFormat::KTX
+    } else if data.starts_with(ASTC) {
+        EncodedImageFormat::ASTC
+    } else if brand.map_or(false, |brand| HEIF_BRANDS.contains(&brand)) {
+        EncodedImageFormat::HEIF
+    } else if brand.map_or(false, |brand| AVIF_BRANDS.contains(&brand)) {
+        EncodedImageFormat::AVIF
+    } else {
+        return None;
+    };
+
+    Some(format)
+}
+
+/// Detects the format of the encoded image data `reader` provides.
+///
+/// Reads at most [ENCODED_IMAGE_FORMAT_SNIFF_BYTES] and restores the reader's position
+/// afterwards, so that the stream can be passed on to a decoder.
+pub fn detect_encoded_image_format_from_reader(
+    reader: &mut (impl io::Read + io::Seek),
+) -> io::Result<Option<EncodedImageFormat>> {
+    let position = reader.seek(io::SeekFrom::Current(0))?;
+    let mut buffer = [0u8; ENCODED_IMAGE_FORMAT_SNIFF_BYTES];
+    let mut len = 0;
+    while len < buffer.len() {
+        match reader.read(&mut buffer[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    reader.seek(io::SeekFrom::Start(position))?;
+    Ok(detect_encoded_image_format(&buffer[..len]))
+}
+
+#[test]
+fn test_detect_encoded_image_format() {
+    assert_eq!(
+        detect_encoded_image_format(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+        Some(EncodedImageFormat::PNG)
+    );
+    assert_eq!(
+        detect_encoded_image_format(b"\xFF\xD8\xFF\xE0\0\x10JFIF"),
+        Some(EncodedImageFormat::JPEG)
+    );
+    assert_eq!(
+        detect_encoded_image_format(b"RIFF\x24\0\0\0WEBPVP8 "),
+        Some(EncodedImageFormat::WEBP)
+    );
+    assert_eq!(
+        detect_encoded_image_format(b"\0\0\0\x1cftypavif\0\0\0\0"),
+        Some(EncodedImageFormat::AVIF)
+    );
+    assert_eq!(
+        detect_encoded_image_format(b"\0\0\0\x18ftypheic\0\0\0\0"),
+        Some(EncodedImageFormat::HEIF)
+    );
+    assert_eq!(detect_encoded_image_format(b"RIFF"), None);
+    assert_eq!(detect_encoded_image_format(b""), None);
+}
+
+#[test]
+fn test_detect_encoded_image_format_from_reader_restores_position() {
+    let mut cursor = io::Cursor::new(b"GIF89a\x01\0\x01\0".to_vec());
+    assert_eq!(
+        detect_encoded_image_format_from_reader(&mut cursor).unwrap(),
+        Some(EncodedImageFormat::GIF)
+    );
+    assert_eq!(cursor.position(), 0);
+}
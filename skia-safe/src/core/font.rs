@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::{
-    scalar, FontHinting, FontMetrics, GlyphId, Paint, Path, Point, Rect, TextEncoding, Typeface,
-    Unichar,
+    scalar, FontHinting, FontMetrics, GlyphId, Paint, Path, PixelGeometry, Point, Rect,
+    SurfaceProps, TextEncoding, Typeface, Unichar,
 };
 use skia_bindings as sb;
 use skia_bindings::{SkFont, SkFont_PrivFlags};
@@ -23,6 +23,12 @@ impl NativeDrop for SkFont {
     }
 }
 
+impl NativeClone for SkFont {
+    fn clone(&self) -> Self {
+        construct(|font| unsafe { sb::C_SkFont_CopyConstruct(font, self) })
+    }
+}
+
 impl NativePartialEq for SkFont {
     fn eq(&self, rhs: &Self) -> bool {
         unsafe { sb::C_SkFont_Equals(self, rhs) }
@@ -149,6 +155,33 @@ impl Handle<SkFont> {
         unsafe { sb::C_SkFont_getHinting(self.native()) }
     }
 
+    /// Sets edging, hinting, subpixel positioning and baseline snapping to values that suit text
+    /// drawn to a surface with `props` at `device_scale` device pixels per logical pixel.
+    ///
+    /// - Subpixel anti-aliasing is used only if the pixel geometry of the surface is known and
+    ///   the display is not high density. Displays with an unknown subpixel layout, like most
+    ///   OLED and rotated displays, get grayscale anti-aliasing.
+    /// - Standard density displays use slight hinting and snap baselines to whole pixels, high
+    ///   density displays (`device_scale >= 2`) preserve the glyph shapes and positions.
+    /// - Glyphs are always positioned with subpixel precision.
+    pub fn set_rendering_for(&mut self, props: &SurfaceProps, device_scale: scalar) -> &mut Self {
+        let high_density = device_scale >= 2.0;
+        let edging = if props.pixel_geometry() != PixelGeometry::Unknown && !high_density {
+            Edging::SubpixelAntiAlias
+        } else {
+            Edging::AntiAlias
+        };
+        let hinting = if high_density {
+            FontHinting::None
+        } else {
+            FontHinting::Slight
+        };
+        self.set_edging(edging)
+            .set_hinting(hinting)
+            .set_subpixel(true)
+            .set_baseline_snap(!high_density)
+    }
+
     #[must_use]
     pub fn with_size(&self, size: scalar) -> Option<Self> {
         if size >= 0.0 && !size.is_infinite() && !size.is_nan() {
@@ -408,3 +441,24 @@ fn test_flags() {
     font.set_embolden(false);
     assert!(!font.is_embolden());
 }
+
+#[test]
+fn test_set_rendering_for() {
+    use crate::SurfacePropsFlags;
+
+    let lcd = SurfaceProps::with_options(SurfacePropsFlags::empty(), PixelGeometry::RGBH);
+    let mut font = Font::default();
+
+    font.set_rendering_for(&lcd, 1.0);
+    assert_eq!(font.edging(), Edging::SubpixelAntiAlias);
+    assert_eq!(font.hinting(), FontHinting::Slight);
+    assert!(font.is_subpixel() && font.is_baseline_snap());
+
+    font.set_rendering_for(&lcd, 2.0);
+    assert_eq!(font.edging(), Edging::AntiAlias);
+    assert_eq!(font.hinting(), FontHinting::None);
+    assert!(!font.is_baseline_snap());
+
+    font.set_rendering_for(&SurfaceProps::default(), 1.0);
+    assert_eq!(font.edging(), Edging::AntiAlias);
+}
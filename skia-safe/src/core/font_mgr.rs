@@ -54,6 +54,12 @@ impl FontStyleSet {
         (font_style, name)
     }
 
+    /// Returns the styles and style names of all the typefaces in the set, in the order of their
+    /// indices.
+    pub fn styles(&mut self) -> Vec<(FontStyle, Option<String>)> {
+        (0..self.count()).map(|i| self.style(i)).collect()
+    }
+
     pub fn new_typeface(&mut self, index: usize) -> Option<Typeface> {
         assert!(index < self.count());
 
@@ -107,6 +113,11 @@ impl FontMgr {
         family_name.as_str().into()
     }
 
+    /// Returns the names of all the font families, in the order of their indices.
+    pub fn family_names(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.count_families()).map(move |i| self.family_name(i))
+    }
+
     pub fn new_styleset(&self, index: usize) -> FontStyleSet {
         assert!(index < self.count_families());
         FontStyleSet::from_ptr(unsafe { self.native().createStyleSet(index.try_into().unwrap()) })
@@ -184,7 +195,7 @@ impl FontMgr {
 
 #[cfg(test)]
 mod tests {
-    use crate::FontMgr;
+    use crate::{FontMgr, FontStyle};
 
     #[test]
     #[serial_test::serial]
@@ -209,4 +220,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn enumerate_families_and_match_their_styles() {
+        let font_mgr = FontMgr::default();
+        let names: Vec<String> = font_mgr.family_names().collect();
+        assert_eq!(names.len(), font_mgr.count_families());
+
+        let name = &names[0];
+        let mut style_set = font_mgr.match_family(name);
+        let styles = style_set.styles();
+        assert_eq!(styles.len(), style_set.count());
+        if let Some((style, _)) = styles.first() {
+            assert!(font_mgr.match_family_style(name, *style).is_some());
+        }
+        let _ =
+            font_mgr.match_family_style_character(name, FontStyle::default(), &["en"], 'A' as _);
+    }
 }
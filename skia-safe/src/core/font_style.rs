@@ -1,14 +1,20 @@
 use crate::prelude::*;
 use skia_bindings as sb;
 use skia_bindings::{SkFontStyle, SkFontStyle_Weight, SkFontStyle_Width};
+use std::fmt;
 use std::ops::Deref;
 
 /// Wrapper type of a font weight.
 ///
 /// Use Weight::from() to create a weight from an i32.
 /// Use *weight to pull out the wrapped value of the Weight.
-#[derive(Copy, Clone, PartialEq, PartialOrd)]
+///
+/// Weights are not limited to the constants, any value in the range `0..=1000` can be used,
+/// for example `Weight::from(450)` for fonts with variable weights. [FontStyle] clamps weights
+/// to this range.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
 #[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Weight(i32);
 
 impl NativeTransmutable<i32> for Weight {}
@@ -50,8 +56,12 @@ impl Weight {
 ///
 /// To create a width of a font from an i32, use Width::from().
 /// To access the underlying value of the font weight, dereference *weight.
-#[derive(Copy, Clone, PartialEq, PartialOrd)]
+///
+/// Widths are the OpenType width classes in the range `1..=9`, which [FontStyle] clamps
+/// widths to. See [Width::from_percentage()] for converting CSS `font-stretch` percentages.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
 #[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Width(i32);
 
 impl NativeTransmutable<i32> for Width {}
@@ -85,6 +95,36 @@ impl Width {
     pub const EXPANDED: Self = Self(SkFontStyle_Width::kExpanded_Width as _);
     pub const EXTRA_EXPANDED: Self = Self(SkFontStyle_Width::kExtraExpanded_Width as _);
     pub const ULTRA_EXPANDED: Self = Self(SkFontStyle_Width::kUltraExpanded_Width as _);
+
+    /// The percentages of the normal width the width classes `1..=9` stand for, as defined by
+    /// OpenType and CSS.
+    const PERCENTAGES: [f32; 9] = [50.0, 62.5, 75.0, 87.5, 100.0, 112.5, 125.0, 150.0, 200.0];
+
+    /// Returns the width class that is closest to `percentage` of the normal width, for example
+    /// the value of the CSS `font-stretch` property.
+    pub fn from_percentage(percentage: f32) -> Self {
+        let (index, _) = Self::PERCENTAGES.iter().enumerate().fold(
+            (0, f32::INFINITY),
+            |(best, distance), (i, p)| {
+                let d = (p - percentage).abs();
+                if d < distance {
+                    (i, d)
+                } else {
+                    (best, distance)
+                }
+            },
+        );
+        Self(index as i32 + 1)
+    }
+
+    /// Returns the percentage of the normal width this width class stands for, or [None] if it
+    /// is not in the range `1..=9`.
+    pub fn to_percentage(self) -> Option<f32> {
+        match self.0 {
+            1..=9 => Some(Self::PERCENTAGES[(self.0 - 1) as usize]),
+            _ => None,
+        }
+    }
 }
 
 pub use skia_bindings::SkFontStyle_Slant as Slant;
@@ -110,6 +150,16 @@ impl PartialEq for FontStyle {
     }
 }
 
+impl fmt::Debug for FontStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FontStyle")
+            .field("weight", &self.weight())
+            .field("width", &self.width())
+            .field("slant", &self.slant())
+            .finish()
+    }
+}
+
 impl Default for FontStyle {
     fn default() -> Self {
         FontStyle::construct(|fs| unsafe { sb::C_SkFontStyle_Construct(fs) })
@@ -117,6 +167,8 @@ impl Default for FontStyle {
 }
 
 impl FontStyle {
+    /// Creates a font style with an arbitrary `weight`, which is clamped to `0..=1000`, and a
+    /// `width` class, which is clamped to `1..=9`.
     pub fn new(weight: Weight, width: Width, slant: Slant) -> Self {
         Self::construct(|fs| unsafe {
             sb::C_SkFontStyle_Construct2(fs, weight.into_native(), width.into_native(), slant)
@@ -135,6 +187,21 @@ impl FontStyle {
         unsafe { sb::C_SkFontStyle_slant(self.native()) }
     }
 
+    #[must_use]
+    pub fn with_weight(self, weight: impl Into<Weight>) -> Self {
+        Self::new(weight.into(), self.width(), self.slant())
+    }
+
+    #[must_use]
+    pub fn with_width(self, width: impl Into<Width>) -> Self {
+        Self::new(self.weight(), width.into(), self.slant())
+    }
+
+    #[must_use]
+    pub fn with_slant(self, slant: Slant) -> Self {
+        Self::new(self.weight(), self.width(), slant)
+    }
+
     pub fn normal() -> FontStyle {
         *font_style_static::NORMAL
     }
@@ -173,3 +240,22 @@ fn test_equality() {
     let style2: FontStyle = Default::default();
     assert!(style == style2);
 }
+
+#[test]
+fn test_arbitrary_weights_and_widths() {
+    let style = FontStyle::new(450.into(), Width::SEMI_EXPANDED, Slant::Oblique);
+    assert_eq!(*style.weight(), 450);
+    assert_eq!(style.width(), Width::SEMI_EXPANDED);
+    assert_eq!(style.slant(), Slant::Oblique);
+
+    let clamped = style.with_weight(1200).with_width(0);
+    assert_eq!(clamped.weight(), Weight::EXTRA_BLACK);
+    assert_eq!(*clamped.width(), 1);
+    assert_eq!(clamped.slant(), Slant::Oblique);
+
+    assert_eq!(Width::from_percentage(100.0), Width::NORMAL);
+    assert_eq!(Width::from_percentage(140.0), Width::EXTRA_EXPANDED);
+    assert_eq!(Width::from_percentage(10.0), Width::ULTRA_CONDENSED);
+    assert_eq!(Width::CONDENSED.to_percentage(), Some(75.0));
+    assert_eq!(Width::from(10).to_percentage(), None);
+}
@@ -20,6 +20,33 @@ pub use skia_bindings::{
 // TODO: Add MipmapBuilder as soon it's documented or
 //       SkMipmap made its way into the public interface.
 
+/// An image that is kept in a texture, see [Image::pin_as_texture()].
+#[cfg(feature = "gpu")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+pub struct PinnedImage {
+    image: Image,
+    context: gpu::DirectContext,
+}
+
+#[cfg(feature = "gpu")]
+impl Drop for PinnedImage {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkImage_unpinAsTexture(self.image.native(), self.context.native_mut()) }
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl PinnedImage {
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+}
+
+/// An immutable image. Raster and lazy generated images can be shared between threads.
+///
+/// Texture-backed images, see [Image::is_texture_backed()], are bound to the thread of their GPU
+/// context and must only be drawn there. Use [Image::new_non_texture_image()] to get an image that
+/// can be used on any thread.
 pub type Image = RCHandle<SkImage>;
 unsafe impl Send for Image {}
 unsafe impl Sync for Image {}
@@ -61,6 +88,20 @@ impl Image {
         Image::from_ptr(unsafe { sb::C_SkImage_MakeFromEncoded(data.into().into_ptr()) })
     }
 
+    /// Decodes `data` into a raster image with `color_type` and `color_space`.
+    ///
+    /// In contrast to [Self::from_encoded()], the image is decoded immediately and the pixels
+    /// are converted while they are decoded, see [crate::codec::Codec::get_image_with_color()].
+    pub fn decode_with_color(
+        data: impl Into<Data>,
+        color_type: ColorType,
+        color_space: impl Into<Option<ColorSpace>>,
+    ) -> Option<Image> {
+        crate::Codec::from_data(data)?
+            .get_image_with_color(color_type, color_space)
+            .ok()
+    }
+
     #[deprecated(since = "0.35.0", note = "Removed without replacement")]
     pub fn decode_to_raster(_encoded: &[u8], _subset: impl Into<Option<IRect>>) -> ! {
         panic!("Removed without replacement")
@@ -276,6 +317,9 @@ impl Image {
         self.image_info().bounds()
     }
 
+    /// An id that identifies the pixels of the image. Images that share their pixels, like a
+    /// snapshot of a surface that was not modified since, may have the same id, so it can be
+    /// used as a cache key.
     pub fn unique_id(&self) -> u32 {
         self.native().fUniqueID
     }
@@ -369,16 +413,46 @@ impl Image {
             .if_true_then_some(|| pixmap.borrows(self))
     }
 
+    /// Returns `true` if the pixels of the image live in a texture of a GPU context, and `false`
+    /// if they live in memory or are generated on demand.
     pub fn is_texture_backed(&self) -> bool {
         unsafe { self.native().isTextureBacked() }
     }
 
+    /// Returns `true` if the image can be drawn on surfaces of `context`. Texture-backed images
+    /// can be drawn only with the context that created them.
     #[cfg(feature = "gpu")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
     pub fn is_valid(&self, context: &mut gpu::RecordingContext) -> bool {
         unsafe { self.native().isValid(context.native_mut()) }
     }
 
+    /// Returns `true` if the image can be drawn on raster surfaces, which is the case for all
+    /// images that are not texture-backed.
+    pub fn is_valid_for_raster(&self) -> bool {
+        // `isValid(nullptr)` returns `true` for texture-backed images as long as their context
+        // is not abandoned.
+        !self.is_texture_backed() && unsafe { self.native().isValid(ptr::null_mut()) }
+    }
+
+    /// Estimates the GPU memory of the texture of a texture-backed image, including its
+    /// mipmaps. Returns `None` if the image is not texture-backed.
+    ///
+    /// See [gpu::estimate_surface_memory()] to estimate the size before the texture is created.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn estimate_gpu_memory(&self) -> Option<usize> {
+        if !self.is_texture_backed() {
+            return None;
+        }
+        let mipmapped = if self.has_mipmaps() {
+            gpu::Mipmapped::Yes
+        } else {
+            gpu::Mipmapped::No
+        };
+        Some(unsafe { sb::C_GrContext_ComputeImageSize(self.clone().into_ptr(), mipmapped, false) })
+    }
+
     #[cfg(feature = "gpu")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
     pub fn flush_with_info(
@@ -430,6 +504,31 @@ impl Image {
         .map(|texture| (texture, origin))
     }
 
+    /// Uploads the image into a texture of `context` and keeps it there until the returned
+    /// [PinnedImage] is dropped. Draws to surfaces of the context use that texture, even if the
+    /// source of the image changes in the meantime. Pins can be nested.
+    ///
+    /// Returns [None] if the image can not be pinned, for example because it is texture-backed
+    /// already.
+    ///
+    /// Pinned raster and lazy images do not expose their texture. To sample an image in the
+    /// passes of an embedding application, create a texture-backed image with
+    /// [Self::new_texture_image()], retrieve its texture with [Self::backend_texture()] and
+    /// `flush_pending_gr_context_io` set to `true`, and keep the image alive as long as the
+    /// texture is in use.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn pin_as_texture(&self, context: &mut gpu::DirectContext) -> Option<PinnedImage> {
+        if self.is_texture_backed() {
+            return None;
+        }
+        unsafe { sb::C_SkImage_pinAsTexture(self.native(), context.native_mut()) }
+            .if_true_then_some(|| PinnedImage {
+                image: self.clone(),
+                context: context.clone(),
+            })
+    }
+
     #[cfg(feature = "gpu")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
     pub fn read_pixels_with_context<'a, P>(
@@ -561,6 +660,46 @@ impl Image {
         Data::from_ptr(unsafe { sb::C_SkImage_encodeToData(self.native(), image_format, quality) })
     }
 
+    /// Encodes the image's pixels and returns the result as encoded data.
+    ///
+    /// Unlike [Self::encode_to_data_with_quality()], this function supports texture-backed
+    /// images: if the image is texture-backed, pending GPU work is flushed and its pixels are
+    /// read back through `context` before they are encoded. Texture-backed images require a
+    /// context, raster-backed images are encoded directly. `quality` defaults to `100`.
+    ///
+    /// Returns [None] if the pixels can not be read back or encoding fails.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn encode<'a>(
+        &self,
+        context: impl Into<Option<&'a mut gpu::DirectContext>>,
+        image_format: EncodedImageFormat,
+        quality: impl Into<Option<i32>>,
+    ) -> Option<Data> {
+        let quality = quality.into().unwrap_or(100);
+        if !self.is_texture_backed() {
+            return self.encode_to_data_with_quality(image_format, quality);
+        }
+
+        let context = context.into()?;
+        let info = self.image_info().clone();
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        if !self.read_pixels_with_context(
+            context,
+            &info,
+            &mut pixels,
+            row_bytes,
+            IPoint::default(),
+            CachingHint::Disallow,
+        ) {
+            return None;
+        }
+
+        Image::from_raster_data(&info, Data::new_copy(&pixels), row_bytes)?
+            .encode_to_data_with_quality(image_format, quality)
+    }
+
     pub fn encoded_data(&self) -> Option<Data> {
         Data::from_ptr(unsafe { sb::C_SkImage_refEncodedData(self.native()) })
     }
@@ -690,6 +829,8 @@ impl Image {
 
     // TODO: MakeBackendTextureFromSkImage()
 
+    /// Returns `true` if the pixels are generated on demand, for example by decoding encoded data
+    /// or by an [ImageGenerator], and may be cached and purged by Skia.
     pub fn is_lazy_generated(&self) -> bool {
         unsafe { self.native().isLazyGenerated() }
     }
@@ -751,6 +892,47 @@ mod tests {
         let _ = CachingHint::Allow;
     }
 
+    #[test]
+    fn test_pixel_location_queries() {
+        use crate::{EncodedImageFormat, Image, Surface};
+
+        let surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let raster = surface.image_snapshot();
+        assert!(!raster.is_texture_backed());
+        assert!(!raster.is_lazy_generated());
+        assert!(raster.is_valid_for_raster());
+
+        let encoded = raster.encode_to_data(EncodedImageFormat::PNG).unwrap();
+        let lazy = Image::from_encoded(encoded).unwrap();
+        assert!(lazy.is_lazy_generated());
+        assert!(lazy.is_valid_for_raster());
+        assert_ne!(lazy.unique_id(), raster.unique_id());
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn test_texture_backed_image_is_not_valid_for_raster() {
+        use crate::gpu::{DirectContext, SurfaceOrigin};
+        use crate::{Budgeted, ImageInfo, Surface};
+
+        let context = DirectContext::new_mock(None).unwrap();
+        let mut surface = Surface::new_render_target(
+            &mut context.clone().into(),
+            Budgeted::Yes,
+            &ImageInfo::new_n32_premul((4, 4), None),
+            None,
+            SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )
+        .unwrap();
+        surface.canvas().clear(crate::Color::RED);
+        let texture = surface.image_snapshot();
+        assert!(texture.is_texture_backed());
+        assert!(texture.is_valid(&mut context.into()));
+        assert!(!texture.is_valid_for_raster());
+    }
+
     #[test]
     fn test_compression_type_naming() {
         // legacy type (replaced in m81 by ETC2_RGB8_UNORM)
@@ -0,0 +1,206 @@
+//! Conversions between the pixel types of the [image](https://crates.io/crates/image) crate and
+//! [Image], [Bitmap] and [Pixmap].
+//!
+//! 8 bit RGBA and grayscale buffers can be borrowed as a [Pixmap] without copying their pixels,
+//! all other conversions copy the pixels.
+
+use crate::prelude::*;
+use crate::{image::CachingHint, AlphaType, Bitmap, ColorType, Data, Image, ImageInfo, Pixmap};
+use ::image::{DynamicImage, GrayImage, RgbaImage};
+use std::{convert::TryFrom, error::Error, fmt};
+
+/// Error when pixels can not be converted between the image crate and Skia, for example
+/// because the dimensions are out of range or the source color type is unknown.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ImageConversionError;
+
+impl fmt::Display for ImageConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Failed to convert pixels")
+    }
+}
+
+impl Error for ImageConversionError {}
+
+fn image_info(
+    (width, height): (u32, u32),
+    color_type: ColorType,
+) -> Result<ImageInfo, ImageConversionError> {
+    let width = i32::try_from(width).map_err(|_| ImageConversionError)?;
+    let height = i32::try_from(height).map_err(|_| ImageConversionError)?;
+    let alpha_type = if color_type == ColorType::Gray8 {
+        AlphaType::Opaque
+    } else {
+        AlphaType::Unpremul
+    };
+    Ok(ImageInfo::new(
+        (width, height),
+        color_type,
+        alpha_type,
+        None,
+    ))
+}
+
+impl<'a> TryFrom<&'a RgbaImage> for Borrows<'a, Pixmap> {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &'a RgbaImage) -> Result<Self, Self::Error> {
+        let info = image_info(image.dimensions(), ColorType::RGBA8888)?;
+        Ok(Pixmap::new(&info, image.as_raw(), info.min_row_bytes()))
+    }
+}
+
+impl<'a> TryFrom<&'a GrayImage> for Borrows<'a, Pixmap> {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &'a GrayImage) -> Result<Self, Self::Error> {
+        let info = image_info(image.dimensions(), ColorType::Gray8)?;
+        Ok(Pixmap::new(&info, image.as_raw(), info.min_row_bytes()))
+    }
+}
+
+impl TryFrom<&RgbaImage> for Image {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &RgbaImage) -> Result<Self, Self::Error> {
+        let info = image_info(image.dimensions(), ColorType::RGBA8888)?;
+        Image::from_raster_data(&info, Data::new_copy(image.as_raw()), info.min_row_bytes())
+            .ok_or(ImageConversionError)
+    }
+}
+
+impl TryFrom<&GrayImage> for Image {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &GrayImage) -> Result<Self, Self::Error> {
+        let info = image_info(image.dimensions(), ColorType::Gray8)?;
+        Image::from_raster_data(&info, Data::new_copy(image.as_raw()), info.min_row_bytes())
+            .ok_or(ImageConversionError)
+    }
+}
+
+impl TryFrom<&DynamicImage> for Image {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &DynamicImage) -> Result<Self, Self::Error> {
+        match image {
+            DynamicImage::ImageRgba8(image) => Image::try_from(image),
+            DynamicImage::ImageLuma8(image) => Image::try_from(image),
+            image => Image::try_from(&image.to_rgba8()),
+        }
+    }
+}
+
+impl TryFrom<&RgbaImage> for Bitmap {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &RgbaImage) -> Result<Self, Self::Error> {
+        bitmap_from_pixmap(&Borrows::<Pixmap>::try_from(image)?)
+    }
+}
+
+impl TryFrom<&GrayImage> for Bitmap {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &GrayImage) -> Result<Self, Self::Error> {
+        bitmap_from_pixmap(&Borrows::<Pixmap>::try_from(image)?)
+    }
+}
+
+impl TryFrom<&DynamicImage> for Bitmap {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &DynamicImage) -> Result<Self, Self::Error> {
+        match image {
+            DynamicImage::ImageRgba8(image) => Bitmap::try_from(image),
+            DynamicImage::ImageLuma8(image) => Bitmap::try_from(image),
+            image => Bitmap::try_from(&image.to_rgba8()),
+        }
+    }
+}
+
+fn bitmap_from_pixmap(pixmap: &Pixmap) -> Result<Bitmap, ImageConversionError> {
+    let mut bitmap = Bitmap::new();
+    if !bitmap.try_alloc_pixels_info(pixmap.info(), None) {
+        return Err(ImageConversionError);
+    }
+    pixmap
+        .read_pixels_to_pixmap(bitmap.pixmap(), (0, 0))
+        .if_true_some(bitmap)
+        .ok_or(ImageConversionError)
+}
+
+/// Reads the pixels into a RGBA buffer, `read` is invoked with the destination info, pixels and
+/// row bytes.
+fn read_rgba(
+    (width, height): (i32, i32),
+    read: impl FnOnce(&ImageInfo, &mut [u8], usize) -> bool,
+) -> Result<RgbaImage, ImageConversionError> {
+    let dimensions = (
+        u32::try_from(width).map_err(|_| ImageConversionError)?,
+        u32::try_from(height).map_err(|_| ImageConversionError)?,
+    );
+    let info = image_info(dimensions, ColorType::RGBA8888)?;
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+    if !read(&info, &mut pixels, row_bytes) {
+        return Err(ImageConversionError);
+    }
+    RgbaImage::from_raw(dimensions.0, dimensions.1, pixels).ok_or(ImageConversionError)
+}
+
+impl TryFrom<&Pixmap> for RgbaImage {
+    type Error = ImageConversionError;
+
+    fn try_from(pixmap: &Pixmap) -> Result<Self, Self::Error> {
+        read_rgba(
+            (pixmap.width(), pixmap.height()),
+            |info, pixels, row_bytes| pixmap.read_pixels(info, pixels, row_bytes, (0, 0)),
+        )
+    }
+}
+
+impl TryFrom<&Bitmap> for RgbaImage {
+    type Error = ImageConversionError;
+
+    fn try_from(bitmap: &Bitmap) -> Result<Self, Self::Error> {
+        RgbaImage::try_from(bitmap.pixmap())
+    }
+}
+
+/// Texture-backed images can not be converted, use [Image::new_raster_image()] first.
+impl TryFrom<&Image> for RgbaImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        read_rgba(
+            (image.width(), image.height()),
+            |info, pixels, row_bytes| {
+                image.read_pixels(info, pixels, row_bytes, (0, 0), CachingHint::Disallow)
+            },
+        )
+    }
+}
+
+impl TryFrom<&Image> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        RgbaImage::try_from(image).map(DynamicImage::ImageRgba8)
+    }
+}
+
+#[test]
+fn test_rgba_round_trip() {
+    let mut buffer = RgbaImage::new(3, 2);
+    buffer.put_pixel(1, 1, ::image::Rgba([10, 20, 30, 255]));
+
+    let pixmap = Borrows::<Pixmap>::try_from(&buffer).unwrap();
+    assert_eq!(pixmap.get_color((1, 1)), crate::Color::from_rgb(10, 20, 30));
+
+    let image = Image::try_from(&buffer).unwrap();
+    assert_eq!(RgbaImage::try_from(&image).unwrap(), buffer);
+
+    let bitmap = Bitmap::try_from(&DynamicImage::ImageRgba8(buffer.clone())).unwrap();
+    assert_eq!(RgbaImage::try_from(&bitmap).unwrap(), buffer);
+}
@@ -1,9 +1,10 @@
 use crate::prelude::*;
-use crate::{ColorFilter, FilterQuality, IRect, Matrix, NativeFlattenable, Rect};
+use crate::{ColorFilter, FilterQuality, Flattenable, IRect, Matrix, NativeFlattenable, Rect};
 use skia_bindings as sb;
 use skia_bindings::{
     SkColorFilter, SkFlattenable, SkImageFilter, SkImageFilter_CropRect, SkRefCntBase,
 };
+use std::fmt::Write;
 use std::ptr;
 
 #[derive(Clone)]
@@ -96,6 +97,19 @@ impl NativeFlattenable for SkImageFilter {
 impl ImageFilter {
     // TODO: wrapfilterImage()? SkSpecialImage is declared in src/core/
 
+    /// Maps device space bounds through the filter, taking the transformation `ctm` into
+    /// account.
+    ///
+    /// With [MapDirection::Forward], `src` are the bounds of the content the filter is applied
+    /// to and the result are the bounds the filter draws into. A layer for a blur or a shadow
+    /// needs to be large enough to contain these bounds to avoid clipping the effect.
+    ///
+    /// With [MapDirection::Reverse], `src` are the bounds that should be drawn and the result
+    /// are the bounds of the content that contributes to them, for example to find out which
+    /// part of a layer needs to be rendered.
+    ///
+    /// `input_rect` is only used with [MapDirection::Reverse] and should be the bounds of the
+    /// content, if known.
     pub fn filter_bounds<'a>(
         &self,
         src: impl AsRef<IRect>,
@@ -156,6 +170,34 @@ impl ImageFilter {
         })
     }
 
+    /// Returns the inputs of the filter. An input that is `None` is the source image the filter
+    /// is applied to.
+    pub fn inputs(&self) -> impl Iterator<Item = Option<ImageFilter>> + '_ {
+        (0..self.count_inputs()).map(move |i| self.get_input(i))
+    }
+
+    /// Returns the crop rect the filter was created with, or `None` if the filter is not
+    /// cropped.
+    pub fn crop_rect(&self) -> Option<CropRect> {
+        let mut crop_rect = CropRect::default();
+        unsafe { sb::C_SkImageFilter_cropRect(self.native(), crop_rect.native_mut()) }
+            .if_true_some(crop_rect)
+    }
+
+    /// Returns a readable tree of the filter graph that starts at this filter, for debugging
+    /// composed filters.
+    ///
+    /// Every line describes one filter with its type name, its crop rect and the color filter
+    /// it applies, if any. The inputs of a filter are listed below it, indented by two spaces.
+    /// `source` marks an input that is the source image. A filter that is used more than once
+    /// in the graph is only described the first time it appears, later it is marked as
+    /// `(shared)`.
+    pub fn dump_tree(&self) -> String {
+        let mut tree = String::new();
+        dump_filter(self, 0, &mut Vec::new(), &mut tree);
+        tree
+    }
+
     pub fn compute_fast_bounds(&self, bounds: impl AsRef<Rect>) -> Rect {
         Rect::from_native_c(unsafe {
             sb::C_SkImageFilter_computeFastBounds(self.native(), bounds.as_ref().native())
@@ -166,6 +208,10 @@ impl ImageFilter {
         unsafe { self.native().canComputeFastBounds() }
     }
 
+    /// Returns a filter that applies this filter as if `matrix` were concatenated to the
+    /// canvas matrix, so that the parameters of a filter graph that is defined in a normalized
+    /// space, like blur sigmas and offsets, can be scaled and placed for every draw without
+    /// building the graph again.
     pub fn with_local_matrix(&self, matrix: &Matrix) -> Option<ImageFilter> {
         ImageFilter::from_ptr(unsafe {
             sb::C_SkImageFilter_makeWithLocalMatrix(self.native(), matrix.native())
@@ -181,6 +227,59 @@ impl ImageFilter {
     }
 }
 
+fn dump_filter(
+    filter: &ImageFilter,
+    depth: usize,
+    visited: &mut Vec<*const SkImageFilter>,
+    tree: &mut String,
+) {
+    let indent = depth * 2;
+    write!(
+        tree,
+        "{:indent$}{}",
+        "",
+        filter.type_name().to_string_lossy(),
+        indent = indent
+    )
+    .unwrap();
+
+    let ptr = filter.native() as *const SkImageFilter;
+    if visited.contains(&ptr) {
+        tree.push_str(" (shared)\n");
+        return;
+    }
+    visited.push(ptr);
+
+    if let Some(crop_rect) = filter.crop_rect() {
+        let rect = crop_rect.rect();
+        write!(
+            tree,
+            " crop: [{}, {}, {}, {}]",
+            rect.left, rect.top, rect.right, rect.bottom
+        )
+        .unwrap();
+        if crop_rect.flags() != crop_rect::CropEdge::HAS_ALL {
+            write!(tree, " {:?}", crop_rect.flags()).unwrap();
+        }
+    }
+    if let Some(color_filter) = filter.color_filter_node() {
+        write!(
+            tree,
+            " color filter: {}",
+            color_filter.type_name().to_string_lossy()
+        )
+        .unwrap();
+    }
+    tree.push('\n');
+
+    for input in filter.inputs() {
+        match input {
+            Some(input) => dump_filter(&input, depth + 1, visited, tree),
+            None => writeln!(tree, "{:indent$}source", "", indent = indent + 2).unwrap(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{CropRect, MapDirection, NativeTransmutable};
@@ -197,4 +296,58 @@ mod tests {
     fn test_map_direction_naming() {
         let _ = MapDirection::Forward;
     }
+
+    #[test]
+    fn test_filter_bounds_of_blur() {
+        use crate::{image_filters, Contains, IRect, Matrix};
+
+        let blur = image_filters::blur((4.0, 4.0), None, None, None).unwrap();
+        let content = IRect::new(10, 10, 20, 20);
+        let scale = Matrix::scale((2.0, 2.0));
+
+        let forward = blur.filter_bounds(content, &scale, MapDirection::Forward, None);
+        assert!(forward.contains(&content));
+        // 3 sigma, scaled by the matrix.
+        assert_eq!(forward, IRect::new(-14, -14, 44, 44));
+
+        let reverse = blur.filter_bounds(content, &scale, MapDirection::Reverse, Some(&content));
+        assert!(reverse.contains(&content));
+    }
+
+    #[test]
+    fn test_with_local_matrix() {
+        use crate::{image_filters, IRect, Matrix};
+
+        let offset = image_filters::offset((10.0, 0.0), None, None).unwrap();
+        let scaled = offset
+            .with_local_matrix(&Matrix::scale((2.0, 2.0)))
+            .unwrap();
+        let bounds = IRect::new(0, 0, 10, 10);
+        assert_eq!(
+            scaled.filter_bounds(bounds, &Matrix::new_identity(), MapDirection::Forward, None),
+            IRect::new(20, 0, 30, 10)
+        );
+    }
+
+    #[test]
+    fn test_dump_tree() {
+        use crate::{image_filters, IRect};
+
+        let crop = IRect::new(0, 0, 100, 50);
+        let blur = image_filters::blur((2.0, 2.0), None, None, &crop).unwrap();
+        assert_eq!(blur.crop_rect().unwrap().rect(), &crate::Rect::from(crop));
+
+        let merged =
+            image_filters::merge(vec![Some(blur.clone()), Some(blur), None], None).unwrap();
+        assert!(merged.crop_rect().is_none());
+        assert_eq!(merged.inputs().count(), 3);
+
+        let tree = merged.dump_tree();
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("  ") && lines[1].ends_with("crop: [0, 0, 100, 50]"));
+        assert_eq!(lines[2], "    source");
+        assert!(lines[3].ends_with(" (shared)"));
+        assert_eq!(lines[4], "  source");
+    }
 }
@@ -3,7 +3,10 @@ use crate::gpu;
 use crate::prelude::*;
 use crate::{image, ColorSpace, Data, ISize, ImageInfo, Matrix, Paint, Picture};
 use skia_bindings as sb;
-use skia_bindings::SkImageGenerator;
+use skia_bindings::{SkData, SkImageGenerator, SkImageInfo};
+use std::os::raw;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
 
 pub type ImageGenerator = RefHandle<SkImageGenerator>;
 unsafe impl Send for ImageGenerator {}
@@ -52,6 +55,24 @@ impl ImageGenerator {
 
     // TODO: generateTexture()
 
+    /// Creates a generator that produces the pixels of an image of the given `info` with
+    /// `generator`, for example by decoding tiles of a huge image file on demand.
+    ///
+    /// Wrap the generator with [crate::Image::from_generator()] to create a lazily generated image.
+    /// Skia requests the pixels when the image is drawn or read, and may cache them.
+    pub fn new_custom(info: &ImageInfo, generator: impl CustomImageGenerator + 'static) -> Self {
+        let generator: Box<Box<dyn CustomImageGenerator>> = Box::new(Box::new(generator));
+        let param = sb::RustImageGenerator_Param {
+            // note: the native generator owns the boxed trait object and drops it in its
+            // destructor.
+            generator: Box::into_raw(generator) as *mut raw::c_void,
+            drop: Some(drop_trampoline),
+            getPixels: Some(get_pixels_trampoline),
+            refEncodedData: Some(encoded_data_trampoline),
+        };
+        Self::from_ptr(unsafe { sb::C_RustImageGenerator_new(info.native(), &param) }).unwrap()
+    }
+
     pub fn from_encoded(encoded: impl Into<Data>) -> Option<Self> {
         Self::from_ptr(unsafe { sb::C_SkImageGenerator_MakeFromEncoded(encoded.into().into_ptr()) })
     }
@@ -76,3 +97,122 @@ impl ImageGenerator {
         })
     }
 }
+
+/// Produces the pixels of an [ImageGenerator] in Rust, see [ImageGenerator::new_custom()].
+pub trait CustomImageGenerator: Send {
+    /// Writes the pixels of the whole image in the format of `info` to `pixels`.
+    ///
+    /// The dimensions of `info` are the ones the generator was created with, but its color type,
+    /// alpha type and color space may differ. Returns `false` if the pixels can not be generated
+    /// in the requested format.
+    fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+
+    /// Returns the encoded data of the image, if the generator is based on an encoded format.
+    fn encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+}
+
+type BoxedCustomImageGenerator = Box<dyn CustomImageGenerator>;
+
+unsafe extern "C" fn drop_trampoline(generator: *mut raw::c_void) {
+    drop(Box::from_raw(generator as *mut BoxedCustomImageGenerator))
+}
+
+unsafe extern "C" fn get_pixels_trampoline(
+    generator: *mut raw::c_void,
+    info: *const SkImageInfo,
+    pixels: *mut raw::c_void,
+    row_bytes: usize,
+) -> bool {
+    let generator = &mut *(generator as *mut BoxedCustomImageGenerator);
+    let info = ImageInfo::from_native_ref(&*info);
+    let pixels = slice::from_raw_parts_mut(pixels as *mut u8, info.compute_byte_size(row_bytes));
+    // Panics must not unwind into Skia, they are reported as a failure instead.
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        generator.get_pixels(info, pixels, row_bytes)
+    }))
+    .unwrap_or(false)
+}
+
+unsafe extern "C" fn encoded_data_trampoline(generator: *mut raw::c_void) -> *mut SkData {
+    let generator = &mut *(generator as *mut BoxedCustomImageGenerator);
+    panic::catch_unwind(AssertUnwindSafe(|| generator.encoded_data()))
+        .ok()
+        .flatten()
+        .map(|data| data.into_ptr())
+        .unwrap_or(ptr::null_mut())
+}
+
+#[test]
+fn test_custom_image_generator() {
+    use crate::image::CachingHint;
+    use crate::{AlphaType, ColorType, Image};
+
+    struct Stripes;
+
+    impl CustomImageGenerator for Stripes {
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::RGBA8888 {
+                return false;
+            }
+            for (y, row) in pixels.chunks_mut(row_bytes).enumerate() {
+                let value = if y % 2 == 0 { 0xff } else { 0 };
+                for pixel in row[..info.min_row_bytes()].chunks_mut(4) {
+                    pixel.copy_from_slice(&[value, value, value, 0xff]);
+                }
+            }
+            true
+        }
+    }
+
+    let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+    let mut generator = ImageGenerator::new_custom(&info, Stripes);
+    assert_eq!(generator.info().dimensions(), ISize::new(4, 4));
+    assert!(generator.encoded_data().is_none());
+
+    let image = Image::from_generator(generator).unwrap();
+    assert!(image.is_lazy_generated());
+    let mut pixels = [0u8; 4 * 4 * 4];
+    assert!(image.read_pixels(
+        &info,
+        &mut pixels,
+        info.min_row_bytes(),
+        (0, 0),
+        CachingHint::Allow
+    ));
+    assert_eq!(&pixels[0..4], &[0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(&pixels[16..20], &[0, 0, 0, 0xff]);
+}
+
+#[test]
+fn test_panicking_custom_image_generator() {
+    use crate::image::CachingHint;
+    use crate::Image;
+
+    struct Panics;
+
+    impl CustomImageGenerator for Panics {
+        fn get_pixels(&mut self, _info: &ImageInfo, _pixels: &mut [u8], _row_bytes: usize) -> bool {
+            panic!("get_pixels")
+        }
+
+        fn encoded_data(&mut self) -> Option<Data> {
+            panic!("encoded_data")
+        }
+    }
+
+    let info = ImageInfo::new_n32_premul((4, 4), None);
+    let mut generator = ImageGenerator::new_custom(&info, Panics);
+    assert!(generator.encoded_data().is_none());
+
+    let image = Image::from_generator(generator).unwrap();
+    let mut pixels = [0u8; 4 * 4 * 4];
+    assert!(!image.read_pixels(
+        &info,
+        &mut pixels,
+        info.min_row_bytes(),
+        (0, 0),
+        CachingHint::Disallow
+    ));
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::{
     scalar, BlendMode, Color, Color4f, ColorFilter, ColorSpace, FilterQuality, ImageFilter,
-    MaskFilter, Path, PathEffect, Rect, Shader,
+    MaskFilter, Matrix, Path, PathEffect, Rect, Shader,
 };
 use skia_bindings as sb;
 use skia_bindings::SkPaint;
@@ -93,6 +93,8 @@ impl Paint {
         unsafe { self.native().__bindgen_anon_1.fBitfields.fDither() != 0 }
     }
 
+    /// Enables dithering, which reduces the banding of gradients and other shaders that are
+    /// drawn into destinations with 8 bits per channel or less.
     pub fn set_dither(&mut self, dither: bool) -> &mut Self {
         unsafe {
             self.native_mut()
@@ -236,6 +238,19 @@ impl Paint {
         .if_true_some(r)
     }
 
+    /// Same as [Self::get_fill_path()], but the precision of the curves of the outline is
+    /// derived from `matrix`, the transformation the path is drawn with, the same way Skia does
+    /// when it strokes a path.
+    pub fn get_fill_path_with_matrix(
+        &self,
+        src: &Path,
+        cull_rect: Option<&Rect>,
+        matrix: &Matrix,
+    ) -> Option<Path> {
+        let res_scale = unsafe { sb::C_SkPaint_resScaleForStroking(matrix.native()) };
+        self.get_fill_path(src, cull_rect, res_scale)
+    }
+
     pub fn shader(&self) -> Option<Shader> {
         Shader::from_unshared_ptr(self.native().fShader.fPtr)
     }
@@ -365,3 +380,23 @@ fn union_flags() {
         paint.set_style(Style::Fill);
     }
 }
+
+#[test]
+fn fill_path_with_matrix() {
+    let mut line = Path::new();
+    line.move_to((0.0, 0.0)).line_to((100.0, 0.0));
+    let mut stroke = Paint::default();
+    stroke.set_style(Style::Stroke).set_stroke_width(4.0);
+    stroke.set_path_effect(PathEffect::dash(&[10.0, 10.0], 0.0));
+
+    let dashed = stroke
+        .get_fill_path_with_matrix(&line, None, &Matrix::scale((2.0, 2.0)))
+        .unwrap();
+    assert!(dashed.contains((5.0, 1.0)));
+    assert!(!dashed.contains((15.0, 1.0)));
+
+    stroke.set_stroke_width(0.0);
+    assert!(stroke
+        .get_fill_path_with_matrix(&line, None, &Matrix::default())
+        .is_none());
+}
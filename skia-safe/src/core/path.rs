@@ -9,7 +9,8 @@ use crate::interop::DynamicMemoryWStream;
 use crate::matrix::ApplyPerspectiveClip;
 use crate::prelude::*;
 use crate::{
-    scalar, Data, Matrix, PathDirection, PathFillType, PathSegmentMask, Point, RRect, Rect, Vector,
+    scalar, Data, Matrix, Paint, PaintCap, PaintJoin, PaintStyle, PathDirection, PathFillType,
+    PathSegmentMask, Point, RRect, Rect, Vector,
 };
 use skia_bindings as sb;
 use skia_bindings::{SkPath, SkPath_Iter};
@@ -1068,6 +1069,53 @@ impl Path {
         unsafe { self.native().contains(p.x, p.y) }
     }
 
+    /// Returns true if the point `p` is within the area that is covered when the path is drawn
+    /// with `paint`, taking its style, stroke and path effect into account.
+    ///
+    /// `tolerance` extends the area by that distance in all directions, for example to make thin
+    /// shapes easier to hit with touch input. Curves, including conics, are tested against their
+    /// exact outline, so no rasterization is involved.
+    pub fn hit_test(&self, p: impl Into<Point>, paint: &Paint, tolerance: scalar) -> bool {
+        let p = p.into();
+        let tolerance = tolerance.max(0.0);
+        // path effects like dashes only need to be computed near the point.
+        let outset = tolerance + paint.stroke_width() + 1.0;
+        let cull_rect = Rect::new(p.x - outset, p.y - outset, p.x + outset, p.y + outset);
+
+        let plain_fill = paint.style() == PaintStyle::Fill && paint.path_effect().is_none();
+        let fill_path;
+        let (area, hairline) = if plain_fill {
+            (self, false)
+        } else {
+            match paint.get_fill_path(self, Some(&cull_rect), None) {
+                Some(path) => {
+                    fill_path = path;
+                    (&fill_path, false)
+                }
+                // hairlines are drawn one pixel wide.
+                None => (self, true),
+            }
+        };
+
+        if !hairline && area.contains(p) {
+            return true;
+        }
+        if tolerance == 0.0 && !hairline {
+            return false;
+        }
+
+        let mut outline_paint = Paint::default();
+        outline_paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width((tolerance * 2.0).max(if hairline { 1.0 } else { 0.0 }))
+            .set_stroke_cap(PaintCap::Round)
+            .set_stroke_join(PaintJoin::Round);
+        outline_paint
+            .get_fill_path(area, Some(&cull_rect), None)
+            .map(|outline| outline.contains(p))
+            .unwrap_or(false)
+    }
+
     /// Write this path out to a value of type [Data]. This is for debugging purposes, if you want to write
     /// the path out in a format that can be read back later, you should use `fn serialize`.
     pub fn dump_as_data(&self, dump_as_hex: bool) -> Data {
@@ -1166,6 +1214,27 @@ mod tests {
         assert!(p.is_volatile());
     }
 
+    #[test]
+    fn test_hit_test() {
+        use crate::{Paint, PaintStyle};
+
+        let mut path = Path::new();
+        path.move_to((0.0, 0.0))
+            .conic_to((50.0, 0.0), (50.0, 50.0), 0.5);
+        path.close();
+
+        let fill = Paint::default();
+        assert!(path.hit_test((40.0, 10.0), &fill, 0.0));
+        assert!(!path.hit_test((10.0, 40.0), &fill, 0.0));
+
+        let mut stroke = Paint::default();
+        stroke.set_style(PaintStyle::Stroke).set_stroke_width(4.0);
+        assert!(!path.hit_test((40.0, 10.0), &stroke, 0.0));
+        assert!(path.hit_test((50.0, 49.0), &stroke, 0.0));
+        assert!(!path.hit_test((53.0, 49.0), &stroke, 0.0));
+        assert!(path.hit_test((53.0, 49.0), &stroke, 2.0));
+    }
+
     #[test]
     fn test_path_rect() {
         let r = Rect::new(0.0, 0.0, 100.0, 100.0);
@@ -69,12 +69,32 @@ pub mod point_data {
     }
 }
 
+/// The parameters of a dash path effect, see [PathEffect::as_a_dash()].
 #[derive(Clone, PartialEq, Debug)]
 pub struct DashInfo {
+    /// The lengths of the alternating "on" and "off" intervals, there is always an even number
+    /// of them.
     pub intervals: Vec<scalar>,
+    /// The offset into the intervals at which the dashing starts. Skia normalizes the phase to
+    /// `0..period()`, so it may differ from the phase the effect was created with.
     pub phase: scalar,
 }
 
+impl DashInfo {
+    /// The length of one repetition of the dash pattern.
+    pub fn period(&self) -> scalar {
+        self.intervals.iter().sum()
+    }
+
+    /// Returns the lengths of the "on" intervals paired with the lengths of the following "off"
+    /// intervals.
+    pub fn on_off_pairs(&self) -> impl Iterator<Item = (scalar, scalar)> + '_ {
+        self.intervals
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+    }
+}
+
 pub type PathEffect = RCHandle<SkPathEffect>;
 unsafe impl Send for PathEffect {}
 unsafe impl Sync for PathEffect {}
@@ -170,6 +190,21 @@ impl PathEffect {
         .if_true_some(point_data)
     }
 
+    /// Returns `true` if this is a dash path effect, which is cheaper than [Self::as_a_dash()],
+    /// because the intervals are not copied.
+    pub fn is_dash(&self) -> bool {
+        let mut dash_info = construct(|di| unsafe { sb::C_SkPathEffect_DashInfo_Construct(di) });
+        match unsafe { self.native().asADash(&mut dash_info) } {
+            SkPathEffect_DashType::kDash_DashType => true,
+            SkPathEffect_DashType::kNone_DashType => false,
+        }
+    }
+
+    /// Returns the intervals and phase if this is a dash path effect, for example to show the
+    /// dash settings of a [crate::Paint] that was deserialized.
+    ///
+    /// Only effects created with [PathEffect::dash()] are recognized, [None] is returned for
+    /// all other effects, including those that combine a dash effect with other effects.
     // TODO: rename to to_a_dash()?
     pub fn as_a_dash(&self) -> Option<DashInfo> {
         let mut dash_info = construct(|di| unsafe { sb::C_SkPathEffect_DashInfo_Construct(di) });
@@ -209,3 +244,26 @@ fn create_and_drop_point_data() {
     let data = PointData::default();
     drop(data)
 }
+
+#[test]
+fn test_dash_readback() {
+    use crate::Flattenable;
+
+    let intervals = [10.0, 5.0, 2.0, 5.0];
+    let dash = PathEffect::dash(&intervals, 3.0).unwrap();
+    assert!(dash.is_dash());
+
+    let restored = PathEffect::deserialize(dash.serialize().as_bytes()).unwrap();
+    let info = restored.as_a_dash().unwrap();
+    assert_eq!(info.intervals, intervals);
+    assert_eq!(info.phase, 3.0);
+    assert_eq!(info.period(), 22.0);
+    assert_eq!(
+        info.on_off_pairs().collect::<Vec<_>>(),
+        [(10.0, 5.0), (2.0, 5.0)]
+    );
+
+    let corner = PathEffect::corner_path(4.0).unwrap();
+    assert!(!corner.is_dash());
+    assert!(PathEffect::sum(dash, corner).as_a_dash().is_none());
+}
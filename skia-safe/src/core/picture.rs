@@ -3,6 +3,9 @@ use crate::{Canvas, Data, Rect};
 use crate::{Matrix, Shader, TileMode};
 use skia_bindings as sb;
 use skia_bindings::{SkPicture, SkRefCntBase};
+use std::any::Any;
+use std::os::raw;
+use std::panic::{self, AssertUnwindSafe};
 
 pub type Picture = RCHandle<SkPicture>;
 unsafe impl Sync for Picture {}
@@ -27,16 +30,67 @@ impl Picture {
         })
     }
 
-    // TODO: AbortCallback and the function that use it.
-
     pub fn playback(&self, canvas: &mut Canvas) {
         unsafe { sb::C_SkPicture_playback(self.native(), canvas.native_mut()) }
     }
 
+    /// Draws the picture like [Self::playback()], but calls `abort` before each drawing
+    /// operation and stops as soon as it returns `true`, for example when a frame deadline
+    /// passed. Returns `true` if the playback was aborted.
+    ///
+    /// The state of the canvas is restored after an aborted playback. Pictures that are nested
+    /// in this picture are not aborted, and pictures with only a single operation ignore
+    /// `abort`.
+    ///
+    /// If `abort` panics, the playback is aborted and the panic is resumed after Skia returned.
+    pub fn playback_with_abort<F: FnMut() -> bool>(&self, canvas: &mut Canvas, abort: F) -> bool {
+        struct Context<F> {
+            abort: F,
+            aborted: bool,
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        unsafe extern "C" fn abort_trampoline<F: FnMut() -> bool>(
+            context: *mut raw::c_void,
+        ) -> bool {
+            let context = &mut *(context as *mut Context<F>);
+            let abort = &mut context.abort;
+            context.aborted = match panic::catch_unwind(AssertUnwindSafe(abort)) {
+                Ok(aborted) => aborted,
+                Err(payload) => {
+                    // Panics must not unwind into Skia.
+                    context.panic = Some(payload);
+                    true
+                }
+            };
+            context.aborted
+        }
+
+        let mut context = Context {
+            abort,
+            aborted: false,
+            panic: None,
+        };
+        unsafe {
+            sb::C_SkPicture_playbackWithAbort(
+                self.native(),
+                canvas.native_mut(),
+                &mut context as *mut _ as *mut raw::c_void,
+                Some(abort_trampoline::<F>),
+            )
+        }
+        if let Some(payload) = context.panic {
+            panic::resume_unwind(payload)
+        }
+        context.aborted
+    }
+
     pub fn cull_rect(&self) -> Rect {
         Rect::from_native_c(unsafe { sb::C_SkPicture_cullRect(self.native()) })
     }
 
+    /// An id that identifies the picture, which can be used as a cache key, see
+    /// [crate::utils::embedder_cache].
     pub fn unique_id(&self) -> u32 {
         unsafe { sb::C_SkPicture_uniqueID(self.native()) }
     }
@@ -97,3 +151,52 @@ impl Picture {
         .unwrap()
     }
 }
+
+#[test]
+fn test_playback_with_abort() {
+    use crate::{Color, Paint, PictureRecorder, Surface};
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::from_wh(10.0, 10.0), None);
+    let paint = Paint::default();
+    for i in 0..10 {
+        canvas.draw_rect(Rect::from_xywh(i as f32, 0.0, 1.0, 10.0), &paint);
+    }
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+    let mut calls = 0;
+    let aborted = picture.playback_with_abort(surface.canvas(), || {
+        calls += 1;
+        calls > 3
+    });
+    assert!(aborted);
+    assert_eq!(calls, 4);
+    let pixmap = surface.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((0, 5)), Color::BLACK);
+    assert_eq!(pixmap.get_color((9, 5)), Color::TRANSPARENT);
+
+    assert!(!picture.playback_with_abort(surface.canvas(), || false));
+}
+
+#[test]
+fn test_playback_with_panicking_abort() {
+    use crate::{PictureRecorder, Surface};
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::from_wh(10.0, 10.0), None);
+    canvas.draw_rect(Rect::from_wh(5.0, 5.0), &Default::default());
+    canvas.draw_rect(Rect::from_wh(10.0, 10.0), &Default::default());
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+    let mut calls = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        picture.playback_with_abort(surface.canvas(), || {
+            calls += 1;
+            panic!("abort")
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(calls, 1);
+}
@@ -8,6 +8,7 @@ pub use IPoint as IVector;
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IPoint {
     pub x: i32,
     pub y: i32,
@@ -104,6 +105,7 @@ pub type Vector = Point;
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: scalar,
     pub y: scalar,
@@ -8,6 +8,7 @@ use std::mem;
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IRect {
     pub left: i32,
     pub top: i32,
@@ -324,6 +325,7 @@ impl Contains<Rect> for IRect {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub left: scalar,
     pub top: scalar,
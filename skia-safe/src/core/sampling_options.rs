@@ -0,0 +1,119 @@
+use crate::prelude::*;
+use crate::{image::CubicResampler, FilterQuality, MipmapMode};
+
+pub use skia_bindings::SkSamplingMode as SamplingMode;
+#[test]
+fn test_sampling_mode_naming() {
+    let _ = SamplingMode::Linear;
+}
+
+/// The cubic resampler Skia uses for [FilterQuality::High].
+const MITCHELL: CubicResampler = CubicResampler {
+    B: 1.0 / 3.0,
+    C: 1.0 / 3.0,
+};
+
+/// Describes how pixels are sampled when images are scaled or transformed.
+///
+/// Not all functions of Skia m87 take sampling options yet. Functions that take a
+/// [FilterQuality] natively convert them with [SamplingOptions::filter_quality()].
+#[derive(Copy, Clone, Debug)]
+pub struct SamplingOptions {
+    pub use_cubic: bool,
+    pub cubic: CubicResampler,
+    pub sampling: SamplingMode,
+    pub mipmap: MipmapMode,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        SamplingOptions {
+            use_cubic: false,
+            cubic: CubicResampler { B: 0.0, C: 0.0 },
+            sampling: SamplingMode::Nearest,
+            mipmap: MipmapMode::None,
+        }
+    }
+}
+
+impl PartialEq for SamplingOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.use_cubic == other.use_cubic
+            && self.cubic.B == other.cubic.B
+            && self.cubic.C == other.cubic.C
+            && self.sampling == other.sampling
+            && self.mipmap == other.mipmap
+    }
+}
+
+impl SamplingOptions {
+    pub fn new(sampling: SamplingMode, mipmap: MipmapMode) -> Self {
+        SamplingOptions {
+            sampling,
+            mipmap,
+            ..Default::default()
+        }
+    }
+
+    /// The [FilterQuality] that samples the same way as these options.
+    ///
+    /// [FilterQuality::High] always uses the Mitchell cubic resampler, so [None] is returned for
+    /// other cubic resamplers, which can not be represented by a filter quality.
+    pub fn filter_quality(&self) -> Option<FilterQuality> {
+        if self.use_cubic {
+            return (self.cubic.B == MITCHELL.B && self.cubic.C == MITCHELL.C)
+                .if_true_some(FilterQuality::High);
+        }
+        Some(match (self.sampling, self.mipmap) {
+            (SamplingMode::Nearest, _) => FilterQuality::None,
+            (SamplingMode::Linear, MipmapMode::None) => FilterQuality::Low,
+            (SamplingMode::Linear, _) => FilterQuality::Medium,
+        })
+    }
+}
+
+impl From<CubicResampler> for SamplingOptions {
+    fn from(cubic: CubicResampler) -> Self {
+        SamplingOptions {
+            use_cubic: true,
+            cubic,
+            ..Default::default()
+        }
+    }
+}
+
+/// Converts the legacy [FilterQuality] the same way Skia does, [FilterQuality::High] uses the
+/// Mitchell cubic resampler.
+impl From<FilterQuality> for SamplingOptions {
+    fn from(quality: FilterQuality) -> Self {
+        match quality {
+            FilterQuality::None => SamplingOptions::new(SamplingMode::Nearest, MipmapMode::None),
+            FilterQuality::Low => SamplingOptions::new(SamplingMode::Linear, MipmapMode::None),
+            FilterQuality::Medium => {
+                SamplingOptions::new(SamplingMode::Linear, MipmapMode::Nearest)
+            }
+            FilterQuality::High => MITCHELL.into(),
+        }
+    }
+}
+
+#[test]
+fn test_filter_quality_round_trip() {
+    for quality in &[
+        FilterQuality::None,
+        FilterQuality::Low,
+        FilterQuality::Medium,
+        FilterQuality::High,
+    ] {
+        assert_eq!(
+            SamplingOptions::from(*quality).filter_quality(),
+            Some(*quality)
+        );
+    }
+}
+
+#[test]
+fn test_cubic_filter_quality() {
+    let catmull_rom = CubicResampler { B: 0.0, C: 0.5 };
+    assert_eq!(SamplingOptions::from(catmull_rom).filter_quality(), None);
+}
@@ -66,10 +66,14 @@ impl Default for RCHandle<SkShader> {
 }
 
 impl Shader {
+    /// Returns `true` if the shader is guaranteed to produce only opaque colors, in which case
+    /// drawing with it can skip blending, as long as the paint's alpha is opaque, too.
     pub fn is_opaque(&self) -> bool {
         unsafe { sb::C_SkShader_isOpaque(self.native()) }
     }
 
+    /// Returns the image, the local matrix and the tile modes if the shader draws an image, so
+    /// that simple cases can be drawn with [crate::Canvas::draw_image_rect()] instead.
     pub fn image(&self) -> Option<(Image, Matrix, (TileMode, TileMode))> {
         unsafe {
             let mut matrix = Matrix::default();
@@ -82,10 +86,17 @@ impl Shader {
         }
     }
 
+    /// Returns `true` if the shader draws an image, see [Self::image()].
     pub fn is_a_image(&self) -> bool {
         unsafe { sb::C_SkShader_isAImage(self.native()) }
     }
 
+    /// Returns a shader that maps its local coordinates through `matrix` before this shader
+    /// is evaluated, so that a shader that is defined in a normalized space, for example a
+    /// gradient from `(0, 0)` to `(1, 0)`, can be placed and scaled for every draw without
+    /// creating it again.
+    ///
+    /// `matrix` is combined with the local matrix the shader already has.
     pub fn with_local_matrix(&self, matrix: &Matrix) -> Self {
         Self::from_ptr(unsafe {
             sb::C_SkShader_makeWithLocalMatrix(self.native(), matrix.native())
@@ -140,3 +151,34 @@ pub mod shaders {
         panic!("removed without replacement");
     }
 }
+
+#[test]
+fn test_introspection() {
+    use crate::Surface;
+
+    assert!(shaders::color(Color::RED).is_opaque());
+    assert!(!shaders::color(Color::from_argb(0x80, 0xff, 0, 0)).is_opaque());
+    assert!(!shaders::color(Color::RED).is_a_image());
+
+    let surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+    let image = surface.image_snapshot();
+    let matrix = Matrix::translate((1.0, 2.0));
+    let shader = image.to_shader((TileMode::Repeat, TileMode::Mirror), &matrix);
+    assert!(shader.is_a_image());
+    let (shader_image, shader_matrix, tile_modes) = shader.image().unwrap();
+    assert_eq!(shader_image.unique_id(), image.unique_id());
+    assert_eq!(shader_matrix, matrix);
+    assert_eq!(tile_modes, (TileMode::Repeat, TileMode::Mirror));
+}
+
+#[test]
+fn test_with_local_matrix() {
+    use crate::Surface;
+
+    let surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+    let image = surface.image_snapshot();
+    let shader = image.to_shader(None, None);
+    let matrix = Matrix::scale((2.0, 3.0));
+    let (_, shader_matrix, _) = shader.with_local_matrix(&matrix).image().unwrap();
+    assert_eq!(shader_matrix, matrix);
+}
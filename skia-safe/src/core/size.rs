@@ -6,6 +6,7 @@ use std::ops::{Div, DivAssign, Mul, MulAssign};
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ISize {
     pub width: i32,
     pub height: i32,
@@ -59,6 +60,7 @@ impl ISize {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: scalar,
     pub height: scalar,
@@ -0,0 +1,38 @@
+use crate::ColorType;
+use std::{error::Error, fmt};
+
+/// The reason why a surface or a GPU context could not be created.
+///
+/// Skia itself only reports that creation failed, so the parameters are validated before
+/// Skia is called to find out what is wrong with them. If they are valid and Skia still fails,
+/// the error is [SkiaError::OutOfMemory] when only an allocation could have failed, and
+/// [SkiaError::Unknown] otherwise.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SkiaError {
+    /// A parameter is out of range, for example the dimensions are empty or too large, or the
+    /// row bytes are too small. The string describes the parameter.
+    InvalidParameters(&'static str),
+    /// The color type can not be used for the requested object, for example because the GPU
+    /// backend can not render to it.
+    UnsupportedColorType(ColorType),
+    /// The GPU context was abandoned, for example because the device was lost.
+    ContextAbandoned,
+    /// The memory for the pixels could not be allocated.
+    OutOfMemory,
+    /// Skia failed for a reason that could not be determined.
+    Unknown,
+}
+
+impl fmt::Display for SkiaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SkiaError::InvalidParameters(what) => write!(f, "Invalid parameters: {}", what),
+            SkiaError::UnsupportedColorType(ct) => write!(f, "Unsupported color type: {:?}", ct),
+            SkiaError::ContextAbandoned => write!(f, "The GPU context was abandoned"),
+            SkiaError::OutOfMemory => write!(f, "Out of memory"),
+            SkiaError::Unknown => write!(f, "Skia failed (reason unknown)"),
+        }
+    }
+}
+
+impl Error for SkiaError {}
@@ -3,21 +3,46 @@
 use crate::gpu::{self, BackendRenderTarget};
 use crate::prelude::*;
 use crate::{
-    Bitmap, Budgeted, Canvas, ColorSpace, ColorType, DeferredDisplayList, IPoint, IRect, ISize,
-    Image, ImageInfo, Paint, Pixmap, Size, SurfaceCharacterization, SurfaceProps,
+    AlphaType, Bitmap, BlendMode, Budgeted, Canvas, Color, ColorSpace, ColorType, Data,
+    DeferredDisplayList, EncodedImageFormat, FilterQuality, IPoint, IRect, ISize, Image, ImageInfo,
+    Paint, Pixmap, Rect, Size, SkiaError, SurfaceCharacterization, SurfaceProps,
 };
 use skia_bindings as sb;
 use skia_bindings::{SkRefCntBase, SkSurface};
-use std::ptr;
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ffi, process, ptr};
 
 pub use skia_bindings::SkSurface_BackendHandleAccess as BackendHandleAccess;
 pub use skia_bindings::SkSurface_BackendSurfaceAccess as BackendSurfaceAccess;
 pub use skia_bindings::SkSurface_ContentChangeMode as ContentChangeMode;
 
+/// A surface that can be drawn to through its [Canvas].
+///
+/// A surface is neither [Send] nor [Sync]. Functions that only read from it take `&self`, even
+/// though Skia may lazily create the canvas or update the cached snapshot internally. Functions
+/// that change its pixels require `&mut self`.
+///
+/// A surface that is not shared can be moved to another thread with
+/// [ConditionallySend::wrap_send()], unless it is backed by a GPU context, which must only be used
+/// from the thread it was created on.
+///
+/// ```compile_fail
+/// use skia_safe::Surface;
+///
+/// let surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+/// std::thread::spawn(move || drop(surface));
+/// ```
 pub type Surface = RCHandle<SkSurface>;
 
 impl NativeRefCountedBase for SkSurface {
     type Base = SkRefCntBase;
+
+    #[cfg(feature = "gpu")]
+    fn is_thread_affine(&self) -> bool {
+        let surface = self as *const SkSurface as *mut SkSurface;
+        !unsafe { (*surface).recordingContext() }.is_null()
+    }
 }
 
 impl Surface {
@@ -46,8 +71,62 @@ impl Surface {
         .map(move |surface| surface.borrows(pixels))
     }
 
+    /// Creates a raster surface that draws directly into `pixels`, which are owned by another
+    /// system, for example a memory mapped file or a frame of a video decoder.
+    ///
+    /// `release` is called with `pixels` when the surface is dropped. Image snapshots copy the
+    /// pixels, so they do not keep them alive. If the surface can not be created, `release` is
+    /// dropped without being called and the caller keeps the ownership of the pixels.
+    ///
+    /// `release` runs inside the destructor of the native surface, which a panic must not
+    /// unwind into, so the process is aborted if it panics.
+    ///
+    /// # Safety
+    ///
+    /// `pixels` must point to at least `image_info.compute_byte_size(row_bytes)` bytes that
+    /// stay valid and are not accessed otherwise until `release` is called.
+    pub unsafe fn new_raster_direct_with_release<F>(
+        image_info: &ImageInfo,
+        pixels: *mut u8,
+        row_bytes: impl Into<Option<usize>>,
+        release: F,
+        surface_props: Option<&SurfaceProps>,
+    ) -> Option<Surface>
+    where
+        F: FnOnce(*mut u8) + Send + 'static,
+    {
+        unsafe extern "C" fn release_trampoline<F: FnOnce(*mut u8)>(
+            pixels: *mut ffi::c_void,
+            context: *mut ffi::c_void,
+        ) {
+            let release = Box::from_raw(context as *mut F);
+            if panic::catch_unwind(AssertUnwindSafe(|| release(pixels as *mut u8))).is_err() {
+                eprintln!("Panic in FFI callback for the `SkSurface` release proc");
+                process::abort();
+            }
+        }
+
+        let row_bytes = row_bytes
+            .into()
+            .unwrap_or_else(|| image_info.min_row_bytes());
+        let context = Box::into_raw(Box::new(release));
+
+        let surface = Self::from_ptr(sb::C_SkSurface_MakeRasterDirectReleaseProc(
+            image_info.native(),
+            pixels as _,
+            row_bytes,
+            Some(release_trampoline::<F>),
+            context as _,
+            surface_props.native_ptr_or_null(),
+        ));
+        if surface.is_none() {
+            // Skia does not call the release proc if it rejects the arguments.
+            drop(Box::from_raw(context));
+        }
+        surface
+    }
+
     // TODO: MakeRasterDirect(&Pixmap)
-    // TODO: MakeRasterDirectReleaseProc()?
 
     pub fn new_raster(
         image_info: &ImageInfo,
@@ -69,6 +148,57 @@ impl Surface {
             sb::C_SkSurface_MakeRasterN32Premul(size.width, size.height, ptr::null())
         })
     }
+
+    /// Same as [Self::new_raster()], but returns the reason why the surface could not be
+    /// created.
+    pub fn try_new_raster(
+        image_info: &ImageInfo,
+        row_bytes: impl Into<Option<usize>>,
+        surface_props: Option<&SurfaceProps>,
+    ) -> Result<Self, SkiaError> {
+        let row_bytes = row_bytes.into();
+        validate_raster_info(image_info, row_bytes)?;
+        Self::new_raster(image_info, row_bytes, surface_props).ok_or(SkiaError::OutOfMemory)
+    }
+
+    /// Same as [Self::new_raster_n32_premul()], but returns the reason why the surface could not
+    /// be created.
+    pub fn try_new_raster_n32_premul(size: impl Into<ISize>) -> Result<Self, SkiaError> {
+        Self::try_new_raster(&ImageInfo::new_n32_premul(size, None), None, None)
+    }
+}
+
+/// Checks the parameters of a raster surface like Skia does, see `SkSurfaceValidateRasterInfo()`.
+fn validate_raster_info(image_info: &ImageInfo, row_bytes: Option<usize>) -> Result<(), SkiaError> {
+    const MAX_DIMENSION: i32 = i32::MAX >> 2;
+    const MAX_TOTAL_SIZE: usize = i32::MAX as usize;
+
+    if image_info.is_empty() {
+        return Err(SkiaError::InvalidParameters("dimensions are empty"));
+    }
+    if image_info.width() > MAX_DIMENSION || image_info.height() > MAX_DIMENSION {
+        return Err(SkiaError::InvalidParameters("dimensions are too large"));
+    }
+    if image_info.color_type() == ColorType::Unknown {
+        return Err(SkiaError::UnsupportedColorType(ColorType::Unknown));
+    }
+    if image_info.alpha_type() == AlphaType::Unknown {
+        return Err(SkiaError::InvalidParameters("alpha type is unknown"));
+    }
+    let row_bytes = match row_bytes {
+        Some(row_bytes) if row_bytes != 0 => row_bytes,
+        _ => image_info.min_row_bytes(),
+    };
+    if !image_info.valid_row_bytes(row_bytes) {
+        return Err(SkiaError::InvalidParameters(
+            "row bytes are too small or not aligned to pixels",
+        ));
+    }
+    let height = usize::try_from(image_info.height()).unwrap();
+    match row_bytes.checked_mul(height) {
+        Some(size) if size <= MAX_TOTAL_SIZE => Ok(()),
+        _ => Err(SkiaError::InvalidParameters("pixels are too large")),
+    }
 }
 
 #[cfg(feature = "gpu")]
@@ -199,6 +329,56 @@ impl Surface {
         })
     }
 
+    /// Same as [Self::new_render_target()], but returns the reason why the surface could not be
+    /// created.
+    ///
+    /// The parameters are checked against the capabilities of the context, so a
+    /// [SkiaError::Unknown] error usually means that the GPU backend failed to allocate the
+    /// render target.
+    pub fn try_new_render_target(
+        context: &mut gpu::RecordingContext,
+        budgeted: Budgeted,
+        image_info: &ImageInfo,
+        sample_count: impl Into<Option<usize>>,
+        surface_origin: gpu::SurfaceOrigin,
+        surface_props: Option<&SurfaceProps>,
+        should_create_with_mips: impl Into<Option<bool>>,
+    ) -> Result<Self, SkiaError> {
+        let sample_count = sample_count.into();
+        if context.abandoned() {
+            return Err(SkiaError::ContextAbandoned);
+        }
+        if image_info.is_empty() {
+            return Err(SkiaError::InvalidParameters("dimensions are empty"));
+        }
+        let max_size = context.max_render_target_size();
+        if image_info.width() > max_size || image_info.height() > max_size {
+            return Err(SkiaError::InvalidParameters(
+                "dimensions exceed the maximum render target size",
+            ));
+        }
+        let color_type = image_info.color_type();
+        if !context.color_type_supported_as_surface(color_type) {
+            return Err(SkiaError::UnsupportedColorType(color_type));
+        }
+        let max_sample_count = context.max_surface_sample_count_for_color_type(color_type);
+        if sample_count.unwrap_or(0) > max_sample_count {
+            return Err(SkiaError::InvalidParameters(
+                "sample count exceeds the maximum for the color type",
+            ));
+        }
+        Self::new_render_target(
+            context,
+            budgeted,
+            image_info,
+            sample_count,
+            surface_origin,
+            surface_props,
+            should_create_with_mips,
+        )
+        .ok_or(SkiaError::Unknown)
+    }
+
     pub fn new_render_target_with_characterization(
         context: &mut gpu::RecordingContext,
         characterization: &SurfaceCharacterization,
@@ -232,14 +412,14 @@ impl Surface {
         unsafe { sb::C_SkSurface_height(self.native()) }
     }
 
-    pub fn image_info(&mut self) -> ImageInfo {
+    pub fn image_info(&self) -> ImageInfo {
         let mut info = ImageInfo::default();
-        unsafe { sb::C_SkSurface_imageInfo(self.native_mut(), info.native_mut()) };
+        unsafe { sb::C_SkSurface_imageInfo(self.native_mut_force(), info.native_mut()) };
         info
     }
 
-    pub fn generation_id(&mut self) -> u32 {
-        unsafe { self.native_mut().generationID() }
+    pub fn generation_id(&self) -> u32 {
+        unsafe { (*self.native_mut_force()).generationID() }
     }
 
     pub fn notify_content_will_change(&mut self, mode: ContentChangeMode) -> &mut Self {
@@ -261,10 +441,18 @@ impl Surface {
             .map(|dc| dc.into())
     }
 
-    pub fn recording_context(&mut self) -> Option<gpu::RecordingContext> {
-        gpu::RecordingContext::from_unshared_ptr(unsafe { self.native_mut().recordingContext() })
+    pub fn recording_context(&self) -> Option<gpu::RecordingContext> {
+        gpu::RecordingContext::from_unshared_ptr(unsafe {
+            (*self.native_mut_force()).recordingContext()
+        })
     }
 
+    /// Returns the texture that backs a GPU surface, or [None] if the surface is not backed by a
+    /// texture. The backend specific properties, for example the id of a GL texture, are
+    /// available through [gpu::BackendTexture::gl_texture_info()] and
+    /// [gpu::BackendTexture::vulkan_image_info()].
+    ///
+    /// Pending work must be flushed before the texture is used outside of Skia.
     pub fn get_backend_texture(
         &mut self,
         handle_access: BackendHandleAccess,
@@ -331,36 +519,131 @@ impl Surface {
         Canvas::borrow_from_native(canvas_ref)
     }
 
-    // TODO: why is self mutable here?
-    pub fn new_surface(&mut self, info: &ImageInfo) -> Option<Surface> {
-        Surface::from_ptr(unsafe { sb::C_SkSurface_makeSurface(self.native_mut(), info.native()) })
+    /// Notifies the surface that its content will change with `mode`, which defaults to
+    /// [ContentChangeMode::Retain], and returns a guard that provides access to its [Canvas].
+    ///
+    /// The guard borrows the surface mutably, so no snapshot can be taken while the canvas is
+    /// drawn to. Call [WritableCanvas::flush_on_drop()] to flush and submit the drawing when
+    /// the guard is dropped.
+    pub fn writable_canvas(
+        &mut self,
+        mode: impl Into<Option<ContentChangeMode>>,
+    ) -> WritableCanvas {
+        self.notify_content_will_change(mode.into().unwrap_or(ContentChangeMode::Retain));
+        WritableCanvas {
+            surface: self,
+            flush_on_drop: false,
+        }
+    }
+
+    pub fn new_surface(&self, info: &ImageInfo) -> Option<Surface> {
+        Surface::from_ptr(unsafe {
+            sb::C_SkSurface_makeSurface(self.native_mut_force(), info.native())
+        })
     }
 
-    pub fn new_surface_with_dimensions(&mut self, dim: impl Into<ISize>) -> Option<Surface> {
+    pub fn new_surface_with_dimensions(&self, dim: impl Into<ISize>) -> Option<Surface> {
         let dim = dim.into();
         Surface::from_ptr(unsafe {
-            sb::C_SkSurface_makeSurface2(self.native_mut(), dim.width, dim.height)
+            sb::C_SkSurface_makeSurface2(self.native_mut_force(), dim.width, dim.height)
         })
     }
 
-    pub fn image_snapshot(&mut self) -> Image {
+    pub fn image_snapshot(&self) -> Image {
         Image::from_ptr(unsafe {
-            sb::C_SkSurface_makeImageSnapshot(self.native_mut(), ptr::null())
+            sb::C_SkSurface_makeImageSnapshot(self.native_mut_force(), ptr::null())
         })
         .unwrap()
     }
 
     // TODO: combine this function with image_snapshot and make bounds optional()?
-    pub fn image_snapshot_with_bounds(&mut self, bounds: impl AsRef<IRect>) -> Option<Image> {
+    pub fn image_snapshot_with_bounds(&self, bounds: impl AsRef<IRect>) -> Option<Image> {
         Image::from_ptr(unsafe {
-            sb::C_SkSurface_makeImageSnapshot(self.native_mut(), bounds.as_ref().native())
+            sb::C_SkSurface_makeImageSnapshot(self.native_mut_force(), bounds.as_ref().native())
         })
     }
 
-    pub fn draw(&mut self, canvas: &mut Canvas, size: impl Into<Size>, paint: Option<&Paint>) {
+    /// Captures the pixels inside `bounds` (or the whole surface if `bounds` is [None]) and
+    /// returns them as an image.
+    ///
+    /// If `dimensions` are specified and differ from the size of the captured area, the
+    /// captured pixels are rescaled with `filter_quality`, which defaults to
+    /// [FilterQuality::Medium]. The rescaling happens on a surface that is compatible with this
+    /// one, so the returned image is texture-backed if this surface is.
+    pub fn capture_to_image(
+        &self,
+        bounds: impl Into<Option<IRect>>,
+        dimensions: impl Into<Option<ISize>>,
+        filter_quality: impl Into<Option<FilterQuality>>,
+    ) -> Option<Image> {
+        let snapshot = match bounds.into() {
+            Some(bounds) => self.image_snapshot_with_bounds(bounds)?,
+            None => self.image_snapshot(),
+        };
+
+        let dimensions = match dimensions.into() {
+            Some(dimensions) if dimensions != snapshot.dimensions() => dimensions,
+            _ => return Some(snapshot),
+        };
+        if dimensions.is_empty() {
+            return None;
+        }
+
+        let info = snapshot.image_info().with_dimensions(dimensions);
+        let mut target = self.new_surface(&info)?;
+        let mut paint = Paint::default();
+        paint
+            .set_blend_mode(BlendMode::Src)
+            .set_filter_quality(filter_quality.into().unwrap_or(FilterQuality::Medium));
+        target
+            .canvas()
+            .draw_image_rect(&snapshot, None, Rect::from_isize(dimensions), &paint);
+        Some(target.image_snapshot())
+    }
+
+    /// Captures the pixels like [Self::capture_to_image()] does and encodes them to
+    /// `image_format`. `quality` defaults to `100`.
+    ///
+    /// GPU-backed surfaces are read back with the surface's context.
+    pub fn capture_to_data(
+        &self,
+        bounds: impl Into<Option<IRect>>,
+        dimensions: impl Into<Option<ISize>>,
+        filter_quality: impl Into<Option<FilterQuality>>,
+        image_format: EncodedImageFormat,
+        quality: impl Into<Option<i32>>,
+    ) -> Option<Data> {
+        let image = self.capture_to_image(bounds, dimensions, filter_quality)?;
+
+        #[cfg(feature = "gpu")]
+        {
+            let mut context = self
+                .recording_context()
+                .and_then(|mut context| context.as_direct_context());
+            image.encode(context.as_mut(), image_format, quality)
+        }
+
+        #[cfg(not(feature = "gpu"))]
+        image.encode_to_data_with_quality(image_format, quality.into().unwrap_or(100))
+    }
+
+    /// Clears the surface with `background`, lets `draw` render into its canvas and returns a
+    /// snapshot of the result.
+    pub(crate) fn render_to_image(
+        mut self,
+        background: Option<Color>,
+        draw: impl FnOnce(&mut Canvas),
+    ) -> Image {
+        let canvas = self.canvas();
+        canvas.clear(background.unwrap_or(Color::TRANSPARENT));
+        draw(canvas);
+        self.image_snapshot()
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, size: impl Into<Size>, paint: Option<&Paint>) {
         let size = size.into();
         unsafe {
-            self.native_mut().draw(
+            (*self.native_mut_force()).draw(
                 canvas.native_mut(),
                 size.width,
                 size.height,
@@ -375,14 +658,13 @@ impl Surface {
             .if_true_then_some(move || pm.borrows(self))
     }
 
-    // TODO: why is self mut?
-    pub fn read_pixels_to_pixmap(&mut self, dst: &Pixmap, src: impl Into<IPoint>) -> bool {
+    pub fn read_pixels_to_pixmap(&self, dst: &Pixmap, src: impl Into<IPoint>) -> bool {
         let src = src.into();
-        unsafe { self.native_mut().readPixels(dst.native(), src.x, src.y) }
+        unsafe { (*self.native_mut_force()).readPixels(dst.native(), src.x, src.y) }
     }
 
     pub fn read_pixels(
-        &mut self,
+        &self,
         dst_info: &ImageInfo,
         dst_pixels: &mut [u8],
         dst_row_bytes: usize,
@@ -397,7 +679,7 @@ impl Surface {
         };
         let src = src.into();
         unsafe {
-            self.native_mut().readPixels1(
+            (*self.native_mut_force()).readPixels1(
                 dst_info.native(),
                 dst_pixels.as_mut_ptr() as _,
                 dst_row_bytes,
@@ -407,11 +689,10 @@ impl Surface {
         }
     }
 
-    // TODO: why is self mut?
     // TODO: why is Bitmap non-mutable.
-    pub fn read_pixels_to_bitmap(&mut self, bitmap: &Bitmap, src: impl Into<IPoint>) -> bool {
+    pub fn read_pixels_to_bitmap(&self, bitmap: &Bitmap, src: impl Into<IPoint>) -> bool {
         let src = src.into();
-        unsafe { self.native_mut().readPixels2(bitmap.native(), src.x, src.y) }
+        unsafe { (*self.native_mut_force()).readPixels2(bitmap.native(), src.x, src.y) }
     }
 
     // TODO: AsyncReadResult, RescaleGamma (m79, m86)
@@ -441,13 +722,56 @@ impl Surface {
         }
     }
 
-    // TODO: wait()
+    /// Inserts a list of GPU semaphores that the current GPU-backed API must wait on before
+    /// executing any more commands on the GPU for this surface.
+    ///
+    /// If `delete_semaphores_after_wait` is `true`, Skia takes ownership of the semaphores and
+    /// deletes them once the wait is complete.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn wait(
+        &mut self,
+        semaphores: &[gpu::BackendSemaphore],
+        delete_semaphores_after_wait: bool,
+    ) -> bool {
+        unsafe {
+            sb::C_SkSurface_wait(
+                self.native_mut(),
+                semaphores.len().try_into().unwrap(),
+                semaphores.native().as_ptr(),
+                delete_semaphores_after_wait,
+            )
+        }
+    }
 
     pub fn characterize(&self) -> Option<SurfaceCharacterization> {
         let mut sc = SurfaceCharacterization::default();
         unsafe { self.native().characterize(sc.native_mut()) }.if_true_some(sc)
     }
 
+    /// Estimates the GPU memory of the color buffers of a GPU surface, including the
+    /// multisampled buffer and mipmaps. Returns `None` if the surface is not backed by a GPU
+    /// context.
+    ///
+    /// See [gpu::estimate_surface_memory()] to estimate the size before the surface is created.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn estimate_gpu_memory(&self) -> Option<usize> {
+        let characterization = self.characterize()?;
+        let mipmapped = if characterization.is_mip_mapped() {
+            gpu::Mipmapped::Yes
+        } else {
+            gpu::Mipmapped::No
+        };
+        Some(gpu::estimate_surface_memory(
+            characterization.dimensions(),
+            characterization.color_type(),
+            characterization.sample_count(),
+            characterization.is_textureable(),
+            mipmapped,
+        ))
+    }
+
     pub fn draw_display_list(
         &mut self,
         deferred_display_list: impl Into<DeferredDisplayList>,
@@ -461,6 +785,44 @@ impl Surface {
     }
 }
 
+/// Provides access to the [Canvas] of a [Surface], returned by [Surface::writable_canvas()].
+pub struct WritableCanvas<'a> {
+    surface: &'a mut Surface,
+    flush_on_drop: bool,
+}
+
+impl<'a> Deref for WritableCanvas<'a> {
+    type Target = Canvas;
+
+    fn deref(&self) -> &Self::Target {
+        let canvas_ref = unsafe { &mut *(*self.surface.native_mut_force()).getCanvas() };
+        Canvas::borrow_from_native(canvas_ref)
+    }
+}
+
+impl<'a> DerefMut for WritableCanvas<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.surface.canvas()
+    }
+}
+
+impl<'a> Drop for WritableCanvas<'a> {
+    fn drop(&mut self) {
+        if self.flush_on_drop {
+            self.surface.flush_and_submit(false);
+        }
+    }
+}
+
+impl<'a> WritableCanvas<'a> {
+    /// Configures whether the surface is flushed and the work submitted to the GPU when the
+    /// guard is dropped. This has no effect on raster surfaces.
+    pub fn flush_on_drop(mut self, flush: bool) -> Self {
+        self.flush_on_drop = flush;
+        self
+    }
+}
+
 #[cfg(feature = "gpu")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
@@ -495,9 +857,10 @@ impl Surface {
 #[cfg(test)]
 mod tests {
     use super::{
-        BackendHandleAccess, BackendSurfaceAccess, Canvas, ContentChangeMode, ISize, ImageInfo,
-        NativeAccess, NativeRefCounted, NativeRefCountedBase, Paint, Surface,
+        BackendHandleAccess, BackendSurfaceAccess, Canvas, ContentChangeMode, IRect, ISize,
+        ImageInfo, NativeAccess, NativeRefCounted, NativeRefCountedBase, Paint, Surface,
     };
+    use crate::prelude::ConditionallySend;
 
     #[test]
     fn test_surface_content_change_mode_naming() {
@@ -514,6 +877,23 @@ mod tests {
         let _ = BackendSurfaceAccess::Present;
     }
 
+    #[test]
+    fn test_writable_canvas() {
+        let mut surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let before = surface.image_snapshot();
+        {
+            let mut canvas = surface.writable_canvas(None).flush_on_drop(true);
+            canvas.clear(crate::Color::RED);
+            assert_eq!(canvas.base_layer_size(), ISize::new(4, 4));
+        }
+        let after = surface.image_snapshot();
+        assert_ne!(before.unique_id(), after.unique_id());
+        assert_eq!(
+            surface.peek_pixels().unwrap().get_color((0, 0)),
+            crate::Color::RED
+        );
+    }
+
     #[test]
     fn create() {
         assert!(Surface::new_raster_n32_premul((0, 0)).is_none());
@@ -521,6 +901,56 @@ mod tests {
         assert_eq!(1, surface.native().ref_counted_base()._ref_cnt())
     }
 
+    #[test]
+    fn test_raster_surface_can_be_sent() {
+        let surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        assert!(surface.can_send());
+        let sendable = surface.wrap_send().ok().unwrap();
+        let surface = std::thread::spawn(move || {
+            let mut surface = sendable.unwrap();
+            surface.canvas().clear(crate::Color::RED);
+            surface.wrap_send().ok().unwrap()
+        })
+        .join()
+        .unwrap()
+        .unwrap();
+        assert_eq!(surface.width(), 16);
+    }
+
+    #[test]
+    fn test_try_new_raster_reports_invalid_parameters() {
+        use crate::{AlphaType, ColorType, SkiaError};
+
+        assert!(Surface::try_new_raster_n32_premul((16, 16)).is_ok());
+        assert_eq!(
+            Surface::try_new_raster_n32_premul((0, 16)).err(),
+            Some(SkiaError::InvalidParameters("dimensions are empty"))
+        );
+        let unknown = ImageInfo::new((16, 16), ColorType::Unknown, AlphaType::Premul, None);
+        assert_eq!(
+            Surface::try_new_raster(&unknown, None, None).err(),
+            Some(SkiaError::UnsupportedColorType(ColorType::Unknown))
+        );
+        let info = ImageInfo::new_n32_premul((16, 16), None);
+        assert_eq!(
+            Surface::try_new_raster(&info, 16, None).err(),
+            Some(SkiaError::InvalidParameters(
+                "row bytes are too small or not aligned to pixels"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_capture_to_image_rescales() {
+        let surface = Surface::new_raster_n32_premul((64, 32)).unwrap();
+        let full = surface.capture_to_image(None, None, None).unwrap();
+        assert_eq!(full.dimensions(), ISize::new(64, 32));
+        let scaled = surface
+            .capture_to_image(IRect::from_wh(32, 32), ISize::new(16, 8), None)
+            .unwrap();
+        assert_eq!(scaled.dimensions(), ISize::new(16, 8));
+    }
+
     #[test]
     fn test_raster_direct() {
         let image_info = ImageInfo::new(
@@ -542,9 +972,57 @@ mod tests {
         surface.canvas().draw_circle((10, 10), 10.0, &paint);
     }
 
+    #[test]
+    fn test_raster_direct_with_release() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let image_info = ImageInfo::new_n32_premul((4, 4), None);
+        let pixels = vec![0u8; image_info.compute_min_byte_size()].into_boxed_slice();
+        let pixels = Box::into_raw(pixels) as *mut u8;
+        let len = image_info.compute_min_byte_size();
+        let released = Arc::new(AtomicBool::new(false));
+
+        let mut surface = {
+            let released = released.clone();
+            unsafe {
+                Surface::new_raster_direct_with_release(
+                    &image_info,
+                    pixels,
+                    None,
+                    move |pixels| {
+                        drop(Box::from_raw(std::slice::from_raw_parts_mut(pixels, len)));
+                        released.store(true, Ordering::SeqCst);
+                    },
+                    None,
+                )
+            }
+            .unwrap()
+        };
+        surface.canvas().clear(crate::Color::WHITE);
+        let image = surface.image_snapshot();
+        assert!(!released.load(Ordering::SeqCst));
+        drop(surface);
+        assert!(released.load(Ordering::SeqCst));
+        assert_eq!(image.width(), 4);
+
+        let invalid_info = ImageInfo::new_n32_premul((-1, 4), None);
+        let mut other = [0u8; 64];
+        assert!(unsafe {
+            Surface::new_raster_direct_with_release(
+                &invalid_info,
+                other.as_mut_ptr(),
+                None,
+                |_| panic!("must not be called"),
+                None,
+            )
+        }
+        .is_none());
+    }
+
     #[test]
     fn test_drawing_owned_as_exclusive_ref_ergonomics() {
-        let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        let surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
 
         // option1:
         // - An &mut canvas can be drawn to.
@@ -1,3 +1,4 @@
+use crate::{AlphaType, ColorType, ImageInfo, Pixmap};
 use skia_bindings as sb;
 use std::convert::TryInto;
 
@@ -21,3 +22,163 @@ pub fn swap_rb_inplace(pixels: &mut [u32]) {
         )
     }
 }
+
+/// Converts pixels between color types, alpha types, and color spaces with Skia's optimized
+/// conversion routines. `src` must contain `src_info.height()` rows of `src_row_bytes`, and
+/// `dst` exactly `dst_info.height()` rows of `dst_row_bytes`.
+///
+/// Returns `false` if the dimensions differ, or if the conversion is not supported, for
+/// example from a color type with alpha to [ColorType::Gray8].
+pub fn convert_pixels(
+    dst_info: &ImageInfo,
+    dst: &mut [u8],
+    dst_row_bytes: usize,
+    src_info: &ImageInfo,
+    src: &[u8],
+    src_row_bytes: usize,
+) -> bool {
+    if dst_info.dimensions() != src_info.dimensions()
+        || src.len() < src_info.compute_byte_size(src_row_bytes)
+        || src_row_bytes < src_info.min_row_bytes()
+    {
+        return false;
+    }
+    Pixmap::new(src_info, src, src_row_bytes).read_pixels(dst_info, dst, dst_row_bytes, (0, 0))
+}
+
+/// Swaps the red and blue channels of 32 bit pixels that are stored as bytes, which converts
+/// RGBA to BGRA and vice versa. Unlike [swap_rb()], the slices do not need to be aligned.
+pub fn swap_rb_8888(dst: &mut [u8], src: &[u8]) {
+    convert_row(
+        dst,
+        (ColorType::BGRA8888, AlphaType::Unpremul),
+        src,
+        (ColorType::RGBA8888, AlphaType::Unpremul),
+    )
+}
+
+/// Multiplies the color channels of [ColorType::RGBA8888] or [ColorType::BGRA8888] pixels by
+/// their alpha.
+pub fn premultiply_8888(dst: &mut [u8], src: &[u8], color_type: ColorType) {
+    assert_8888(color_type);
+    convert_row(
+        dst,
+        (color_type, AlphaType::Premul),
+        src,
+        (color_type, AlphaType::Unpremul),
+    )
+}
+
+/// Divides the color channels of premultiplied [ColorType::RGBA8888] or
+/// [ColorType::BGRA8888] pixels by their alpha.
+pub fn unpremultiply_8888(dst: &mut [u8], src: &[u8], color_type: ColorType) {
+    assert_8888(color_type);
+    convert_row(
+        dst,
+        (color_type, AlphaType::Unpremul),
+        src,
+        (color_type, AlphaType::Premul),
+    )
+}
+
+/// Converts [ColorType::RGBA8888] or [ColorType::BGRA8888] pixels to [ColorType::RGBAF16]
+/// pixels with the same `alpha_type`, so `dst` must be twice as long as `src`.
+pub fn convert_8888_to_f16(
+    dst: &mut [u8],
+    src: &[u8],
+    color_type: ColorType,
+    alpha_type: AlphaType,
+) {
+    assert_8888(color_type);
+    convert_row(
+        dst,
+        (ColorType::RGBAF16, alpha_type),
+        src,
+        (color_type, alpha_type),
+    )
+}
+
+fn assert_8888(color_type: ColorType) {
+    assert!(
+        color_type == ColorType::RGBA8888 || color_type == ColorType::BGRA8888,
+        "Expected RGBA8888 or BGRA8888 pixels"
+    );
+}
+
+/// Converts one row of pixels.
+fn convert_row(
+    dst: &mut [u8],
+    (dst_color_type, dst_alpha_type): (ColorType, AlphaType),
+    src: &[u8],
+    (src_color_type, src_alpha_type): (ColorType, AlphaType),
+) {
+    let src_bpp = src_color_type.bytes_per_pixel();
+    assert_eq!(src.len() % src_bpp, 0, "Incomplete source pixel");
+    let width = src.len() / src_bpp;
+    assert_eq!(
+        dst.len(),
+        width * dst_color_type.bytes_per_pixel(),
+        "The destination does not match the number of source pixels"
+    );
+    if width == 0 {
+        return;
+    }
+    let dimensions: (i32, i32) = (width.try_into().unwrap(), 1);
+    let src_info = ImageInfo::new(dimensions, src_color_type, src_alpha_type, None);
+    let dst_info = ImageInfo::new(dimensions, dst_color_type, dst_alpha_type, None);
+    let dst_row_bytes = dst.len();
+    assert!(convert_pixels(
+        &dst_info,
+        dst,
+        dst_row_bytes,
+        &src_info,
+        src,
+        src.len()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_rb_of_bytes() {
+        let src = [1u8, 2, 3, 4, 5, 6, 7, 8];
+        let mut dst = [0u8; 8];
+        swap_rb_8888(&mut dst, &src);
+        assert_eq!(dst, [3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+
+    #[test]
+    fn premultiply_and_unpremultiply() {
+        let src = [255u8, 128, 0, 128, 10, 20, 30, 255];
+        let mut premul = [0u8; 8];
+        premultiply_8888(&mut premul, &src, ColorType::RGBA8888);
+        assert_eq!(premul, [128, 64, 0, 128, 10, 20, 30, 255]);
+
+        let mut unpremul = [0u8; 8];
+        unpremultiply_8888(&mut unpremul, &premul, ColorType::RGBA8888);
+        assert_eq!(unpremul[3], 128);
+        assert_eq!(unpremul[4..], src[4..]);
+        assert!((unpremul[1] as i32 - 128).abs() <= 1);
+    }
+
+    #[test]
+    fn convert_to_f16() {
+        let src = [255u8, 0, 0, 255];
+        let mut dst = [0u8; 8];
+        convert_8888_to_f16(&mut dst, &src, ColorType::BGRA8888, AlphaType::Unpremul);
+        // 1.0 as a half float is 0x3c00, stored little endian.
+        assert_eq!(dst, [0, 0, 0, 0, 0, 0x3c, 0, 0x3c]);
+    }
+
+    #[test]
+    fn convert_pixels_checks_the_dimensions() {
+        let info = ImageInfo::new((2, 1), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let other = ImageInfo::new((1, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let src = [0u8; 8];
+        let mut dst = [0u8; 8];
+        assert!(!convert_pixels(&other, &mut dst, 4, &info, &src, 8));
+        assert!(convert_pixels(&info, &mut dst, 8, &info, &src, 8));
+    }
+}
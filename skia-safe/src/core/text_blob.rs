@@ -2,7 +2,8 @@ use crate::prelude::*;
 use crate::{scalar, Font, GlyphId, Paint, Point, RSXform, Rect, TextEncoding, Typeface};
 use skia_bindings as sb;
 use skia_bindings::{
-    SkTextBlob, SkTextBlobBuilder, SkTextBlob_Iter, SkTextBlob_Iter_Run, SkTypeface,
+    SkTextBlob, SkTextBlobBuilder, SkTextBlobRunIterator, SkTextBlob_Iter, SkTextBlob_Iter_Run,
+    SkTypeface,
 };
 use std::convert::TryInto;
 use std::{ptr, slice};
@@ -39,6 +40,11 @@ impl TextBlob {
         self.native().fUniqueID
     }
 
+    /// Returns the horizontal intervals where the glyphs of the blob intersect the vertical
+    /// `bounds`, as pairs of left and right edges, one pair for each intersecting glyph.
+    ///
+    /// `bounds` are the top and bottom of a horizontal band, for example an underline. The
+    /// `paint` is used to take stroking and path effects into account.
     // TODO: consider to provide an inplace variant.
     pub fn get_intercepts(&self, bounds: [scalar; 2], paint: Option<&Paint>) -> Vec<scalar> {
         unsafe {
@@ -58,6 +64,79 @@ impl TextBlob {
         }
     }
 
+    /// Returns an iterator over the runs of the blob, including their fonts and glyph positions.
+    pub fn glyph_runs(&self) -> GlyphRunIter {
+        GlyphRunIter::new(self)
+    }
+
+    /// Rebuilds the blob with a transform for each glyph, for example to draw wavy or jittery
+    /// text without shaping it again.
+    ///
+    /// `f` receives the index of the glyph in the blob, its id, and its current transform, which
+    /// maps the glyph's origin to its position in the blob. The returned transform replaces it,
+    /// so rotations and scales must be relative to the current transform to keep the glyph in
+    /// place:
+    ///
+    /// ```rust,ignore
+    /// let wavy = blob.transform_glyphs(|i, _, xform| {
+    ///     RSXform::new(xform.scos, xform.ssin, (xform.tx, xform.ty + (i as f32).sin() * 4.0))
+    /// });
+    /// ```
+    ///
+    /// All runs of the returned blob are RSXform runs. Returns [None] if the blob has no glyphs.
+    pub fn transform_glyphs(
+        &self,
+        mut f: impl FnMut(usize, GlyphId, RSXform) -> RSXform,
+    ) -> Option<TextBlob> {
+        let mut builder = TextBlobBuilder::new();
+        let mut index = 0;
+        for run in self.glyph_runs() {
+            let current = run.xforms();
+            let (glyphs, xforms) = builder.alloc_run_rsxform(&run.font, run.glyphs.len());
+            glyphs.copy_from_slice(run.glyphs);
+            for ((xform, glyph), current) in xforms.iter_mut().zip(run.glyphs).zip(current) {
+                *xform = f(index, *glyph, current);
+                index += 1;
+            }
+        }
+        builder.make()
+    }
+
+    /// Returns the segments of an underline from `x_range[0]` to `x_range[1]` that skip the
+    /// glyphs which intersect the underline's vertical `bounds`, for example descenders.
+    ///
+    /// `gap` is the space that is left free on both sides of each intersecting glyph.
+    pub fn underline_segments(
+        &self,
+        x_range: [scalar; 2],
+        bounds: [scalar; 2],
+        gap: scalar,
+        paint: Option<&Paint>,
+    ) -> Vec<[scalar; 2]> {
+        let [start, end] = x_range;
+        let mut intercepts: Vec<[scalar; 2]> = self
+            .get_intercepts(bounds, paint)
+            .chunks_exact(2)
+            .map(|interval| [interval[0] - gap, interval[1] + gap])
+            // the intervals are sorted below, which requires that they are comparable.
+            .filter(|[left, right]| left.is_finite() && right.is_finite())
+            .collect();
+        intercepts.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+
+        let mut segments = Vec::new();
+        let mut x = start;
+        for [left, right] in intercepts {
+            if left > x && x < end {
+                segments.push([x, left.min(end)]);
+            }
+            x = x.max(right);
+        }
+        if x < end {
+            segments.push([x, end]);
+        }
+        segments
+    }
+
     pub fn from_str(str: impl AsRef<str>, font: &Font) -> Option<TextBlob> {
         Self::from_text(str.as_ref().as_bytes(), TextEncoding::UTF8, font)
     }
@@ -285,8 +364,206 @@ impl<'a> Iterator for Borrows<'a, Handle<SkTextBlob_Iter>> {
     }
 }
 
+/// Iterates over the runs of a [TextBlob], see [TextBlob::glyph_runs()].
+pub type GlyphRunIter<'a> = Borrows<'a, Handle<SkTextBlobRunIterator>>;
+
+/// A run of glyphs that share the same font.
+pub struct GlyphRun<'a> {
+    pub font: Font,
+    pub glyphs: &'a [GlyphId],
+    positioning: GlyphPositioning<'a>,
+    offset: Point,
+}
+
+/// How the glyphs of a run were positioned when the blob was built.
+enum GlyphPositioning<'a> {
+    Default,
+    Horizontal(&'a [scalar]),
+    Full(&'a [Point]),
+    RSXform(&'a [RSXform]),
+}
+
+impl GlyphRun<'_> {
+    /// The origin of each glyph.
+    pub fn positions(&self) -> Vec<Point> {
+        let offset = self.offset;
+        match self.positioning {
+            GlyphPositioning::Default => {
+                let mut positions = vec![Point::default(); self.glyphs.len()];
+                self.font.get_pos(self.glyphs, &mut positions, Some(offset));
+                positions
+            }
+            GlyphPositioning::Horizontal(xpos) => xpos
+                .iter()
+                .map(|x| Point::new(offset.x + x, offset.y))
+                .collect(),
+            GlyphPositioning::Full(pos) => pos.iter().map(|p| offset + *p).collect(),
+            GlyphPositioning::RSXform(xforms) => xforms
+                .iter()
+                .map(|xform| Point::new(offset.x + xform.tx, offset.y + xform.ty))
+                .collect(),
+        }
+    }
+
+    /// The bounds of the run's glyphs, taking their positions and transforms into account.
+    pub fn bounds(&self, paint: Option<&Paint>) -> Rect {
+        let mut glyph_bounds = vec![Rect::default(); self.glyphs.len()];
+        self.font.get_bounds(self.glyphs, &mut glyph_bounds, paint);
+        let corners: Vec<Point> = glyph_bounds
+            .iter()
+            .zip(self.xforms())
+            .flat_map(|(bounds, xform)| {
+                let map = move |x: scalar, y: scalar| {
+                    Point::new(
+                        xform.scos * x - xform.ssin * y + xform.tx,
+                        xform.ssin * x + xform.scos * y + xform.ty,
+                    )
+                };
+                vec![
+                    map(bounds.left, bounds.top),
+                    map(bounds.right, bounds.top),
+                    map(bounds.right, bounds.bottom),
+                    map(bounds.left, bounds.bottom),
+                ]
+            })
+            .collect();
+        Rect::from_bounds(&corners).unwrap_or_default()
+    }
+
+    /// The transform of each glyph. For runs that are not RSXform runs, these are translations
+    /// to the glyph's origin.
+    pub fn xforms(&self) -> Vec<RSXform> {
+        match self.positioning {
+            GlyphPositioning::RSXform(xforms) => xforms
+                .iter()
+                .map(|xform| {
+                    RSXform::new(
+                        xform.scos,
+                        xform.ssin,
+                        (self.offset.x + xform.tx, self.offset.y + xform.ty),
+                    )
+                })
+                .collect(),
+            _ => self
+                .positions()
+                .into_iter()
+                .map(|p| RSXform::new(1.0, 0.0, p))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Borrows<'a, Handle<SkTextBlobRunIterator>> {
+    pub fn new(text_blob: &'a TextBlob) -> Self {
+        Handle::construct(|iter| unsafe {
+            sb::C_SkTextBlobRunIterator_construct(iter, text_blob.native())
+        })
+        .borrows(text_blob)
+    }
+}
+
+impl NativeDrop for SkTextBlobRunIterator {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkTextBlobRunIterator_destruct(self) }
+    }
+}
+
+impl<'a> Iterator for Borrows<'a, Handle<SkTextBlobRunIterator>> {
+    type Item = GlyphRun<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut font = Font::default();
+        let mut glyphs = ptr::null();
+        let mut count = 0;
+        let mut pos = ptr::null();
+        let mut offset = Point::default();
+        let mut positioning = 0;
+        unsafe {
+            if !sb::C_SkTextBlobRunIterator_next(
+                self.native_mut(),
+                font.native_mut(),
+                &mut glyphs,
+                &mut count,
+                &mut pos,
+                offset.native_mut(),
+                &mut positioning,
+            ) {
+                return None;
+            }
+            let count = count.try_into().unwrap();
+            let glyphs = if !glyphs.is_null() && count != 0 {
+                slice::from_raw_parts(glyphs, count)
+            } else {
+                &[]
+            };
+            // the values of SkTextBlobRunIterator::GlyphPositioning.
+            let positioning = match positioning {
+                0 => GlyphPositioning::Default,
+                1 => GlyphPositioning::Horizontal(slice::from_raw_parts(pos, count)),
+                2 => GlyphPositioning::Full(slice::from_raw_parts(pos as *const Point, count)),
+                3 => GlyphPositioning::RSXform(slice::from_raw_parts(pos as *const RSXform, count)),
+                _ => panic!("unsupported glyph positioning: {}", positioning),
+            };
+            Some(GlyphRun {
+                font,
+                glyphs,
+                positioning,
+                offset,
+            })
+        }
+    }
+}
+
 #[test]
 fn test_point_size_equals_size_of_two_scalars_used_in_alloc_run_pos() {
     use std::mem;
     assert_eq!(mem::size_of::<Point>(), mem::size_of::<[scalar; 2]>())
 }
+
+#[test]
+fn test_transform_glyphs() {
+    let font = Font::default();
+    let blob = TextBlob::new("Hello", &font).unwrap();
+    let positions: Vec<Point> = blob.glyph_runs().flat_map(|run| run.positions()).collect();
+    assert_eq!(positions.len(), 5);
+
+    let wavy = blob
+        .transform_glyphs(|i, _, xform| {
+            RSXform::new(xform.scos, xform.ssin, (xform.tx, xform.ty + i as scalar))
+        })
+        .unwrap();
+    let runs: Vec<_> = wavy.glyph_runs().collect();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].glyphs, blob.glyph_runs().next().unwrap().glyphs);
+    for (i, (p, moved)) in positions.iter().zip(runs[0].positions()).enumerate() {
+        assert_eq!(moved, Point::new(p.x, p.y + i as scalar));
+    }
+}
+
+#[test]
+fn test_underline_segments() {
+    let font = Font::default();
+    let blob = TextBlob::new("gap", &font).unwrap();
+    let bounds = blob.glyph_runs().next().unwrap().bounds(None);
+    assert!(!bounds.is_empty());
+    // the descenders of "g" and "p" cross a band just below the baseline.
+    let band = [1.0, 2.0];
+    let intercepts = blob.get_intercepts(band, None);
+    assert_eq!(intercepts.len(), 4);
+    let segments = blob.underline_segments([bounds.left, bounds.right], band, 1.0, None);
+    assert!(segments.len() >= 2);
+    for [left, right] in segments {
+        assert!(left < right);
+        for interval in intercepts.chunks_exact(2) {
+            assert!(right <= interval[0] - 1.0 || left >= interval[1] + 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_underline_segments_with_nan_gap() {
+    let blob = TextBlob::new("gap", &Font::default()).unwrap();
+    assert_eq!(
+        blob.underline_segments([0.0, 10.0], [1.0, 2.0], scalar::NAN, None),
+        vec![[0.0, 10.0]]
+    );
+}
@@ -223,6 +223,34 @@ impl Typeface {
         LocalizedStringsIter::from_ptr(unsafe { self.native().createFamilyNameIterator() }).unwrap()
     }
 
+    /// Returns all the family names that are specified in the font, together with their BCP 47
+    /// language tags.
+    ///
+    /// Fonts usually contain at least one English name and may contain translated names, for
+    /// example a Japanese name tagged with `"ja"` or `"ja-JP"`.
+    pub fn family_names(&self) -> impl Iterator<Item = LocalizedString> {
+        self.new_family_name_iterator()
+    }
+
+    /// Returns the family name that matches the first of the given BCP 47 language tags,
+    /// for example `["de-CH", "en"]`.
+    ///
+    /// A tag matches if it is equal to the name's language tag, or if one of them is a prefix
+    /// of the other that ends at a `-` subtag boundary, so `"ja"` matches `"ja-JP"`. The
+    /// comparison ignores ASCII case. If no name matches, [Self::family_name()] is returned.
+    pub fn localized_family_name(&self, languages: &[impl AsRef<str>]) -> String {
+        let names: Vec<LocalizedString> = self.family_names().collect();
+        languages
+            .iter()
+            .find_map(|language| {
+                names
+                    .iter()
+                    .find(|name| language_tags_match(&name.language, language.as_ref()))
+            })
+            .map(|name| name.string.clone())
+            .unwrap_or_else(|| self.family_name())
+    }
+
     pub fn family_name(&self) -> String {
         let mut name = interop::String::default();
         unsafe {
@@ -231,6 +259,8 @@ impl Typeface {
         name.as_str().into()
     }
 
+    /// Returns the PostScript name of the font, which does not depend on the locale, or `None`
+    /// if the font does not specify one.
     pub fn post_script_name(&self) -> Option<String> {
         let mut name = interop::String::default();
         unsafe { self.native().getPostScriptName(name.native_mut()) }
@@ -245,6 +275,16 @@ impl Typeface {
     }
 }
 
+fn language_tags_match(a: &str, b: &str) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.is_empty() {
+        return false;
+    }
+    longer.is_char_boundary(shorter.len())
+        && longer[..shorter.len()].eq_ignore_ascii_case(shorter)
+        && (longer.len() == shorter.len() || longer.as_bytes()[shorter.len()] == b'-')
+}
+
 pub type LocalizedStringsIter = RefHandle<SkTypeface_LocalizedStrings>;
 
 impl NativeDrop for SkTypeface_LocalizedStrings {
@@ -303,3 +343,23 @@ fn family_name_iterator_owns_the_strings_and_returns_at_least_one_name_for_the_d
     }
     assert!(any);
 }
+
+#[test]
+fn localized_family_name_falls_back_to_the_family_name() {
+    let tf = Typeface::default();
+    assert!(tf.family_names().count() > 0);
+    assert_eq!(
+        tf.localized_family_name(&["x-unknown-language"]),
+        tf.family_name()
+    );
+    let _ = tf.post_script_name();
+}
+
+#[test]
+fn language_tags_match_at_subtag_boundaries() {
+    assert!(language_tags_match("ja", "ja-JP"));
+    assert!(language_tags_match("EN-us", "en"));
+    assert!(language_tags_match("de", "de"));
+    assert!(!language_tags_match("ja", "jav"));
+    assert!(!language_tags_match("", "en"));
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use crate::{Color, Point, Rect};
+use crate::{Color, ISize, Point, Rect};
 use skia_bindings as sb;
 use skia_bindings::{
     SkPoint, SkVertices, SkVertices_Attribute, SkVertices_Attribute_Type, SkVertices_Builder,
@@ -22,6 +22,10 @@ pub struct Bone {
     values: [f32; 6],
 }
 
+// TODO: SkMesh and SkMeshSpecification (custom vertex and fragment SkSL with vertex and index
+// buffers) were introduced after m87 and can not be wrapped yet. Until then, per-vertex data can
+// be passed to runtime effect shaders through custom vertex attributes, see [Attribute].
+
 pub use skia_bindings::SkVertices_VertexMode as VertexMode;
 #[test]
 fn test_vertices_vertex_mode_naming() {
@@ -170,6 +174,71 @@ impl Vertices {
         .unwrap()
     }
 
+    /// Creates a mesh of `columns` x `rows` cells that maps a regular grid over an image of
+    /// `image_size` to the points in `dst`, for example to warp an image that is drawn with
+    /// [crate::Canvas::draw_image_mesh()].
+    ///
+    /// `dst` contains the `(columns + 1) * (rows + 1)` destination points of the grid's
+    /// corners, row by row. The texture coordinates are in the pixel space of the image. Each
+    /// cell is drawn as two triangles, so the image is interpolated linearly inside the
+    /// triangles, which distorts perspective, unless the grid is fine enough.
+    ///
+    /// Returns [None] if the grid is empty, `dst` does not contain the expected number of
+    /// points, or the mesh has more vertices than can be indexed with 16 bits.
+    pub fn new_image_mesh(
+        image_size: impl Into<ISize>,
+        (columns, rows): (usize, usize),
+        dst: &[Point],
+    ) -> Option<Vertices> {
+        let image_size = image_size.into();
+        let (grid_width, grid_height) = (columns + 1, rows + 1);
+        let vertex_count = grid_width * grid_height;
+        if columns == 0
+            || rows == 0
+            || dst.len() != vertex_count
+            || vertex_count > usize::from(u16::MAX) + 1
+        {
+            return None;
+        }
+
+        let index_count = columns * rows * 6;
+        let mut builder = Builder::new(
+            VertexMode::Triangles,
+            vertex_count,
+            index_count,
+            BuilderFlags::HAS_TEX_COORDS,
+        );
+        builder.positions().copy_from_slice(dst);
+
+        let (cell_width, cell_height) = (
+            image_size.width as f32 / columns as f32,
+            image_size.height as f32 / rows as f32,
+        );
+        for (i, tex) in builder.tex_coords().unwrap().iter_mut().enumerate() {
+            let (x, y) = (i % grid_width, i / grid_width);
+            *tex = Point::new(x as f32 * cell_width, y as f32 * cell_height);
+        }
+
+        let indices = builder.indices().unwrap();
+        for (cell, triangles) in indices.chunks_exact_mut(6).enumerate() {
+            let (x, y) = (cell % columns, cell / columns);
+            let top_left = (y * grid_width + x) as u16;
+            let top_right = top_left + 1;
+            let bottom_left = top_left + grid_width as u16;
+            let bottom_right = bottom_left + 1;
+            triangles.copy_from_slice(&[
+                top_left,
+                top_right,
+                bottom_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            ]);
+        }
+
+        Some(builder.detach())
+    }
+
     pub const MAX_CUSTOM_ATTRIBUTES: usize = 8;
 
     pub fn unique_id(&self) -> u32 {
@@ -388,3 +457,21 @@ impl Builder {
         Vertices::from_ptr(unsafe { sb::C_SkVertices_Builder_detach(self.native_mut()) }).unwrap()
     }
 }
+
+#[test]
+fn test_image_mesh() {
+    let dst: Vec<Point> = (0..3 * 2)
+        .map(|i| Point::new((i % 3) as f32 * 10.0, (i / 3) as f32 * 20.0))
+        .collect();
+    let mesh = Vertices::new_image_mesh((100, 50), (2, 1), &dst).unwrap();
+    assert_eq!(mesh.vertex_count(), 6);
+    assert_eq!(mesh.index_count(), 12);
+    assert_eq!(mesh.positions(), &dst[..]);
+    let tex_coords = mesh.tex_coords().unwrap();
+    assert_eq!(tex_coords[1], Point::new(50.0, 0.0));
+    assert_eq!(tex_coords[5], Point::new(100.0, 50.0));
+    assert_eq!(&mesh.indices().unwrap()[..6], &[0, 1, 3, 1, 4, 3]);
+
+    assert!(Vertices::new_image_mesh((100, 50), (2, 2), &dst).is_none());
+    assert!(Vertices::new_image_mesh((100, 50), (0, 1), &[]).is_none());
+}
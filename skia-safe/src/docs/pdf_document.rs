@@ -230,6 +230,7 @@ pub mod pdf {
         }
     }
 
+    /// The metadata of a PDF document and the options that control how it is generated.
     #[derive(Debug, Default)]
     pub struct Metadata {
         pub title: String,
@@ -240,13 +241,29 @@ pub mod pdf {
         pub producer: String,
         pub creation: Option<DateTime>,
         pub modified: Option<DateTime>,
+        /// The resolution in dots per inch at which features that can not be represented in
+        /// PDF, like some image filters and blend modes, are rasterized.
+        /// [Metadata::DEFAULT_RASTER_DPI] if [None]. Higher values produce better print quality
+        /// and larger documents.
         pub raster_dpi: Option<scalar>,
+        /// Adds the XMP metadata, document UUID, and sRGB output intent that are required for
+        /// PDF/A-2b conformance, which is needed for long term archiving. The document gets
+        /// larger and is not reproducible anymore.
         pub pdfa: bool,
+        /// The quality in the range `0..=100` with which images without alpha are encoded as
+        /// JPEG. If [None] or larger than 100, images are encoded losslessly, which is Skia's
+        /// default.
         pub encoding_quality: Option<i32>,
         // TODO: this is not supported yet
         structure_element_tree_root: Option<StructureElementNode>,
     }
 
+    impl Metadata {
+        /// The resolution at which features that can not be represented in PDF are rasterized
+        /// if [Metadata::raster_dpi] is not set, which corresponds to a scale of 1.
+        pub const DEFAULT_RASTER_DPI: scalar = 72.0;
+    }
+
     // TODO: SetNodeId
 
     pub fn new_document(metadata: Option<&Metadata>) -> Document {
@@ -314,3 +331,30 @@ fn create_attribute_list() {
     _al.append_float_array("Owner", "Name", &[1.0, 2.0, 3.0]);
     _al.append_string_array("Owner", "Name", &["A", "B", "C"]);
 }
+
+#[test]
+fn pdfa_adds_metadata_stream() {
+    use crate::Rect;
+
+    let write = |pdfa: bool| {
+        let metadata = pdf::Metadata {
+            title: "archived".into(),
+            raster_dpi: Some(300.0),
+            pdfa,
+            encoding_quality: Some(80),
+            ..Default::default()
+        };
+        let mut page = pdf::new_document(Some(&metadata)).begin_page((100.0, 100.0), None);
+        page.canvas()
+            .draw_rect(Rect::from_wh(10.0, 10.0), &Default::default());
+        page.end_page().close()
+    };
+
+    let contains = |data: &crate::Data, pattern: &[u8]| {
+        data.as_bytes()
+            .windows(pattern.len())
+            .any(|window| window == pattern)
+    };
+    assert!(contains(&write(true), b"/Metadata"));
+    assert!(!contains(&write(false), b"/Metadata"));
+}
@@ -48,6 +48,7 @@ pub mod overdraw_color_filter;
 pub mod paint_image_filter;
 pub mod perlin_noise_shader;
 pub mod picture_image_filter;
+pub mod presets;
 pub mod runtime_effect;
 pub use runtime_effect::RuntimeEffect;
 pub mod shader_mask_filter;
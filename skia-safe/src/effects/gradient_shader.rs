@@ -1,6 +1,17 @@
+//! Gradient shaders.
+//!
+//! Gradients are interpolated in floating point, but their results are quantized to the color
+//! type of the destination, which can cause visible banding in large, subtle gradients. To
+//! reduce it, either enable dithering with [crate::Paint::set_dither()] on the paint the
+//! gradient is drawn with, or draw into a destination with more precision, like a surface of
+//! [crate::ColorType::RGBAF16]. Colors can be specified with more than 8 bits per channel and in
+//! wide gamut color spaces by passing [Color4f]s together with their [ColorSpace], see
+//! [GradientShaderColors].
+
 use crate::prelude::*;
 use crate::{scalar, Color, Color4f, ColorSpace, Matrix, Point, Shader, TileMode};
 use skia_bindings as sb;
+use std::{error::Error, fmt};
 
 impl Shader {
     pub fn linear_gradient<'a>(
@@ -65,7 +76,12 @@ impl Shader {
 }
 
 bitflags! {
+    /// Flags that control the interpolation of gradient colors.
+    ///
+    /// Dithering is not controlled by a gradient flag, but by [crate::Paint::set_dither()].
     pub struct Flags: u32 {
+        /// Interpolates the colors after they are premultiplied, instead of interpolating the
+        /// unpremultiplied colors.
         const INTERPOLATE_COLORS_IN_PREMUL = sb::SkGradientShader_Flags_kInterpolateColorsInPremul_Flag as _;
     }
 }
@@ -278,6 +294,9 @@ pub fn sweep<'a>(
 /// Type that represents either a slice of Color, or a slice of Color4f and a color space.
 /// Whenever this type is expected, it's either possible to directly pass a &[Color] , or
 /// a tuple of type (&[Color4f], &ColorSpace).
+///
+/// [Color]s are limited to 8 bits per channel in sRGB, use [Color4f]s to specify the colors of
+/// gradients that are drawn into wide gamut or [crate::ColorType::RGBAF16] destinations.
 pub enum GradientShaderColors<'a> {
     Colors(&'a [Color]),
     ColorsInSpace(&'a [Color4f], ColorSpace),
@@ -308,3 +327,300 @@ impl<'a> From<(&'a [Color4f], ColorSpace)> for GradientShaderColors<'a> {
         GradientShaderColors::<'a>::ColorsInSpace(c.0, c.1)
     }
 }
+
+/// Collects the color stops of a gradient, validates them, and creates gradient shaders.
+///
+/// Skia silently clamps and reorders invalid positions, but [GradientBuilder] reports them with
+/// a [GradientError]. Positions must be in the range `0..=1` and must not decrease, and if the
+/// first stop is not at `0` or the last stop is not at `1`, the gradient is padded with the
+/// colors of these stops.
+#[derive(Clone)]
+pub struct GradientBuilder {
+    colors: Vec<Color4f>,
+    positions: Vec<scalar>,
+    color_space: Option<ColorSpace>,
+    mode: TileMode,
+    flags: self::Flags,
+    local_matrix: Option<Matrix>,
+}
+
+/// The reason why a [GradientBuilder] could not create a gradient shader.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GradientError {
+    /// No color stop was added.
+    NoStops,
+    /// The position of the stop at `index` is not in the range `0..=1`.
+    PositionOutOfRange { index: usize, position: scalar },
+    /// The position of the stop at `index` is smaller than the position of the stop before.
+    PositionDecreasing { index: usize, position: scalar },
+    /// Skia could not create the shader, for example because the geometry is degenerate.
+    ShaderCreationFailed,
+}
+
+impl fmt::Display for GradientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GradientError::NoStops => write!(f, "The gradient has no color stops"),
+            GradientError::PositionOutOfRange { index, position } => write!(
+                f,
+                "The position {} of color stop {} is not in the range 0..=1",
+                position, index
+            ),
+            GradientError::PositionDecreasing { index, position } => write!(
+                f,
+                "The position {} of color stop {} is smaller than the position before",
+                position, index
+            ),
+            GradientError::ShaderCreationFailed => write!(f, "Failed to create the shader"),
+        }
+    }
+}
+
+impl Error for GradientError {}
+
+impl Default for GradientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GradientBuilder {
+    pub fn new() -> Self {
+        Self {
+            colors: Vec::new(),
+            positions: Vec::new(),
+            color_space: None,
+            mode: TileMode::Clamp,
+            flags: self::Flags::default(),
+            local_matrix: None,
+        }
+    }
+
+    /// Adds a color stop at `position`.
+    pub fn stop(&mut self, position: scalar, color: impl Into<Color4f>) -> &mut Self {
+        self.positions.push(position);
+        self.colors.push(color.into());
+        self
+    }
+
+    /// Adds a hard stop, at which the color changes from `before` to `after` without
+    /// interpolation.
+    pub fn hard_stop(
+        &mut self,
+        position: scalar,
+        before: impl Into<Color4f>,
+        after: impl Into<Color4f>,
+    ) -> &mut Self {
+        self.stop(position, before).stop(position, after)
+    }
+
+    /// Sets the color space of the colors, sRGB if it is not set.
+    pub fn color_space(&mut self, color_space: impl Into<Option<ColorSpace>>) -> &mut Self {
+        self.color_space = color_space.into();
+        self
+    }
+
+    /// Sets how the gradient is drawn outside of its range, [TileMode::Clamp] by default.
+    pub fn tile_mode(&mut self, mode: TileMode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn flags(&mut self, flags: self::Flags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn local_matrix(&mut self, local_matrix: impl Into<Option<Matrix>>) -> &mut Self {
+        self.local_matrix = local_matrix.into();
+        self
+    }
+
+    /// Returns the validated colors and positions, padded to the range `0..=1`.
+    pub fn validated_stops(&self) -> Result<(Vec<Color4f>, Vec<scalar>), GradientError> {
+        if self.positions.is_empty() {
+            return Err(GradientError::NoStops);
+        }
+        for (index, &position) in self.positions.iter().enumerate() {
+            if !(0.0..=1.0).contains(&position) {
+                return Err(GradientError::PositionOutOfRange { index, position });
+            }
+            if index > 0 && position < self.positions[index - 1] {
+                return Err(GradientError::PositionDecreasing { index, position });
+            }
+        }
+
+        let mut colors = self.colors.clone();
+        let mut positions = self.positions.clone();
+        if positions[0] > 0.0 {
+            colors.insert(0, colors[0].clone());
+            positions.insert(0, 0.0);
+        }
+        if positions[positions.len() - 1] < 1.0 {
+            colors.push(colors[colors.len() - 1].clone());
+            positions.push(1.0);
+        }
+        Ok((colors, positions))
+    }
+
+    pub fn linear(
+        &self,
+        points: (impl Into<Point>, impl Into<Point>),
+    ) -> Result<Shader, GradientError> {
+        self.build(|colors, positions, local_matrix| {
+            linear(
+                points,
+                colors,
+                positions,
+                self.mode,
+                self.flags,
+                local_matrix,
+            )
+        })
+    }
+
+    pub fn radial(
+        &self,
+        center: impl Into<Point>,
+        radius: scalar,
+    ) -> Result<Shader, GradientError> {
+        self.build(|colors, positions, local_matrix| {
+            radial(
+                center,
+                radius,
+                colors,
+                positions,
+                self.mode,
+                self.flags,
+                local_matrix,
+            )
+        })
+    }
+
+    pub fn two_point_conical(
+        &self,
+        (start, start_radius): (impl Into<Point>, scalar),
+        (end, end_radius): (impl Into<Point>, scalar),
+    ) -> Result<Shader, GradientError> {
+        self.build(|colors, positions, local_matrix| {
+            two_point_conical(
+                start,
+                start_radius,
+                end,
+                end_radius,
+                colors,
+                positions,
+                self.mode,
+                self.flags,
+                local_matrix,
+            )
+        })
+    }
+
+    pub fn sweep(
+        &self,
+        center: impl Into<Point>,
+        angles: impl Into<Option<(scalar, scalar)>>,
+    ) -> Result<Shader, GradientError> {
+        self.build(|colors, positions, local_matrix| {
+            sweep(
+                center,
+                colors,
+                positions,
+                self.mode,
+                angles,
+                self.flags,
+                local_matrix,
+            )
+        })
+    }
+
+    fn build(
+        &self,
+        make: impl FnOnce(GradientShaderColors, &[scalar], Option<&Matrix>) -> Option<Shader>,
+    ) -> Result<Shader, GradientError> {
+        let (colors, positions) = self.validated_stops()?;
+        let color_space = self
+            .color_space
+            .clone()
+            .unwrap_or_else(ColorSpace::new_srgb);
+        make(
+            GradientShaderColors::ColorsInSpace(&colors, color_space),
+            &positions,
+            self.local_matrix.as_ref(),
+        )
+        .ok_or(GradientError::ShaderCreationFailed)
+    }
+}
+
+#[test]
+fn test_gradient_builder() {
+    let mut builder = GradientBuilder::new();
+    assert_eq!(
+        builder.validated_stops().unwrap_err(),
+        GradientError::NoStops
+    );
+
+    builder
+        .stop(0.25, Color::RED)
+        .hard_stop(0.5, Color::GREEN, Color::BLUE);
+    let (colors, positions) = builder.validated_stops().unwrap();
+    assert_eq!(positions, [0.0, 0.25, 0.5, 0.5, 1.0]);
+    assert_eq!(colors[0], Color4f::from(Color::RED));
+    assert_eq!(colors[4], Color4f::from(Color::BLUE));
+    assert!(builder.linear(((0.0, 0.0), (100.0, 0.0))).is_ok());
+    assert!(builder.sweep((50.0, 50.0), None).is_ok());
+
+    builder.stop(0.4, Color::BLACK);
+    assert_eq!(
+        builder.validated_stops().unwrap_err(),
+        GradientError::PositionDecreasing {
+            index: 3,
+            position: 0.4
+        }
+    );
+
+    let mut builder = GradientBuilder::new();
+    builder.stop(0.0, Color::RED).stop(1.5, Color::BLUE);
+    assert_eq!(
+        builder.radial((0.0, 0.0), 10.0).unwrap_err(),
+        GradientError::PositionOutOfRange {
+            index: 1,
+            position: 1.5
+        }
+    );
+}
+
+#[test]
+fn test_dither_reduces_banding() {
+    use crate::{Paint, Rect, Surface};
+
+    // counts how often the color changes along the first row.
+    fn transitions(dither: bool) -> usize {
+        let mut surface = Surface::new_raster_n32_premul((256, 1)).unwrap();
+        let colors: &[Color] = &[
+            Color::from_rgb(0x30, 0x30, 0x30),
+            Color::from_rgb(0x34, 0x34, 0x34),
+        ];
+        let shader = linear(
+            ((0.0, 0.0), (256.0, 0.0)),
+            colors,
+            None,
+            TileMode::Clamp,
+            None,
+            None,
+        )
+        .unwrap();
+        let mut paint = Paint::default();
+        paint.set_shader(shader).set_dither(dither);
+        surface
+            .canvas()
+            .draw_rect(Rect::from_wh(256.0, 1.0), &paint);
+        let pixmap = surface.peek_pixels().unwrap();
+        (1..256)
+            .filter(|x| pixmap.get_color((*x, 0)) != pixmap.get_color((x - 1, 0)))
+            .count()
+    }
+
+    assert!(transitions(true) > transitions(false));
+}
@@ -1,7 +1,8 @@
 use crate::prelude::*;
 use crate::{
     scalar, BlendMode, Color, ColorChannel, ColorFilter, FilterQuality, IPoint, IRect, ISize,
-    Image, ImageFilter, Matrix, Paint, Picture, Point3, Rect, Region, TileMode, Vector,
+    Image, ImageFilter, Matrix, Paint, Picture, Point3, Rect, Region, RoundOut, SamplingOptions,
+    TileMode, Vector,
 };
 use skia_bindings as sb;
 use skia_bindings::SkImageFilter;
@@ -49,6 +50,18 @@ pub fn arithmetic<'a>(
     })
 }
 
+/// Blurs the output of `input` with the standard deviations `sigma_x` and `sigma_y`.
+///
+/// The blur samples up to three standard deviations around each pixel, `tile_mode` defines the
+/// values that are sampled outside of the input's bounds:
+///
+/// - [TileMode::Decal] (the default) samples transparent pixels, so the edges of the input fade
+///   out and the output grows by three standard deviations in each direction.
+/// - [TileMode::Clamp] repeats the edge pixels, which keeps the edges of background blurs and of
+///   blurred images opaque.
+/// - [TileMode::Repeat] and [TileMode::Mirror] wrap around the input.
+///
+/// If `crop_rect` is set, it defines the input's bounds and restricts the output.
 pub fn blur<'a>(
     (sigma_x, sigma_y): (scalar, scalar),
     tile_mode: impl Into<Option<TileMode>>,
@@ -89,6 +102,24 @@ pub fn compose(
     })
 }
 
+/// Restricts the output of `input` to `rect`, everything outside of it is transparent.
+///
+/// Skia m87 supports [TileMode::Decal] only, [None] is returned for all other tile modes. To
+/// repeat the input, use [tile()].
+pub fn crop(
+    rect: impl AsRef<Rect>,
+    tile_mode: impl Into<Option<TileMode>>,
+    input: impl Into<Option<ImageFilter>>,
+) -> Option<ImageFilter> {
+    // TODO: SkImageFilters::Crop() with tile modes is not available in m87, until then, decal
+    // cropping is implemented with an identity offset filter that uses a crop rect.
+    if tile_mode.into().unwrap_or(TileMode::Decal) != TileMode::Decal {
+        return None;
+    }
+    let crop_rect: IRect = rect.as_ref().round_out();
+    offset(Vector::default(), input, &crop_rect)
+}
+
 pub fn displacement_map<'a>(
     (x_channel_selector, y_channel_selector): (ColorChannel, ColorChannel),
     scale: scalar,
@@ -222,11 +253,19 @@ pub fn matrix_convolution<'a>(
     })
 }
 
+/// Transforms the output of `input` by `matrix`, which may contain perspective. `sampling`
+/// accepts [SamplingOptions] as well as the legacy [FilterQuality].
+///
+/// Returns [None] for cubic resamplers other than the one of [FilterQuality::High], see
+/// [SamplingOptions::filter_quality()].
 pub fn matrix_transform(
     matrix: &Matrix,
-    filter_quality: FilterQuality,
+    sampling: impl Into<SamplingOptions>,
     input: impl Into<Option<ImageFilter>>,
 ) -> Option<ImageFilter> {
+    // TODO: SkImageFilters::MatrixTransform() takes a SkFilterQuality in m87, so the sampling
+    // options are converted to the matching filter quality.
+    let filter_quality = sampling.into().filter_quality()?;
     ImageFilter::from_ptr(unsafe {
         sb::C_SkImageFilters_MatrixTransform(
             matrix.native(),
@@ -486,3 +525,31 @@ pub fn spot_lit_specular<'a>(
         )
     })
 }
+
+#[test]
+fn test_crop_tile_modes() {
+    let rect = Rect::from_xywh(10.0, 10.0, 20.5, 20.5);
+    assert!(crop(rect, None, None).is_some());
+    assert!(crop(rect, TileMode::Decal, None).is_some());
+    assert!(crop(rect, TileMode::Repeat, None).is_none());
+}
+
+#[test]
+fn test_blur_tile_modes() {
+    use crate::{Color, Surface};
+
+    let mut source = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    source.canvas().clear(Color::RED);
+    let image = source.image_snapshot();
+
+    let corner_alpha = |tile_mode: TileMode| {
+        let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+        let mut paint = Paint::default();
+        paint.set_image_filter(blur((2.0, 2.0), tile_mode, None, None));
+        surface.canvas().draw_image(&image, (8, 8), Some(&paint));
+        surface.peek_pixels().unwrap().get_color((8, 8)).a()
+    };
+
+    assert!(corner_alpha(TileMode::Decal) < 0xc0);
+    assert!(corner_alpha(TileMode::Clamp) > 0xf0);
+}
@@ -0,0 +1,278 @@
+//! Ready to use [RuntimeEffect]s for common effects.
+//!
+//! Each preset consists of its SkSL source, which can be copied as a starting point for custom
+//! effects, and a function that compiles it and sets its uniforms. Effects that need the
+//! content they are applied to take it as a `content` [Shader], for example an image shader.
+//!
+//! # Replacements for legacy effects
+//!
+//! Skia removed a number of effects in favor of effects that are written in SkSL:
+//!
+//! - `SkAvoidXfermode`, which modified the destination only where it was close to, or far from
+//!   a color, can be replaced by a runtime color filter that compares the color to a uniform
+//!   and keeps or changes it. [desaturate_except_hue()] shows how to select colors by their
+//!   hue.
+//! - `SkLightingShader`, which lit content with a normal map, has no direct replacement.
+//!   Lighting of an alpha mask, for example for embossed text, is available as the diffuse and
+//!   specular lighting image filters in [super::image_filters] and
+//!   [super::lighting_image_filter]. Normal map based lighting can be written as a runtime
+//!   shader that samples a normal map and a diffuse map as children.
+//! - `SkBlurDrawLooper` and `SkLayerDrawLooper` are replaced by drawing the content multiple
+//!   times, or by image filters, for example [super::image_filters::drop_shadow()]. The
+//!   [glow()] preset renders a glow or a soft drop shadow with a single shader.
+
+use super::runtime_effect::{self, RuntimeEffect};
+use crate::{scalar, Color4f, ColorFilter, Data, Point, Shader, Vector};
+use std::ffi::CString;
+
+/// The SkSL source of [glow()].
+pub const GLOW_SKSL: &str = r#"
+in shader content;
+uniform half4 glowColor;
+uniform float2 offset;
+uniform float radius;
+
+void main(float2 p, inout half4 color) {
+    float2 q = p - offset;
+    float d = radius * 0.7071;
+    half a = sample(content, q).a * 0.2;
+    a += (sample(content, q + float2(radius, 0)).a + sample(content, q - float2(radius, 0)).a
+        + sample(content, q + float2(0, radius)).a + sample(content, q - float2(0, radius)).a)
+        * 0.1;
+    a += (sample(content, q + float2(d, d)).a + sample(content, q - float2(d, d)).a
+        + sample(content, q + float2(d, -d)).a + sample(content, q - float2(d, -d)).a)
+        * 0.1;
+    half4 c = sample(content, p);
+    color = c + glowColor * a * (1 - c.a);
+}
+"#;
+
+/// The SkSL source of [outline()].
+pub const OUTLINE_SKSL: &str = r#"
+in shader content;
+uniform half4 outlineColor;
+uniform float width;
+
+void main(float2 p, inout half4 color) {
+    float d = width * 0.7071;
+    half a = max(
+        max(max(sample(content, p + float2(width, 0)).a, sample(content, p - float2(width, 0)).a),
+            max(sample(content, p + float2(0, width)).a, sample(content, p - float2(0, width)).a)),
+        max(max(sample(content, p + float2(d, d)).a, sample(content, p - float2(d, d)).a),
+            max(sample(content, p + float2(d, -d)).a, sample(content, p - float2(d, -d)).a)));
+    half4 c = sample(content, p);
+    color = c + outlineColor * a * (1 - c.a);
+}
+"#;
+
+/// The SkSL source of [desaturate_except_hue()].
+pub const DESATURATE_EXCEPT_HUE_SKSL: &str = r#"
+uniform half hue;
+uniform half tolerance;
+
+void main(inout half4 color) {
+    half3 rgb = color.a > 0 ? color.rgb / color.a : half3(0);
+    half maxC = max(rgb.r, max(rgb.g, rgb.b));
+    half minC = min(rgb.r, min(rgb.g, rgb.b));
+    half chroma = maxC - minC;
+    half keep = 0;
+    if (chroma > 0) {
+        half h;
+        if (maxC == rgb.r) {
+            h = (rgb.g - rgb.b) / chroma;
+        } else if (maxC == rgb.g) {
+            h = (rgb.b - rgb.r) / chroma + 2;
+        } else {
+            h = (rgb.r - rgb.g) / chroma + 4;
+        }
+        half distance = abs(fract(h / 6) - hue);
+        distance = min(distance, 1 - distance);
+        keep = 1 - smoothstep(tolerance * 0.5, tolerance, distance);
+    }
+    half luma = dot(rgb, half3(0.2126, 0.7152, 0.0722));
+    color.rgb = mix(half3(luma), rgb, keep) * color.a;
+}
+"#;
+
+/// The SkSL source of [vignette()].
+pub const VIGNETTE_SKSL: &str = r#"
+in shader content;
+uniform float2 center;
+uniform float radius;
+uniform float softness;
+uniform half strength;
+
+void main(float2 p, inout half4 color) {
+    half4 c = sample(content, p);
+    half v = smoothstep(radius, radius + softness, distance(p, center));
+    color = half4(c.rgb * (1 - v * strength), c.a);
+}
+"#;
+
+/// Draws `content` over a glow of `color` that follows its shape, blurred by `radius` and
+/// moved by `offset`. With an offset and a dark color, this renders a soft drop shadow.
+///
+/// The glow is approximated with 9 samples, so radii larger than a few pixels show banding.
+/// Use [super::image_filters::drop_shadow()] for large blur radii.
+pub fn glow(
+    content: Shader,
+    color: impl Into<Color4f>,
+    radius: scalar,
+    offset: impl Into<Vector>,
+) -> Option<Shader> {
+    let color = color.into().premul();
+    let offset = offset.into();
+    make_shader(
+        GLOW_SKSL,
+        &[
+            ("glowColor", &[color.r, color.g, color.b, color.a]),
+            ("offset", &[offset.x, offset.y]),
+            ("radius", &[radius]),
+        ],
+        content,
+    )
+}
+
+/// Draws `content` with an outline of `color` and `width` around its opaque parts.
+pub fn outline(content: Shader, color: impl Into<Color4f>, width: scalar) -> Option<Shader> {
+    let color = color.into().premul();
+    make_shader(
+        OUTLINE_SKSL,
+        &[
+            ("outlineColor", &[color.r, color.g, color.b, color.a]),
+            ("width", &[width]),
+        ],
+        content,
+    )
+}
+
+/// Returns a color filter that converts all colors to gray, except for the ones with a hue
+/// close to `hue_degrees`, for example `0` for red, or `120` for green.
+///
+/// Colors whose hues differ by less than half of `tolerance_degrees` keep their saturation,
+/// colors whose hues differ by more than `tolerance_degrees` turn gray, and in between, their
+/// saturation fades out.
+pub fn desaturate_except_hue(
+    hue_degrees: scalar,
+    tolerance_degrees: scalar,
+) -> Option<ColorFilter> {
+    let mut effect = runtime_effect::new(DESATURATE_EXCEPT_HUE_SKSL).ok()?;
+    let uniforms = uniform_data(
+        &effect,
+        &[
+            ("hue", &[hue_degrees.rem_euclid(360.0) / 360.0]),
+            ("tolerance", &[tolerance_degrees.max(0.01) / 360.0]),
+        ],
+    );
+    effect.make_color_filter(uniforms)
+}
+
+/// Darkens `content` outside of the circle at `center` with `radius`. The darkening fades in
+/// over `softness` pixels and reaches `strength`, which is between `0` for none and `1` for
+/// black.
+pub fn vignette(
+    content: Shader,
+    center: impl Into<Point>,
+    radius: scalar,
+    softness: scalar,
+    strength: scalar,
+) -> Option<Shader> {
+    let center = center.into();
+    make_shader(
+        VIGNETTE_SKSL,
+        &[
+            ("center", &[center.x, center.y]),
+            ("radius", &[radius]),
+            ("softness", &[softness.max(0.01)]),
+            ("strength", &[strength]),
+        ],
+        content,
+    )
+}
+
+fn make_shader(sksl: &str, uniforms: &[(&str, &[f32])], content: Shader) -> Option<Shader> {
+    let mut effect = runtime_effect::new(sksl).ok()?;
+    let uniforms = uniform_data(&effect, uniforms);
+    effect.make_shader(uniforms, Some(content), None, false)
+}
+
+/// Lays out the values of the named uniforms as the effect expects them.
+fn uniform_data(effect: &RuntimeEffect, uniforms: &[(&str, &[f32])]) -> Data {
+    let mut data = vec![0u8; effect.uniform_size()];
+    for (name, values) in uniforms {
+        let name = CString::new(*name).unwrap();
+        let offset = effect
+            .find_uniform(&name)
+            .expect("Uniform is not declared by the preset")
+            .offset();
+        for (i, value) in values.iter().enumerate() {
+            let start = offset + i * 4;
+            data[start..start + 4].copy_from_slice(&value.to_ne_bytes());
+        }
+    }
+    Data::new_copy(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Paint, Rect, Surface};
+
+    #[test]
+    fn presets_compile() {
+        for sksl in &[
+            GLOW_SKSL,
+            OUTLINE_SKSL,
+            DESATURATE_EXCEPT_HUE_SKSL,
+            VIGNETTE_SKSL,
+        ] {
+            if let Err(error) = runtime_effect::new(sksl) {
+                panic!("{}\n{}", error, sksl);
+            }
+        }
+        let content = Shader::color(Color::RED);
+        assert!(glow(content.clone(), Color::BLUE, 4.0, (2.0, 2.0)).is_some());
+        assert!(outline(content.clone(), Color::BLACK, 2.0).is_some());
+        assert!(desaturate_except_hue(0.0, 30.0).is_some());
+        assert!(vignette(content, (50.0, 50.0), 20.0, 10.0, 1.0).is_some());
+    }
+
+    fn render(paint: &Paint) -> Surface {
+        let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+        surface
+            .canvas()
+            .draw_rect(Rect::new(0.0, 0.0, 100.0, 100.0), paint);
+        surface
+    }
+
+    #[test]
+    fn vignette_darkens_the_corners() {
+        let mut paint = Paint::default();
+        paint.set_shader(vignette(
+            Shader::color(Color::WHITE),
+            (50.0, 50.0),
+            20.0,
+            10.0,
+            1.0,
+        ));
+        let mut surface = render(&paint);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((50, 50)), Color::WHITE);
+        assert_eq!(pixmap.get_color((0, 0)), Color::BLACK);
+    }
+
+    #[test]
+    fn desaturate_keeps_the_selected_hue() {
+        let mut paint = Paint::default();
+        paint.set_color_filter(desaturate_except_hue(0.0, 30.0));
+        paint.set_color(Color::RED);
+        let mut surface = render(&paint);
+        assert_eq!(surface.peek_pixels().unwrap().get_color((0, 0)), Color::RED);
+
+        paint.set_color(Color::BLUE);
+        let mut surface = render(&paint);
+        let blue = surface.peek_pixels().unwrap().get_color((0, 0));
+        assert_eq!(blue.r(), blue.g());
+        assert_eq!(blue.g(), blue.b());
+    }
+}
@@ -1,6 +1,9 @@
 mod backend_drawable_info;
 pub use self::backend_drawable_info::*;
 
+mod backend_semaphore;
+pub use self::backend_semaphore::*;
+
 mod backend_surface;
 pub use self::backend_surface::*;
 
@@ -13,6 +16,9 @@ pub use self::context_options::ContextOptions;
 mod context;
 pub use self::context::*;
 
+mod context_bound;
+pub use self::context_bound::*;
+
 #[cfg(feature = "d3d")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "d3d")))]
 pub mod d3d;
@@ -21,12 +27,15 @@ mod direct_context;
 pub use self::direct_context::*;
 
 mod driver_bug_workarounds;
-pub use self::driver_bug_workarounds::DriverBugWorkarounds;
+pub use self::driver_bug_workarounds::{DriverBugWorkarounds, NamedWorkarounds};
 
 #[cfg(feature = "gl")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gl")))]
 pub mod gl;
 
+mod memory_usage;
+pub use self::memory_usage::*;
+
 #[cfg(feature = "metal")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "metal")))]
 pub mod mtl;
@@ -34,6 +43,9 @@ pub mod mtl;
 mod recording_context;
 pub use self::recording_context::*;
 
+mod surface_pool;
+pub use self::surface_pool::*;
+
 mod types;
 pub use self::types::*;
 
@@ -0,0 +1,80 @@
+#[cfg(feature = "d3d")]
+use super::d3d;
+#[cfg(feature = "vulkan")]
+use super::vk;
+use super::BackendAPI;
+use crate::prelude::*;
+use skia_bindings as sb;
+use skia_bindings::GrBackendSemaphore;
+
+pub type BackendSemaphore = Handle<GrBackendSemaphore>;
+unsafe impl Send for BackendSemaphore {}
+unsafe impl Sync for BackendSemaphore {}
+
+impl NativeDrop for GrBackendSemaphore {
+    fn drop(&mut self) {
+        unsafe { sb::C_GrBackendSemaphore_destruct(self) }
+    }
+}
+
+impl NativeClone for GrBackendSemaphore {
+    fn clone(&self) -> Self {
+        construct(|semaphore| unsafe { sb::C_GrBackendSemaphore_CopyConstruct(semaphore, self) })
+    }
+}
+
+impl Default for BackendSemaphore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackendSemaphore {
+    pub fn new() -> Self {
+        Self::construct(|semaphore| unsafe { sb::C_GrBackendSemaphore_Construct(semaphore) })
+    }
+
+    /// Creates a semaphore that is backed by a D3D12 fence and a fence value.
+    ///
+    /// See [d3d::FenceInfo] for the protocol Skia expects.
+    #[cfg(feature = "d3d")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "d3d")))]
+    pub fn new_d3d(fence_info: &d3d::FenceInfo) -> Self {
+        Self::construct(|semaphore| unsafe {
+            sb::C_GrBackendSemaphore_ConstructD3D(semaphore, fence_info.native())
+        })
+    }
+
+    /// Creates a semaphore that is backed by a Vulkan semaphore. The semaphore is not owned, it
+    /// must be destroyed by the caller after the GPU has signaled or waited on it.
+    #[cfg(feature = "vulkan")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
+    pub fn new_vulkan(semaphore: vk::Semaphore) -> Self {
+        Self::construct(|s| unsafe { sb::C_GrBackendSemaphore_ConstructVk(s, semaphore) })
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        unsafe { sb::C_GrBackendSemaphore_isInitialized(self.native()) }
+    }
+
+    pub fn backend(&self) -> BackendAPI {
+        self.native().fBackend
+    }
+
+    /// Returns the Vulkan semaphore, or [None] if the semaphore is not backed by Vulkan.
+    #[cfg(feature = "vulkan")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
+    pub fn vulkan_semaphore(&self) -> Option<vk::Semaphore> {
+        let semaphore = unsafe { sb::C_GrBackendSemaphore_vkSemaphore(self.native()) };
+        let null: vk::Semaphore = vk::NULL_HANDLE.into();
+        (semaphore != null).if_true_some(semaphore)
+    }
+
+    #[cfg(feature = "d3d")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "d3d")))]
+    pub fn d3d_fence_info(&self) -> Option<d3d::FenceInfo> {
+        let mut info = std::mem::MaybeUninit::uninit();
+        unsafe { sb::C_GrBackendSemaphore_getD3DFenceInfo(self.native(), info.as_mut_ptr()) }
+            .if_true_then_some(|| d3d::FenceInfo::from_native_c(unsafe { info.assume_init() }))
+    }
+}
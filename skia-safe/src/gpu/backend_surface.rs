@@ -205,6 +205,28 @@ impl BackendTexture {
         .unwrap()
     }
 
+    /// Creates a backend texture from a Metal texture, taking its dimensions and mipmap levels
+    /// from the texture itself. The texture is retained by the returned `BackendTexture`.
+    #[cfg(feature = "metal")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "metal")))]
+    pub fn from_metal_texture(texture: &metal_rs::TextureRef) -> Self {
+        let mipmapped = if texture.mipmap_level_count() > 1 {
+            super::Mipmapped::Yes
+        } else {
+            super::Mipmapped::No
+        };
+        unsafe {
+            Self::new_metal(
+                (
+                    texture.width().try_into().unwrap(),
+                    texture.height().try_into().unwrap(),
+                ),
+                mipmapped,
+                &mtl::TextureInfo::from_texture(texture),
+            )
+        }
+    }
+
     #[cfg(feature = "d3d")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "d3d")))]
     pub fn new_d3d((width, height): (i32, i32), d3d_info: &d3d::TextureResourceInfo) -> Self {
@@ -251,6 +273,11 @@ impl BackendTexture {
 
     #[cfg(feature = "gl")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gl")))]
+    /// Returns the target, id and format of the GL texture, so that it can be bound by
+    /// the embedding application, or [None] if this is not a GL texture.
+    ///
+    /// If the application changes the texture's parameters, it must call
+    /// [Self::gl_texture_parameters_modified()] before Skia uses the texture again.
     pub fn gl_texture_info(&self) -> Option<gl::TextureInfo> {
         unsafe {
             let mut texture_info = gl::TextureInfo::default();
@@ -268,6 +295,11 @@ impl BackendTexture {
 
     #[cfg(feature = "vulkan")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
+    /// Returns a copy of the image, layout and queue family of the Vulkan texture, or [None] if
+    /// this is not a Vulkan texture.
+    ///
+    /// If the application transitions the image to another layout, it must report the new layout
+    /// with [Self::set_vulkan_image_layout()].
     pub fn vulkan_image_info(&self) -> Option<vk::ImageInfo> {
         unsafe {
             // constructor not available.
@@ -459,6 +491,8 @@ impl BackendRenderTarget {
 
     #[cfg(feature = "gl")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gl")))]
+    /// Returns the framebuffer object id and format of the GL render target, or [None] if this is
+    /// not a GL render target.
     pub fn gl_framebuffer_info(&self) -> Option<gl::FramebufferInfo> {
         let mut info = gl::FramebufferInfo::default();
         unsafe { self.native().getGLFramebufferInfo(info.native_mut()) }.if_true_some(info)
@@ -466,6 +500,8 @@ impl BackendRenderTarget {
 
     #[cfg(feature = "vulkan")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
+    /// Returns a copy of the image, layout and queue family of the Vulkan render target, or
+    /// [None] if this is not a Vulkan render target.
     pub fn vulkan_image_info(&self) -> Option<vk::ImageInfo> {
         let mut info = vk::ImageInfo::default();
         unsafe { self.native().getVkImageInfo(info.native_mut()) }.if_true_some(info)
@@ -520,3 +556,31 @@ impl BackendRenderTarget {
         self.native().fIsValid
     }
 }
+
+#[cfg(all(test, feature = "gl"))]
+mod tests {
+    use super::{gl, BackendRenderTarget, BackendTexture};
+    use crate::gpu::{BackendAPI, Mipmapped};
+
+    #[test]
+    fn gl_texture_info_round_trip() {
+        let info = gl::TextureInfo {
+            target: 0x0DE1, // GL_TEXTURE_2D
+            id: 7,
+            format: 0x8058, // GL_RGBA8
+        };
+        let texture = unsafe { BackendTexture::new_gl((16, 16), Mipmapped::No, info) };
+        assert_eq!(texture.backend(), BackendAPI::OpenGL);
+        assert_eq!(texture.gl_texture_info(), Some(info));
+    }
+
+    #[test]
+    fn gl_framebuffer_info_round_trip() {
+        let info = gl::FramebufferInfo {
+            fboid: 3,
+            format: 0x8058, // GL_RGBA8
+        };
+        let render_target = BackendRenderTarget::new_gl((16, 16), None, 8, info);
+        assert_eq!(render_target.gl_framebuffer_info(), Some(info));
+    }
+}
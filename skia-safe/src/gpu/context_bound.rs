@@ -0,0 +1,88 @@
+use super::DirectContext;
+
+/// Owns a [DirectContext] together with the state that depends on it, like surfaces, images and
+/// caches of GPU resources, and releases both as soon as the context is lost.
+///
+/// ```rust,ignore
+/// let mut gpu = ContextBound::new(context, Layers::new());
+/// loop {
+///     if !gpu.check() {
+///         gpu.replace(create_context()?, Layers::new());
+///     }
+///     if let Some((context, layers)) = gpu.get_mut() {
+///         // draw the frame
+///     }
+/// }
+/// ```
+pub struct ContextBound<S> {
+    // note: the state is declared before the context, so that it is dropped first.
+    state: Option<S>,
+    context: Option<DirectContext>,
+}
+
+impl<S> ContextBound<S> {
+    pub fn new(context: DirectContext, state: S) -> Self {
+        ContextBound {
+            state: Some(state),
+            context: Some(context),
+        }
+    }
+
+    /// Checks the health of the context and should be called once per frame before drawing.
+    ///
+    /// Returns `true` if the context can be used. If the context was lost, it is abandoned
+    /// without making any backend API calls, then the state and the context are dropped, and
+    /// `false` is returned until [Self::replace()] is called.
+    pub fn check(&mut self) -> bool {
+        match &mut self.context {
+            Some(context) if !context.abandoned() => true,
+            Some(context) => {
+                context.abandon();
+                self.release();
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the context was lost and its state released.
+    pub fn is_lost(&self) -> bool {
+        self.context.is_none()
+    }
+
+    pub fn context(&mut self) -> Option<&mut DirectContext> {
+        self.context.as_mut()
+    }
+
+    pub fn state(&mut self) -> Option<&mut S> {
+        self.state.as_mut()
+    }
+
+    pub fn get_mut(&mut self) -> Option<(&mut DirectContext, &mut S)> {
+        match (&mut self.context, &mut self.state) {
+            (Some(context), Some(state)) => Some((context, state)),
+            _ => None,
+        }
+    }
+
+    /// Replaces the context and its state, for example with a newly created context after the
+    /// previous one was lost. The previous state is dropped before the previous context.
+    pub fn replace(&mut self, context: DirectContext, state: S) {
+        self.release();
+        self.state = Some(state);
+        self.context = Some(context);
+    }
+
+    /// Returns the context and its state, or [None] if the context was lost.
+    pub fn into_inner(self) -> Option<(DirectContext, S)> {
+        match (self.context, self.state) {
+            (Some(context), Some(state)) => Some((context, state)),
+            _ => None,
+        }
+    }
+
+    fn release(&mut self) {
+        self.state = None;
+        self.context = None;
+    }
+}
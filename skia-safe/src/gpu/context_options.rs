@@ -1,8 +1,12 @@
 use crate::gpu::DriverBugWorkarounds;
 use crate::prelude::*;
+use crate::Data;
 use skia_bindings as sb;
-use skia_bindings::GrContextOptions;
+use skia_bindings::{GrContextOptions, SkData};
 use std::os::raw;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Mutex;
 
 pub use skia_bindings::GrContextOptions_Enable as Enable;
 #[test]
@@ -16,34 +20,85 @@ fn test_shader_cache_strategy_naming() {
     let _ = ShaderCacheStrategy::BackendSource;
 }
 
+/// Options that tune the behavior of a [super::DirectContext], passed when the context is
+/// created. The defaults suit most applications.
 #[repr(C)]
 pub struct ContextOptions {
+    /// Suppress prints for the GrContext.
     pub suppress_prints: bool,
+    /// Controls whether we check for GL errors after functions that allocate resources (e.g.
+    /// glTexImage2D), for shader compilation success, and program link success. Ignored on
+    /// backends other than GL.
     pub skip_gl_error_checks: Enable,
+    /// Overrides the maximum texture size that is detected by querying the backend API. It can
+    /// only reduce the limit, never increase it beyond the detected value.
     pub max_texture_size_override: raw::c_int,
+    /// The threshold in bytes above which we will use a buffer mapping API to map vertex and index
+    /// buffers to CPU memory in order to update them. A value of -1 means the GrContext should
+    /// deduce the optimal value for this platform.
     pub buffer_map_threshold: raw::c_int,
     executor: *mut sb::SkExecutor,
+    /// Construct mipmaps manually, via repeated downsampling draw-calls. This is used when the
+    /// driver's implementation (glGenerateMipmap) contains bugs. This requires mipmap level
+    /// control (ie desktop or ES3).
     pub do_manual_mipmapping: bool,
+    /// Disables the use of coverage counting shortcuts to render paths. Coverage counting can
+    /// cause artifacts along shared edges if care isn't taken to ensure both contours wind in the
+    /// same direction.
     pub disable_coverage_counting_paths: bool,
+    /// Disables the use of distance fields to render paths.
     pub disable_distance_field_paths: bool,
+    /// If `true` this allows path mask textures to be cached. This is only really useful if paths
+    /// are commonly rendered at the same scale and fractional translation.
     pub allow_path_mask_caching: bool,
+    /// If `true`, the GPU will not be used to perform YUV -> RGB conversion when generating
+    /// textures from codec-backed images.
     pub disable_gpu_yuv_conversion: bool,
+    /// The maximum size of cache textures used for Skia's Glyph cache.
     pub glyph_cache_texture_maximum_bytes: usize,
+    /// Below this threshold size in device space distance field fonts won't be used. Distance
+    /// field fonts don't support hinting which is more important at smaller sizes.
     pub min_distance_field_font_size: f32,
+    /// Above this threshold size in device space glyphs are drawn as individual paths.
     pub glyphs_as_paths_font_size: f32,
+    /// Can the glyph atlas use multiple textures. If allowed, each texture's size is bound by
+    /// `glyph_cache_texture_maximum_bytes`.
     pub allow_multiple_glyph_cache_textures: Enable,
+    /// Bugs on certain drivers cause stencil buffers to leak. This flag causes Skia to avoid
+    /// allocating stencil buffers and use alternate rasterization paths, avoiding the leak.
     pub avoid_stencil_buffers: bool,
+    /// If `true`, texture fetches from mip-mapped textures will be biased to read larger MIP
+    /// levels. This has the effect of sharpening those textures, at the cost of some aliasing,
+    /// and possible performance impact.
     pub sharpen_mipmapped_textures: bool,
+    /// Enables driver workaround to use draws instead of HW clears, e.g. glClear on the GL
+    /// backend.
     pub use_draw_instead_of_clear: Enable,
+    /// Allow Ganesh to more aggressively reorder operations to reduce the number of render passes.
+    /// Offscreen draws will be done upfront instead of interrupting the main render pass when
+    /// possible.
     pub reduce_ops_task_splitting: Enable,
+    /// Some ES3 contexts report the ESSL 1.0 and 3.0 texture extensions, but the latter isn't
+    /// available. If set, the ES3 extension is ignored.
     pub prefer_external_images_over_es3: bool,
+    /// Disables correctness workarounds that are enabled for particular GPUs, OSes, or drivers.
+    /// This does not affect code path choices that are made for perfomance reasons nor does it
+    /// override other [ContextOptions] settings.
     pub disable_driver_correctness_workarounds: bool,
+    /// Maximum number of GPU programs or pipelines to keep active in the runtime cache.
     pub runtime_program_cache_size: raw::c_int,
     persistent_cache: *mut sb::GrContextOptions_PersistentCache,
+    /// What to store in the [PersistentCache], if one is set: compiled program binaries, backend
+    /// source, or SkSL.
     pub shader_cache_strategy: ShaderCacheStrategy,
     shader_error_handler: *mut sb::GrContextOptions_ShaderErrorHandler,
+    /// The maximum number of samples to use when rendering internally with multisampling, for
+    /// example to draw paths. Set to `0` to disable internal multisampling.
     pub internal_multisample_count: raw::c_int,
+    /// Maximum number of Vulkan secondary command buffers that are kept in a cache for reuse. A
+    /// value of -1 means Skia decides.
     pub max_cached_vulkan_secondary_command_buffers: raw::c_int,
+    /// Workarounds for specific GPU driver bugs.
     pub driver_bug_workarounds: DriverBugWorkarounds,
 }
 unsafe impl Send for ContextOptions {}
@@ -59,6 +114,96 @@ impl ContextOptions {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Lets Skia load and store compiled shader programs in `cache`, so that they do not need to
+    /// be compiled again in later runs of the application. See [Self::shader_cache_strategy] for
+    /// what is stored.
+    ///
+    /// # Safety
+    ///
+    /// `cache` must outlive all contexts that are created with these options.
+    pub unsafe fn set_persistent_cache(&mut self, cache: &PersistentCacheAdapter) -> &mut Self {
+        self.persistent_cache = cache.native;
+        self
+    }
+}
+
+/// A cache of compiled shader programs that persists across runs of the application, for
+/// example in a file, see [ContextOptions::set_persistent_cache()].
+///
+/// The keys and data are opaque to the cache. Cached data must be discarded when the GPU driver
+/// or Skia changes.
+pub trait PersistentCache: Send {
+    /// Returns the data that was stored for `key`, if any.
+    fn load(&mut self, key: &Data) -> Option<Data>;
+
+    /// Stores `data` for `key`.
+    fn store(&mut self, key: &Data, data: &Data);
+}
+
+/// Makes a [PersistentCache] available to Skia.
+///
+/// Calls to the cache are serialized, so the same adapter can be used for contexts on different
+/// threads.
+pub struct PersistentCacheAdapter {
+    native: *mut sb::GrContextOptions_PersistentCache,
+    // note: boxed, so that the native cache can refer to it with a stable, thin pointer.
+    _cache: Box<Mutex<Box<dyn PersistentCache>>>,
+}
+
+unsafe impl Send for PersistentCacheAdapter {}
+unsafe impl Sync for PersistentCacheAdapter {}
+
+impl Drop for PersistentCacheAdapter {
+    fn drop(&mut self) {
+        unsafe { sb::C_GrContextOptions_PersistentCache_delete(self.native) }
+    }
+}
+
+impl PersistentCacheAdapter {
+    pub fn new(cache: impl PersistentCache + 'static) -> Self {
+        let cache: Box<Mutex<Box<dyn PersistentCache>>> = Box::new(Mutex::new(Box::new(cache)));
+        let param = sb::RustPersistentCache_Param {
+            cache: &*cache as *const _ as *mut raw::c_void,
+            load: Some(load_trampoline),
+            store: Some(store_trampoline),
+        };
+        PersistentCacheAdapter {
+            native: unsafe { sb::C_RustPersistentCache_new(&param) },
+            _cache: cache,
+        }
+    }
+}
+
+// The cache is called from Skia's shader compiler, which a panic must not unwind into. A cache
+// that panicked poisons its mutex and is not called anymore.
+
+unsafe extern "C" fn load_trampoline(cache: *mut raw::c_void, key: *const SkData) -> *mut SkData {
+    let cache = &*(cache as *const Mutex<Box<dyn PersistentCache>>);
+    let key = Data::from_unshared_ptr(key as *mut _).unwrap();
+    panic::catch_unwind(AssertUnwindSafe(|| match cache.lock() {
+        Ok(mut cache) => cache.load(&key),
+        Err(_) => None,
+    }))
+    .ok()
+    .flatten()
+    .map(|data| data.into_ptr())
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn store_trampoline(
+    cache: *mut raw::c_void,
+    key: *const SkData,
+    data: *const SkData,
+) {
+    let cache = &*(cache as *const Mutex<Box<dyn PersistentCache>>);
+    let key = Data::from_unshared_ptr(key as *mut _).unwrap();
+    let data = Data::from_unshared_ptr(data as *mut _).unwrap();
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(mut cache) = cache.lock() {
+            cache.store(&key, &data)
+        }
+    }));
 }
 
 impl NativeTransmutable<GrContextOptions> for ContextOptions {}
@@ -66,3 +211,60 @@ impl NativeTransmutable<GrContextOptions> for ContextOptions {}
 fn test_context_options_layout() {
     ContextOptions::test_layout()
 }
+
+#[test]
+fn test_persistent_cache_adapter() {
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemoryCache(HashMap<Vec<u8>, Vec<u8>>);
+
+    impl PersistentCache for MemoryCache {
+        fn load(&mut self, key: &Data) -> Option<Data> {
+            self.0.get(key.as_bytes()).map(|data| Data::new_copy(data))
+        }
+
+        fn store(&mut self, key: &Data, data: &Data) {
+            self.0
+                .insert(key.as_bytes().to_vec(), data.as_bytes().to_vec());
+        }
+    }
+
+    let adapter = PersistentCacheAdapter::new(MemoryCache::default());
+    let key = Data::new_copy(b"key");
+    let data = Data::new_copy(b"program");
+    unsafe {
+        let cache = &*adapter._cache as *const _ as *mut raw::c_void;
+        store_trampoline(cache, key.native(), data.native());
+        let loaded = Data::from_ptr(load_trampoline(cache, key.native())).unwrap();
+        assert_eq!(loaded.as_bytes(), b"program");
+        assert!(load_trampoline(cache, data.native()).is_null());
+    }
+
+    let mut options = ContextOptions::new();
+    unsafe { options.set_persistent_cache(&adapter) };
+}
+
+#[test]
+fn test_panicking_persistent_cache() {
+    struct Panics;
+
+    impl PersistentCache for Panics {
+        fn load(&mut self, _key: &Data) -> Option<Data> {
+            panic!("load")
+        }
+
+        fn store(&mut self, _key: &Data, _data: &Data) {
+            panic!("store")
+        }
+    }
+
+    let adapter = PersistentCacheAdapter::new(Panics);
+    let key = Data::new_copy(b"key");
+    unsafe {
+        let cache = &*adapter._cache as *const _ as *mut raw::c_void;
+        assert!(load_trampoline(cache, key.native()).is_null());
+        store_trampoline(cache, key.native(), key.native());
+        assert!(load_trampoline(cache, key.native()).is_null());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::gpu;
 use crate::prelude::*;
 use skia_bindings as sb;
 use winapi::{shared::dxgi, shared::dxgiformat, um::d3d12};
@@ -14,11 +15,38 @@ pub use sb::GrD3DResourceStateEnum as ResourceStateEnum;
 
 pub use d3d12::ID3D12CommandQueue;
 pub use d3d12::ID3D12Device;
+pub use d3d12::ID3D12Fence;
 pub use d3d12::ID3D12Resource;
 pub use d3d12::D3D12_RESOURCE_STATES;
 pub use dxgi::IDXGIAdapter1;
 pub use dxgiformat::DXGI_FORMAT;
 
+/// Wraps a D3D12 texture resource that is currently in `resource_state` into a
+/// [gpu::BackendTexture].
+///
+/// Dimensions, format, sample count, and mip levels are taken from the resource's description.
+pub fn backend_texture_from_resource(
+    resource: cp<ID3D12Resource>,
+    resource_state: D3D12_RESOURCE_STATES,
+) -> gpu::BackendTexture {
+    let info = TextureResourceInfo::from_resource_desc(resource, resource_state);
+    let dimensions = info.resource_dimensions();
+    gpu::BackendTexture::new_d3d((dimensions.width, dimensions.height), &info)
+}
+
+/// Wraps a D3D12 resource that is currently in `resource_state` into a
+/// [gpu::BackendRenderTarget], for example a swap chain buffer.
+///
+/// Dimensions, format, and sample count are taken from the resource's description.
+pub fn backend_render_target_from_resource(
+    resource: cp<ID3D12Resource>,
+    resource_state: D3D12_RESOURCE_STATES,
+) -> gpu::BackendRenderTarget {
+    let info = TextureResourceInfo::from_resource_desc(resource, resource_state);
+    let dimensions = info.resource_dimensions();
+    gpu::BackendRenderTarget::new_d3d((dimensions.width, dimensions.height), &info)
+}
+
 impl NativeTransmutable<sb::DXGI_FORMAT> for dxgiformat::DXGI_FORMAT {}
 #[test]
 fn test_dxgi_format_layout() {
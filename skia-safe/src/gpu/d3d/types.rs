@@ -1,7 +1,10 @@
 use super::{ID3D12Resource, D3D12_RESOURCE_STATES, DXGI_FORMAT};
 use crate::gpu;
 use crate::prelude::*;
-use skia_bindings::{GrD3DAlloc, GrD3DMemoryAllocator, GrD3DTextureResourceInfo, SkRefCntBase};
+use crate::ISize;
+use skia_bindings::{
+    GrD3DAlloc, GrD3DFenceInfo, GrD3DMemoryAllocator, GrD3DTextureResourceInfo, SkRefCntBase,
+};
 use winapi::{
     shared::dxgiformat,
     shared::dxgitype,
@@ -78,12 +81,42 @@ impl TextureResourceInfo {
         }
     }
 
+    /// Creates a `TextureResourceInfo` for a resource that is currently in `resource_state`.
+    ///
+    /// The format, sample count, sample quality, and the number of mip levels are taken from the
+    /// resource's description.
+    pub fn from_resource_desc(
+        resource: cp<ID3D12Resource>,
+        resource_state: D3D12_RESOURCE_STATES,
+    ) -> Self {
+        let desc = unsafe { resource.GetDesc() };
+        Self {
+            resource,
+            alloc: None,
+            resource_state,
+            format: desc.Format,
+            sample_count: desc.SampleDesc.Count,
+            level_count: desc.MipLevels.into(),
+            sample_quality_pattern: desc.SampleDesc.Quality,
+            protected: gpu::Protected::No,
+        }
+    }
+
     pub fn with_state(self, resource_state: D3D12_RESOURCE_STATES) -> Self {
         Self {
             resource_state,
             ..self
         }
     }
+
+    /// The width and height of the resource as reported by its description.
+    pub fn resource_dimensions(&self) -> ISize {
+        let desc = unsafe { self.resource.GetDesc() };
+        ISize::new(
+            desc.Width.try_into().unwrap(),
+            desc.Height.try_into().unwrap(),
+        )
+    }
 }
 
 impl From<cp<ID3D12Resource>> for TextureResourceInfo {
@@ -98,6 +131,18 @@ fn test_texture_resource_info_layout() {
     TextureResourceInfo::test_layout();
 }
 
+// TODO: Skia can also signal fences after flushed work completed, but the signal semaphores of
+// gpu::FlushInfo are not accessible yet.
+
+/// A fence and a fence value that is used to synchronize Skia's GPU work with the application's.
+///
+/// A semaphore created with [gpu::BackendSemaphore::new_d3d] and passed to
+/// [gpu::DirectContext::wait()] makes Skia's queue wait until the fence reaches `value` before
+/// any subsequently submitted work executes. The application must signal the fence with this
+/// value on its own queue.
+///
+/// Fence values are monotonically increasing, so the application has to increment `value` for
+/// every new wait request on the same fence.
 #[repr(C)]
 #[derive(Clone)]
 pub struct FenceInfo {
@@ -106,3 +151,24 @@ pub struct FenceInfo {
 }
 unsafe impl Send for FenceInfo {}
 unsafe impl Sync for FenceInfo {}
+
+impl FenceInfo {
+    pub fn new(fence: cp<d3d12::ID3D12Fence>, value: u64) -> Self {
+        Self { fence, value }
+    }
+
+    /// Returns a `FenceInfo` for the same fence that refers to the next fence value, or [None]
+    /// if `value` is [u64::MAX].
+    pub fn next(&self) -> Option<Self> {
+        Some(Self {
+            fence: self.fence.clone(),
+            value: self.value.checked_add(1)?,
+        })
+    }
+}
+
+impl NativeTransmutable<GrD3DFenceInfo> for FenceInfo {}
+#[test]
+fn test_fence_info_layout() {
+    FenceInfo::test_layout();
+}
@@ -4,19 +4,37 @@ use super::d3d;
 #[cfg(feature = "gl")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gl")))]
 use super::gl;
+#[cfg(feature = "metal")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "metal")))]
+use super::mtl;
 #[cfg(feature = "vulkan")]
 #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
 use super::vk;
-use super::ContextOptions;
+use super::{
+    BackendAPI, BackendSemaphore, Capabilities, ContextOptions, RecordingContext,
+    ResourceCacheUsage,
+};
 use crate::prelude::*;
+use crate::{ColorType, Data};
 use skia_bindings as sb;
 use skia_bindings::{GrDirectContext, SkRefCntBase};
 use std::ops::{Deref, DerefMut};
+use std::os::raw::c_long;
+use std::time::Duration;
 
+/// A context that submits work directly to a GPU backend.
+///
+/// A context and all surfaces and images that are backed by it must only be used from the thread
+/// that created it, so they can not be sent to another thread with
+/// [ConditionallySend::wrap_send()].
 pub type DirectContext = RCHandle<GrDirectContext>;
 
 impl NativeRefCountedBase for GrDirectContext {
     type Base = SkRefCntBase;
+
+    fn is_thread_affine(&self) -> bool {
+        true
+    }
 }
 
 impl Deref for DirectContext {
@@ -48,6 +66,27 @@ impl DirectContext {
         })
     }
 
+    /// Same as [Self::new_gl()], but returns the reason why the context could not be created.
+    ///
+    /// An interface that does not provide all the functions Skia requires is reported as
+    /// [crate::SkiaError::InvalidParameters].
+    #[cfg(feature = "gl")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gl")))]
+    pub fn try_new_gl<'a>(
+        interface: impl Into<Option<gl::Interface>>,
+        options: impl Into<Option<&'a ContextOptions>>,
+    ) -> Result<DirectContext, crate::SkiaError> {
+        let interface = interface.into();
+        if let Some(interface) = &interface {
+            if !interface.validate() {
+                return Err(crate::SkiaError::InvalidParameters(
+                    "the GL interface is incomplete",
+                ));
+            }
+        }
+        DirectContext::new_gl(interface, options).ok_or(crate::SkiaError::Unknown)
+    }
+
     #[cfg(feature = "vulkan")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "vulkan")))]
     pub fn new_vulkan<'a>(
@@ -65,21 +104,23 @@ impl DirectContext {
         }
     }
 
-    /// # Safety
-    /// This function is unsafe because `device` and `queue` are untyped handles which need to exceed the
-    /// lifetime of the context returned.
+    /// Creates a context that renders with Metal.
+    ///
+    /// The context retains `device` and `queue` for as long as it exists.
     #[cfg(feature = "metal")]
     #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "metal")))]
-    pub unsafe fn new_metal<'a>(
-        device: *mut std::ffi::c_void,
-        queue: *mut std::ffi::c_void,
+    pub fn new_metal<'a>(
+        device: impl Into<mtl::Device>,
+        queue: impl Into<mtl::CommandQueue>,
         options: impl Into<Option<&'a ContextOptions>>,
     ) -> Option<DirectContext> {
-        DirectContext::from_ptr(sb::C_GrContext_MakeMetal(
-            device,
-            queue,
-            options.into().native_ptr_or_null(),
-        ))
+        DirectContext::from_ptr(unsafe {
+            sb::C_GrContext_MakeMetal(
+                device.into().as_raw() as _,
+                queue.into().as_raw() as _,
+                options.into().native_ptr_or_null(),
+            )
+        })
     }
 
     #[cfg(feature = "d3d")]
@@ -93,4 +134,271 @@ impl DirectContext {
             options.into().native_ptr_or_null(),
         ))
     }
+
+    /// Creates a context with Skia's mock backend, which accepts all commands but does not
+    /// render anything, so that code that uses the GPU API can be tested without a GPU.
+    ///
+    /// Surfaces and textures of the common 8 bit color types can be created, but their content
+    /// is undefined.
+    pub fn new_mock<'a>(options: impl Into<Option<&'a ContextOptions>>) -> Option<DirectContext> {
+        DirectContext::from_ptr(unsafe {
+            sb::C_GrDirectContext_MakeMock(options.into().native_ptr_or_null())
+        })
+    }
+
+    // From GrContext_Base
+    pub fn backend(&self) -> BackendAPI {
+        self.recording_context().backend()
+    }
+
+    /// See [RecordingContext::max_texture_size()].
+    pub fn max_texture_size(&self) -> i32 {
+        self.recording_context().max_texture_size()
+    }
+
+    /// See [RecordingContext::max_render_target_size()].
+    pub fn max_render_target_size(&self) -> i32 {
+        self.recording_context().max_render_target_size()
+    }
+
+    /// See [RecordingContext::color_type_supported_as_surface()].
+    pub fn color_type_supported_as_surface(&self, color_type: ColorType) -> bool {
+        self.recording_context()
+            .color_type_supported_as_surface(color_type)
+    }
+
+    /// See [RecordingContext::color_type_supported_as_image()].
+    pub fn color_type_supported_as_image(&self, color_type: ColorType) -> bool {
+        self.recording_context()
+            .color_type_supported_as_image(color_type)
+    }
+
+    /// See [RecordingContext::max_surface_sample_count_for_color_type()].
+    pub fn max_surface_sample_count_for_color_type(&self, color_type: ColorType) -> usize {
+        self.recording_context()
+            .max_surface_sample_count_for_color_type(color_type)
+    }
+
+    /// See [RecordingContext::capabilities()].
+    pub fn capabilities(&self) -> Capabilities {
+        self.recording_context().capabilities()
+    }
+
+    // A GrDirectContext is a GrRecordingContext, and both are wrapped in an RCHandle.
+    fn recording_context(&self) -> &RecordingContext {
+        unsafe { transmute_ref(self) }
+    }
+
+    /// Returns `true` if the context was abandoned or if the backend reports that the device
+    /// was lost, in which case the context is abandoned, too.
+    ///
+    /// An abandoned context can not be used anymore. All surfaces and images that were created
+    /// with it should be dropped, and a new context created. See [super::ContextBound] for a
+    /// helper that does that.
+    pub fn abandoned(&mut self) -> bool {
+        unsafe { sb::C_GrDirectContext_abandoned(self.native_mut()) }
+    }
+
+    /// Abandons all GPU resources and puts the context into a state in which all operations are
+    /// no-ops. No backend API calls are made, so this is safe to call after a device was lost.
+    pub fn abandon(&mut self) -> &mut Self {
+        unsafe { sb::C_GrDirectContext_abandonContext(self.native_mut()) }
+        self
+    }
+
+    /// Like [Self::abandon()], but frees the backend resources before, which requires that the
+    /// backend context is still valid.
+    pub fn release_resources_and_abandon(&mut self) -> &mut Self {
+        unsafe { sb::C_GrDirectContext_releaseResourcesAndAbandonContext(self.native_mut()) }
+        self
+    }
+
+    /// Frees GPU resources that were not used for at least `not_used`.
+    pub fn perform_deferred_cleanup(&mut self, not_used: Duration) -> &mut Self {
+        unsafe {
+            sb::C_GrContext_performDeferredCleanup(
+                self.native_mut(),
+                not_used.as_millis().try_into().unwrap_or(c_long::MAX),
+            )
+        }
+        self
+    }
+
+    /// Frees all GPU resources that are not in use, for example when the application receives a
+    /// memory warning.
+    pub fn free_gpu_resources(&mut self) -> &mut Self {
+        unsafe { sb::C_GrDirectContext_freeGpuResources(self.native_mut()) }
+        self
+    }
+
+    /// Purges unlocked resources from the resource cache. If `scratch_resources_only` is `true`,
+    /// resources that hold persistent data, like the textures of images, are kept.
+    pub fn purge_unlocked_resources(&mut self, scratch_resources_only: bool) -> &mut Self {
+        unsafe {
+            sb::C_GrDirectContext_purgeUnlockedResources(self.native_mut(), scratch_resources_only)
+        }
+        self
+    }
+
+    /// Returns the GPU memory that is held by the resource cache of the context.
+    ///
+    /// Use [super::estimate_surface_memory()], [crate::Surface::estimate_gpu_memory()], or
+    /// [crate::Image::estimate_gpu_memory()] to estimate what individual resources contribute.
+    pub fn resource_cache_usage(&self) -> ResourceCacheUsage {
+        let mut resource_count = 0;
+        let mut resource_bytes = 0;
+        unsafe {
+            sb::C_GrDirectContext_getResourceCacheUsage(
+                self.native(),
+                &mut resource_count,
+                &mut resource_bytes,
+            )
+        }
+        ResourceCacheUsage {
+            resource_count: resource_count.try_into().unwrap(),
+            resource_bytes,
+            purgeable_bytes: unsafe {
+                sb::C_GrDirectContext_getResourceCachePurgeableBytes(self.native())
+            },
+            limit: self.resource_cache_limit(),
+        }
+    }
+
+    /// The budget of the resource cache in bytes.
+    pub fn resource_cache_limit(&self) -> usize {
+        unsafe { sb::C_GrDirectContext_getResourceCacheLimit(self.native()) }
+    }
+
+    /// Sets the budget of the resource cache. If the cache exceeds the budget, unused
+    /// resources are purged, for example when the application runs on a device with little
+    /// memory.
+    pub fn set_resource_cache_limit(&mut self, max_resource_bytes: usize) -> &mut Self {
+        unsafe {
+            sb::C_GrDirectContext_setResourceCacheLimit(self.native_mut(), max_resource_bytes)
+        }
+        self
+    }
+
+    /// Compiles a shader program that was stored by a
+    /// [super::context_options::PersistentCache] in a previous run ahead of time, for example
+    /// while a loading screen is shown, so that it does not need to be compiled when it is first
+    /// used. Returns `false` if the program could not be compiled.
+    pub fn precompile_shader(&mut self, key: &Data, data: &Data) -> bool {
+        unsafe {
+            sb::C_GrDirectContext_precompileShader(self.native_mut(), key.native(), data.native())
+        }
+    }
+
+    /// Submits the work that was flushed, for example with
+    /// [crate::Surface::flush_with_mutable_state()], to the GPU. If `sync_cpu` is `true`, this
+    /// function blocks until the GPU has finished the work.
+    pub fn submit(&mut self, sync_cpu: bool) -> bool {
+        unsafe { self.native_mut().submit(sync_cpu) }
+    }
+
+    /// Inserts a list of GPU semaphores that the current GPU-backed API must wait on before
+    /// executing any more commands on the GPU for this context.
+    ///
+    /// If `delete_semaphores_after_wait` is `true`, Skia takes ownership of the semaphores and
+    /// deletes them once the wait is complete.
+    pub fn wait(
+        &mut self,
+        semaphores: &[BackendSemaphore],
+        delete_semaphores_after_wait: bool,
+    ) -> bool {
+        unsafe {
+            sb::C_GrDirectContext_wait(
+                self.native_mut(),
+                semaphores.len().try_into().unwrap(),
+                semaphores.native().as_ptr(),
+                delete_semaphores_after_wait,
+            )
+        }
+    }
+}
+
+#[test]
+fn test_mock_context() {
+    use crate::gpu::{BackendAPI, SurfaceOrigin};
+    use crate::{AlphaType, Budgeted, Color, ColorType, ImageInfo, Surface};
+
+    let mut context = DirectContext::new_mock(None).unwrap();
+    assert_eq!(context.backend(), BackendAPI::Mock);
+    assert!(context.max_texture_size() > 0);
+    assert!(context.capabilities().float_is_32_bits);
+
+    let image_info = ImageInfo::new((16, 16), ColorType::RGBA8888, AlphaType::Premul, None);
+    let mut surface = Surface::new_render_target(
+        &mut context.clone().into(),
+        Budgeted::Yes,
+        &image_info,
+        None,
+        SurfaceOrigin::TopLeft,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(!surface.can_send());
+    assert!(!context.can_send());
+    assert_eq!(
+        Surface::try_new_render_target(
+            &mut context.clone().into(),
+            Budgeted::Yes,
+            &image_info,
+            1024,
+            SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )
+        .err(),
+        Some(crate::SkiaError::InvalidParameters(
+            "sample count exceeds the maximum for the color type"
+        ))
+    );
+    surface.canvas().clear(Color::RED);
+    surface.flush_and_submit(false);
+    assert!(!context.abandoned());
+}
+
+#[test]
+fn test_resource_cache_usage() {
+    use crate::gpu::SurfaceOrigin;
+    use crate::{AlphaType, Budgeted, ColorType, ImageInfo, Surface};
+
+    let mut context = DirectContext::new_mock(None).unwrap();
+    context.set_resource_cache_limit(64 * 1024 * 1024);
+    assert_eq!(context.resource_cache_limit(), 64 * 1024 * 1024);
+
+    let image_info = ImageInfo::new((64, 32), ColorType::RGBA8888, AlphaType::Premul, None);
+    let mut surface = Surface::new_render_target(
+        &mut context.clone().into(),
+        Budgeted::Yes,
+        &image_info,
+        None,
+        SurfaceOrigin::TopLeft,
+        None,
+        None,
+    )
+    .unwrap();
+    let estimate = surface.estimate_gpu_memory().unwrap();
+    assert_eq!(estimate, 64 * 32 * 4);
+    surface.flush_and_submit(false);
+
+    let usage = context.resource_cache_usage();
+    assert!(usage.resource_count > 0);
+    assert!(usage.resource_bytes >= estimate);
+    assert_eq!(usage.limit, 64 * 1024 * 1024);
+
+    let image = surface.image_snapshot();
+    assert_eq!(image.estimate_gpu_memory(), Some(estimate));
+    assert_eq!(
+        crate::Image::from_raster_data(
+            &image_info,
+            crate::Data::new_copy(&[0u8; 64 * 32 * 4]),
+            64 * 4
+        )
+        .unwrap()
+        .estimate_gpu_memory(),
+        None
+    );
 }
@@ -1,3 +1,7 @@
+use crate::prelude::*;
+use skia_bindings as sb;
+use std::ffi::CStr;
+
 pub use skia_bindings::GrDriverBugWorkarounds as DriverBugWorkarounds;
 #[test]
 fn test_driver_bug_workarounds_naming() {
@@ -15,3 +19,71 @@ impl ApplyOverrides for DriverBugWorkarounds {
         unsafe { self.applyOverrides(workarounds) }
     }
 }
+
+/// Access to driver bug workarounds by the names that are used in Chrome's GPU driver bug list,
+/// for example `"max_texture_size_limit_4096"`.
+///
+/// ```rust,ignore
+/// let mut options = ContextOptions::new();
+/// options.driver_bug_workarounds =
+///     DriverBugWorkarounds::from_names(vec!["disable_blend_equation_advanced"]).unwrap();
+/// ```
+pub trait NamedWorkarounds: Sized {
+    /// The names of all workarounds.
+    fn names() -> Vec<&'static str>;
+
+    /// Creates workarounds with only the named ones enabled. Returns the names that are unknown,
+    /// if there are any.
+    fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self, Vec<String>>;
+
+    /// Enables or disables a workaround. Returns `false` if the name is unknown.
+    fn set_by_name(&mut self, name: &str, enabled: bool) -> bool;
+}
+
+impl NamedWorkarounds for DriverBugWorkarounds {
+    fn names() -> Vec<&'static str> {
+        let count = unsafe { sb::C_GrDriverBugWorkarounds_namesCount() };
+        (0..count)
+            .map(|i| {
+                unsafe { CStr::from_ptr(sb::C_GrDriverBugWorkarounds_name(i)) }
+                    .to_str()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self, Vec<String>> {
+        let mut workarounds: DriverBugWorkarounds =
+            construct(|w| unsafe { sb::C_GrDriverBugWorkarounds_Construct(w) });
+        let unknown: Vec<String> = names
+            .into_iter()
+            .filter(|name| !workarounds.set_by_name(name, true))
+            .map(|name| name.to_string())
+            .collect();
+        if unknown.is_empty() {
+            Ok(workarounds)
+        } else {
+            Err(unknown)
+        }
+    }
+
+    fn set_by_name(&mut self, name: &str, enabled: bool) -> bool {
+        unsafe { sb::C_GrDriverBugWorkarounds_set(self, name.as_ptr() as _, name.len(), enabled) }
+    }
+}
+
+#[test]
+fn test_named_workarounds() {
+    let names = DriverBugWorkarounds::names();
+    assert!(names.contains(&"max_texture_size_limit_4096"));
+
+    let workarounds =
+        DriverBugWorkarounds::from_names(vec!["max_texture_size_limit_4096"]).unwrap();
+    assert!(workarounds.max_texture_size_limit_4096);
+    assert!(!workarounds.max_fragment_uniform_vectors_32);
+
+    assert_eq!(
+        DriverBugWorkarounds::from_names(vec!["no_such_workaround"]).err(),
+        Some(vec!["no_such_workaround".to_string()])
+    );
+}
@@ -0,0 +1,108 @@
+use super::Mipmapped;
+use crate::{ColorType, ISize};
+
+/// Estimates the GPU memory of a surface or texture the way Skia accounts for it in the
+/// resource cache of a [super::DirectContext].
+///
+/// A `sample_count` greater than `1` adds a multisampled color buffer, and if the surface is
+/// `textureable`, the texture it is resolved to. Stencil buffers and backend specific padding
+/// are not included.
+///
+/// This can be used to decide whether an offscreen layer fits into
+/// [ResourceCacheUsage::headroom()] before it is created, and to downscale it otherwise.
+pub fn estimate_surface_memory(
+    dimensions: impl Into<ISize>,
+    color_type: ColorType,
+    sample_count: usize,
+    textureable: bool,
+    mipmapped: Mipmapped,
+) -> usize {
+    let dimensions = dimensions.into();
+    let color_size = dimensions.width.max(0) as usize
+        * dimensions.height.max(0) as usize
+        * color_type.bytes_per_pixel();
+    let samples_per_pixel = match sample_count {
+        0 | 1 => 1,
+        n if textureable => n + 1,
+        n => n,
+    };
+    let mut size = color_size * samples_per_pixel;
+    if mipmapped == Mipmapped::Yes {
+        size += color_size / 3;
+    }
+    size
+}
+
+/// The GPU memory that is held by the resource cache of a [super::DirectContext], returned by
+/// [super::DirectContext::resource_cache_usage()].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ResourceCacheUsage {
+    /// The number of budgeted resources in the cache.
+    pub resource_count: usize,
+    /// The bytes of the budgeted resources in the cache. Resources that are not budgeted, for
+    /// example wrapped backend textures, are not included.
+    pub resource_bytes: usize,
+    /// The bytes of the resources that are not in use and can be purged.
+    pub purgeable_bytes: usize,
+    /// The budget of the cache, see [super::DirectContext::set_resource_cache_limit()].
+    pub limit: usize,
+}
+
+impl ResourceCacheUsage {
+    /// The bytes that can be allocated before the budget is exceeded, assuming that purgeable
+    /// resources are freed.
+    pub fn headroom(&self) -> usize {
+        self.limit
+            .saturating_add(self.purgeable_bytes)
+            .saturating_sub(self.resource_bytes)
+    }
+
+    /// Returns `true` if `bytes` can be allocated without exceeding the budget.
+    pub fn fits(&self, bytes: usize) -> bool {
+        bytes <= self.headroom()
+    }
+}
+
+#[test]
+fn test_estimate_surface_memory() {
+    let size = 100 * 50 * 4;
+    assert_eq!(
+        estimate_surface_memory((100, 50), ColorType::RGBA8888, 1, true, Mipmapped::No),
+        size
+    );
+    assert_eq!(
+        estimate_surface_memory((100, 50), ColorType::RGBA8888, 0, true, Mipmapped::Yes),
+        size + size / 3
+    );
+    assert_eq!(
+        estimate_surface_memory((100, 50), ColorType::RGBA8888, 4, true, Mipmapped::No),
+        size * 5
+    );
+    assert_eq!(
+        estimate_surface_memory((100, 50), ColorType::RGBA8888, 4, false, Mipmapped::No),
+        size * 4
+    );
+    assert_eq!(
+        estimate_surface_memory((100, 50), ColorType::Alpha8, 1, true, Mipmapped::No),
+        100 * 50
+    );
+}
+
+#[test]
+fn test_resource_cache_headroom() {
+    let usage = ResourceCacheUsage {
+        resource_count: 3,
+        resource_bytes: 1000,
+        purgeable_bytes: 200,
+        limit: 1024,
+    };
+    assert_eq!(usage.headroom(), 224);
+    assert!(usage.fits(224));
+    assert!(!usage.fits(225));
+
+    let unlimited = ResourceCacheUsage {
+        limit: usize::MAX,
+        ..usage
+    };
+    assert_eq!(unlimited.headroom(), usize::MAX - 1000);
+}
@@ -1,5 +1,6 @@
 use crate::prelude;
 use crate::prelude::{NativeAccess, NativeDrop, NativePartialEq};
+use foreign_types::{ForeignType, ForeignTypeRef};
 use skia_bindings as sb;
 use skia_bindings::GrMtlTextureInfo;
 use std::{ffi, ptr};
@@ -7,6 +8,64 @@ use std::{ffi, ptr};
 pub use skia_bindings::GrMTLHandle as Handle;
 pub use skia_bindings::GrMTLPixelFormat as PixelFormat;
 
+macro_rules! typed_handle {
+    ($(#[$attr:meta])* $name:ident, $metal_type:ident, $metal_ref:ident) => {
+        $(#[$attr])*
+        ///
+        /// The handle does not own the Metal object, the caller is responsible for keeping it
+        /// alive as long as the handle is in use.
+        #[repr(transparent)]
+        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+        pub struct $name(Handle);
+
+        impl $name {
+            /// # Safety
+            ///
+            /// `handle` must point to a valid Metal object of the corresponding type.
+            pub unsafe fn from_raw(handle: Handle) -> Self {
+                Self(handle)
+            }
+
+            pub fn as_raw(&self) -> Handle {
+                self.0
+            }
+        }
+
+        impl From<&metal_rs::$metal_ref> for $name {
+            fn from(object: &metal_rs::$metal_ref) -> Self {
+                Self(object.as_ptr() as Handle)
+            }
+        }
+
+        impl From<&metal_rs::$metal_type> for $name {
+            fn from(object: &metal_rs::$metal_type) -> Self {
+                Self(object.as_ptr() as Handle)
+            }
+        }
+    };
+}
+
+typed_handle!(
+    /// A typed handle to a `MTLDevice`.
+    Device,
+    Device,
+    DeviceRef
+);
+
+typed_handle!(
+    /// A typed handle to a `MTLCommandQueue`.
+    CommandQueue,
+    CommandQueue,
+    CommandQueueRef
+);
+
+typed_handle!(
+    /// A typed handle to a `MTLTexture`.
+    Texture,
+    Texture,
+    TextureRef
+);
+
 pub type TextureInfo = prelude::Handle<GrMtlTextureInfo>;
 unsafe impl Send for TextureInfo {}
 unsafe impl Sync for TextureInfo {}
@@ -40,6 +99,14 @@ impl prelude::Handle<GrMtlTextureInfo> {
         Self::construct(|ti| sb::C_GrMtlTextureInfo_Construct(ti, texture))
     }
 
+    /// Creates a `TextureInfo` that retains the texture for as long as it exists.
+    pub fn from_texture(texture: impl Into<Texture>) -> Self {
+        let texture = texture.into();
+        Self::construct(|ti| unsafe {
+            sb::C_GrMtlTextureInfo_ConstructRetained(ti, texture.as_raw())
+        })
+    }
+
     pub fn texture(&self) -> *const ffi::c_void {
         self.native().fTexture.fObject
     }
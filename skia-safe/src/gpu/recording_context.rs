@@ -8,6 +8,10 @@ pub type RecordingContext = RCHandle<GrRecordingContext>;
 
 impl NativeRefCountedBase for GrRecordingContext {
     type Base = SkRefCntBase;
+
+    fn is_thread_affine(&self) -> bool {
+        true
+    }
 }
 
 impl From<RCHandle<GrDirectContext>> for RCHandle<GrRecordingContext> {
@@ -64,6 +68,13 @@ impl RecordingContext {
         unsafe { sb::C_GrRecordingContext_abandoned(self.native_mut()) }
     }
 
+    // From GrContext_Base, unique for each context.
+    pub(crate) fn context_id(&self) -> u32 {
+        unsafe { sb::C_GrRecordingContext_contextID(self.native()) }
+    }
+
+    /// Returns `true` if surfaces with the color type can be created, see
+    /// [Self::max_surface_sample_count_for_color_type()].
     pub fn color_type_supported_as_surface(&self, color_type: ColorType) -> bool {
         unsafe {
             sb::C_GrRecordingContext_colorTypeSupportedAsSurface(
@@ -73,14 +84,18 @@ impl RecordingContext {
         }
     }
 
+    /// The maximum width and height of textures, and therefore of images that are uploaded
+    /// without tiling. Use it to size atlases instead of assuming a limit.
     pub fn max_texture_size(&self) -> i32 {
         unsafe { self.native().maxTextureSize() }
     }
 
+    /// The maximum width and height of render targets, and therefore of GPU surfaces.
     pub fn max_render_target_size(&self) -> i32 {
         unsafe { self.native().maxRenderTargetSize() }
     }
 
+    /// Returns `true` if images with the color type can be created from textures.
     pub fn color_type_supported_as_image(&self, color_type: ColorType) -> bool {
         unsafe {
             self.native()
@@ -88,6 +103,8 @@ impl RecordingContext {
         }
     }
 
+    /// The maximum number of MSAA samples of surfaces with the color type. `1` means that
+    /// the color type is renderable, but without MSAA, and `0` that it is not renderable at all.
     pub fn max_surface_sample_count_for_color_type(&self, color_type: ColorType) -> usize {
         unsafe {
             self.native()
@@ -96,4 +113,46 @@ impl RecordingContext {
         .try_into()
         .unwrap()
     }
+
+    /// Returns the capabilities of the backend's shading language, so that runtime effects can
+    /// choose a shader variant that is supported.
+    ///
+    /// Skia m87 does not report an SkSL version, so features like integer arithmetic or
+    /// derivatives have to be checked individually.
+    pub fn capabilities(&self) -> Capabilities {
+        let mut caps = sb::GrShaderCapabilities {
+            shaderDerivativeSupport: false,
+            integerSupport: false,
+            floatIs32Bits: false,
+            halfIs32Bits: false,
+            flatInterpolationSupport: false,
+            dualSourceBlendingSupport: false,
+        };
+        unsafe { sb::C_GrRecordingContext_shaderCapabilities(self.native_mut_force(), &mut caps) };
+        Capabilities {
+            shader_derivative_support: caps.shaderDerivativeSupport,
+            integer_support: caps.integerSupport,
+            float_is_32_bits: caps.floatIs32Bits,
+            half_is_32_bits: caps.halfIs32Bits,
+            flat_interpolation_support: caps.flatInterpolationSupport,
+            dual_source_blending_support: caps.dualSourceBlendingSupport,
+        }
+    }
+}
+
+/// The shading language capabilities of a GPU backend, see [RecordingContext::capabilities()].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Capabilities {
+    /// `dFdx()`, `dFdy()` and `fwidth()` are available.
+    pub shader_derivative_support: bool,
+    /// Integer types and bitwise operations are available.
+    pub integer_support: bool,
+    /// `float` has at least 32 bits of precision.
+    pub float_is_32_bits: bool,
+    /// `half` has 32 bits of precision, too.
+    pub half_is_32_bits: bool,
+    /// Varyings can be declared `flat`.
+    pub flat_interpolation_support: bool,
+    /// The fragment shader can output a second color that is used by the blend equation.
+    pub dual_source_blending_support: bool,
 }
@@ -0,0 +1,231 @@
+use super::{RecordingContext, SurfaceOrigin};
+use crate::{AlphaType, Budgeted, ColorType, ISize, ImageInfo, Surface};
+
+/// The properties that pooled surfaces must match to be reused.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SurfacePoolKey {
+    pub dimensions: ISize,
+    pub color_type: ColorType,
+    pub sample_count: usize,
+}
+
+impl SurfacePoolKey {
+    pub fn new(dimensions: impl Into<ISize>, color_type: ColorType, sample_count: usize) -> Self {
+        SurfacePoolKey {
+            dimensions: dimensions.into(),
+            color_type,
+            sample_count,
+        }
+    }
+}
+
+/// A surface that was acquired from a [SurfacePool]. Return it with [SurfacePool::release()] to
+/// make it available for reuse.
+pub struct PooledSurface {
+    key: SurfacePoolKey,
+    context_id: u32,
+    surface: Surface,
+}
+
+impl PooledSurface {
+    pub fn key(&self) -> &SurfacePoolKey {
+        &self.key
+    }
+
+    pub fn surface(&mut self) -> &mut Surface {
+        &mut self.surface
+    }
+
+    /// Detaches the surface from the pool.
+    pub fn into_surface(self) -> Surface {
+        self.surface
+    }
+}
+
+/// Reuses GPU surfaces across frames, for example for offscreen layers.
+///
+/// Surfaces that are not in use are kept up to `capacity`, the least recently released ones are
+/// dropped first. Call [Self::next_frame()] once per frame to drop surfaces that were not used
+/// for a number of frames.
+///
+/// Reused surfaces keep their previous content and matrix, so they should be cleared before
+/// drawing.
+///
+/// A pool is bound to the context its first surface is acquired with.
+pub struct SurfacePool {
+    available: Vec<Entry>,
+    context_id: Option<u32>,
+    capacity: usize,
+    max_unused_frames: u64,
+    frame: u64,
+}
+
+struct Entry {
+    key: SurfacePoolKey,
+    surface: Surface,
+    released_in_frame: u64,
+}
+
+impl SurfacePool {
+    /// Creates a pool that keeps up to `capacity` unused surfaces for up to `max_unused_frames`
+    /// frames.
+    pub fn new(capacity: usize, max_unused_frames: u64) -> Self {
+        SurfacePool {
+            available: Vec::new(),
+            context_id: None,
+            capacity,
+            max_unused_frames,
+            frame: 0,
+        }
+    }
+
+    /// The number of unused surfaces in the pool.
+    pub fn len(&self) -> usize {
+        self.available.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.available.is_empty()
+    }
+
+    /// Returns a pooled surface that matches `key`, or creates a new budgeted, premultiplied
+    /// render target surface. Returns [None] if the surface can not be created, or if `context`
+    /// is not the context the pool is bound to.
+    pub fn acquire(
+        &mut self,
+        context: &mut RecordingContext,
+        key: SurfacePoolKey,
+    ) -> Option<PooledSurface> {
+        let context_id = context.context_id();
+        if *self.context_id.get_or_insert(context_id) != context_id {
+            return None;
+        }
+
+        // prefer the most recently released surface, its resources are most likely still warm.
+        if let Some(i) = self.available.iter().rposition(|entry| entry.key == key) {
+            let entry = self.available.remove(i);
+            return Some(PooledSurface {
+                key,
+                context_id,
+                surface: entry.surface,
+            });
+        }
+
+        let image_info = ImageInfo::new(key.dimensions, key.color_type, AlphaType::Premul, None);
+        let surface = Surface::new_render_target(
+            context,
+            Budgeted::Yes,
+            &image_info,
+            key.sample_count,
+            SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )?;
+        Some(PooledSurface {
+            key,
+            context_id,
+            surface,
+        })
+    }
+
+    /// Returns a surface to the pool, so that it can be reused by [Self::acquire()].
+    ///
+    /// Surfaces that were acquired from a pool that is bound to a different context are dropped.
+    pub fn release(&mut self, surface: PooledSurface) {
+        if self.capacity == 0 || self.context_id != Some(surface.context_id) {
+            return;
+        }
+        if self.available.len() == self.capacity {
+            self.available.remove(0);
+        }
+        self.available.push(Entry {
+            key: surface.key,
+            surface: surface.surface,
+            released_in_frame: self.frame,
+        });
+    }
+
+    /// Advances the frame counter and drops the surfaces that were not used for more than
+    /// `max_unused_frames`.
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+        let (frame, max_unused_frames) = (self.frame, self.max_unused_frames);
+        self.available
+            .retain(|entry| frame - entry.released_in_frame <= max_unused_frames);
+    }
+
+    /// Drops all unused surfaces.
+    pub fn purge(&mut self) {
+        self.available.clear();
+    }
+
+    /// Responds to memory pressure by dropping all unused surfaces and then freeing all
+    /// unlocked GPU resources of `context`.
+    ///
+    /// Only direct contexts own GPU resources, for other recording contexts only the surfaces
+    /// are dropped.
+    pub fn purge_and_free_gpu_resources(&mut self, context: &mut RecordingContext) {
+        self.purge();
+        if let Some(mut direct_context) = context.as_direct_context() {
+            direct_context.free_gpu_resources();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SurfacePool, SurfacePoolKey};
+    use crate::gpu::{DirectContext, RecordingContext};
+    use crate::ColorType;
+
+    #[test]
+    fn reuses_released_surfaces() {
+        let mut context: RecordingContext = DirectContext::new_mock(None).unwrap().into();
+        let mut pool = SurfacePool::new(2, 1);
+        let key = SurfacePoolKey::new((16, 16), ColorType::RGBA8888, 1);
+
+        let surface = pool.acquire(&mut context, key).unwrap();
+        assert_eq!(surface.key(), &key);
+        pool.release(surface);
+        assert_eq!(pool.len(), 1);
+
+        let surface = pool.acquire(&mut context, key).unwrap();
+        assert!(pool.is_empty());
+        pool.release(surface);
+
+        let other_key = SurfacePoolKey::new((32, 16), ColorType::RGBA8888, 1);
+        let other = pool.acquire(&mut context, other_key).unwrap();
+        assert_eq!(other.key(), &other_key);
+        assert_eq!(pool.len(), 1);
+        pool.release(other);
+        assert_eq!(pool.len(), 2);
+
+        pool.next_frame();
+        assert_eq!(pool.len(), 2);
+        pool.next_frame();
+        assert!(pool.is_empty());
+
+        let surface = pool.acquire(&mut context, key).unwrap();
+        pool.release(surface);
+        pool.purge_and_free_gpu_resources(&mut context);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn is_bound_to_a_context() {
+        let mut context: RecordingContext = DirectContext::new_mock(None).unwrap().into();
+        let mut other_context: RecordingContext = DirectContext::new_mock(None).unwrap().into();
+        let mut pool = SurfacePool::new(2, 1);
+        let mut other_pool = SurfacePool::new(2, 1);
+        let key = SurfacePoolKey::new((16, 16), ColorType::RGBA8888, 1);
+
+        let surface = pool.acquire(&mut context, key).unwrap();
+        assert!(pool.acquire(&mut other_context, key).is_none());
+
+        let other_surface = other_pool.acquire(&mut other_context, key).unwrap();
+        pool.release(other_surface);
+        assert!(pool.is_empty());
+        pool.release(surface);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -38,6 +38,7 @@ pub use sb::VkRect2D as Rect2D;
 pub use sb::VkRenderPass as RenderPass;
 pub use sb::VkSamplerYcbcrModelConversion as SamplerYcbcrModelConversion;
 pub use sb::VkSamplerYcbcrRange as SamplerYcbcrRange;
+pub use sb::VkSemaphore as Semaphore;
 pub use sb::VkSharingMode as SharingMode;
 
 pub const QUEUE_FAMILY_IGNORED: u32 = !0;
@@ -107,6 +108,13 @@ impl From<NullHandle> for RenderPass {
     }
 }
 
+#[cfg(target_pointer_width = "64")]
+impl From<NullHandle> for Semaphore {
+    fn from(_: NullHandle) -> Self {
+        ptr::null_mut()
+    }
+}
+
 #[cfg(not(target_pointer_width = "64"))]
 impl From<NullHandle> for u64 {
     fn from(_: NullHandle) -> Self {
@@ -18,16 +18,31 @@ pub mod effects;
 pub mod gpu;
 
 mod interop;
+#[cfg(all(feature = "log", any(target_os = "linux", target_os = "macos")))]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "log")))]
+pub mod logging;
 mod modules;
 mod pathops;
 mod prelude;
+#[cfg(feature = "serde")]
+mod serde_support;
 pub mod wrapper;
 // The module private may contain types that leak.
 pub mod private;
+#[cfg(all(feature = "softbuffer", target_endian = "little"))]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "softbuffer")))]
+pub mod softbuffer;
+#[cfg(feature = "serde")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "serde")))]
+pub mod style_sheet;
 pub mod svg;
 // TODO: We don't export utils/* into the crate's root yet. Should we?
 pub mod utils;
 
+#[cfg(feature = "testing")]
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "testing")))]
+pub mod testing;
+
 #[macro_use]
 extern crate bitflags;
 #[macro_use]
@@ -0,0 +1,35 @@
+//! Forwarding of Skia's diagnostic messages to the [log](https://crates.io/crates/log) crate.
+//!
+//! Skia reports problems like shaders that failed to compile or images that could not be decoded
+//! by printing them to `stderr`. After [forward_to_log()] is called, they are logged as warnings
+//! with the target [TARGET] instead.
+//!
+//! This module is only available on Linux and macOS. On Android, Skia's messages keep going to
+//! logcat, and on Windows to the debugger output.
+
+use skia_bindings as sb;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic;
+
+/// The target of the log records of Skia's messages.
+pub const TARGET: &str = "skia";
+
+/// Logs all messages Skia prints from now on as warnings with the target [TARGET].
+pub fn forward_to_log() {
+    unsafe { sb::C_SkDebugf_setHandler(Some(log_message)) }
+}
+
+/// Lets Skia print its messages to `stderr` again, which is the default.
+pub fn forward_to_stderr() {
+    unsafe { sb::C_SkDebugf_setHandler(None) }
+}
+
+unsafe extern "C" fn log_message(message: *const c_char) {
+    let message = CStr::from_ptr(message).to_string_lossy();
+    let message = message.trim_end();
+    if !message.is_empty() {
+        // A panicking logger must not unwind into Skia, the message is dropped instead.
+        let _ = panic::catch_unwind(|| log_rs::warn!(target: TARGET, "{}", message));
+    }
+}
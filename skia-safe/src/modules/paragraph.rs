@@ -23,6 +23,9 @@ pub use paragraph_builder::*;
 mod paragraph_cache;
 pub use paragraph_cache::*;
 
+mod paragraph_layout_cache;
+pub use paragraph_layout_cache::*;
+
 mod paragraph_style;
 pub use paragraph_style::*;
 
@@ -88,6 +88,9 @@ fn test_text_baseline_naming() {
     let _ = TextBaseline::Alphabetic;
 }
 
+/// Defines whether the height of the text style is applied to the ascent of the first line and
+/// the descent of the last line of a paragraph, which is how Flutter trims the leading before
+/// the first and after the last line.
 pub use sb::skia_textlayout_TextHeightBehavior as TextHeightBehavior;
 #[test]
 fn test_text_height_behavior_naming() {
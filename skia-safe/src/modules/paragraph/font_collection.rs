@@ -21,6 +21,10 @@ impl FontCollection {
         unsafe { self.native().getFontManagersCount() }
     }
 
+    /// The font managers are searched in the order dynamic, asset, test and default. Fonts for
+    /// characters that are missing in the font families of the text style are chosen by the font
+    /// managers' character fallback, which depends on the platform. To prefer specific fonts,
+    /// add them to the text style, see [super::TextStyle::add_fallback_font_families()].
     pub fn set_asset_font_manager(&mut self, font_manager: impl Into<Option<FontMgr>>) {
         unsafe {
             sb::C_FontCollection_setAssetFontManager(
@@ -68,6 +68,11 @@ fn test_line_metrics_layout() {
 impl<'a> LineMetrics<'a> {
     // TODO: may support constructors (but what about the lifetime bounds?).
 
+    /// Copies the metrics of the line except the style metrics, which refer to the paragraph.
+    pub fn to_owned_metrics(&self) -> OwnedLineMetrics {
+        self.into()
+    }
+
     /// Returns the number of style metrics in the given index range.
     pub fn get_style_metrics_count(&self, range: Range<usize>) -> usize {
         unsafe { sb::C_LineMetrics_fLineMetrics_count(self.native(), range.start, range.end) }
@@ -93,6 +98,45 @@ impl<'a> LineMetrics<'a> {
     }
 }
 
+/// A copy of the metrics of a line without its style metrics, so that it does not borrow the
+/// paragraph, see [LineMetrics::to_owned_metrics()].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OwnedLineMetrics {
+    pub start_index: usize,
+    pub end_index: usize,
+    pub end_excluding_whitespaces: usize,
+    pub end_including_newline: usize,
+    pub hard_break: bool,
+    pub ascent: f64,
+    pub descent: f64,
+    pub unscaled_ascent: f64,
+    pub height: f64,
+    pub width: f64,
+    pub left: f64,
+    pub baseline: f64,
+    pub line_number: usize,
+}
+
+impl From<&LineMetrics<'_>> for OwnedLineMetrics {
+    fn from(lm: &LineMetrics) -> Self {
+        OwnedLineMetrics {
+            start_index: lm.start_index,
+            end_index: lm.end_index,
+            end_excluding_whitespaces: lm.end_excluding_whitespaces,
+            end_including_newline: lm.end_including_newline,
+            hard_break: lm.hard_break,
+            ascent: lm.ascent,
+            descent: lm.descent,
+            unscaled_ascent: lm.unscaled_ascent,
+            height: lm.height,
+            width: lm.width,
+            left: lm.left,
+            baseline: lm.baseline,
+            line_number: lm.line_number,
+        }
+    }
+}
+
 type StyleMetricsRecord<'a> = (usize, &'a StyleMetrics<'a>);
 
 #[test]
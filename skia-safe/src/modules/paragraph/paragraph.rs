@@ -1,11 +1,13 @@
 #![deny(missing_docs)]
 
-use super::{PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox};
+use super::{PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox, TextDirection};
 use crate::prelude::*;
-use crate::textlayout::LineMetrics;
-use crate::{scalar, Canvas, Point};
+use crate::shaper::RunIterator;
+use crate::textlayout::{LineMetrics, OwnedLineMetrics};
+use crate::{scalar, Canvas, Point, Shaper};
 use skia_bindings as sb;
 use std::ops::{Index, Range};
+use std::{cmp, ptr, slice, str};
 
 /// A multiline text block. This must be created from a [super::ParagraphBuilder].
 pub type Paragraph = RefHandle<sb::skia_textlayout_Paragraph>;
@@ -149,6 +151,132 @@ impl Paragraph {
         unsafe { sb::C_Paragraph_lineNumber(self.native_mut_force()) }
     }
 
+    /// Returns the index of the line that contains the UTF-8 byte offset `text_index`, or `None`
+    /// if the offset is after the end of the text.
+    ///
+    /// A line contains the text from its [LineMetrics::start_index] up to its
+    /// [LineMetrics::end_including_newline]. The offset at the end of the text, where a caret can
+    /// be placed after the last character, belongs to the last line.
+    ///
+    /// The metrics of all lines are copied on each call, so use [Self::get_line_metrics()] to
+    /// look up many offsets.
+    pub fn get_line_number_at(&self, text_index: usize) -> Option<usize> {
+        let line_metrics = self.get_line_metrics();
+        let line_metrics = line_metrics.as_slice();
+        let line = line_metrics.binary_search_by(|lm| {
+            if text_index < lm.start_index {
+                cmp::Ordering::Greater
+            } else if text_index >= lm.end_including_newline {
+                cmp::Ordering::Less
+            } else {
+                cmp::Ordering::Equal
+            }
+        });
+        match line {
+            Ok(line) => Some(line),
+            Err(_) if text_index == self.text_and_direction().0.len() => {
+                line_metrics.len().checked_sub(1)
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Returns a copy of the metrics of the line with the index `line`, or `None` if the
+    /// paragraph has fewer lines.
+    pub fn get_line_metrics_at(&self, line: usize) -> Option<OwnedLineMetrics> {
+        self.get_line_metrics()
+            .as_slice()
+            .get(line)
+            .map(OwnedLineMetrics::from)
+    }
+
+    /// Returns the UTF-8 byte range of the text of the line with the index `line`, or `None` if
+    /// the paragraph has fewer lines.
+    ///
+    /// If `include_whitespace` is `true`, the range includes the trailing whitespace and the
+    /// line break, otherwise it ends after the last visible character.
+    pub fn get_actual_text_range(
+        &self,
+        line: usize,
+        include_whitespace: bool,
+    ) -> Option<Range<usize>> {
+        self.get_line_metrics_at(line).map(|lm| {
+            let end = if include_whitespace {
+                lm.end_including_newline
+            } else {
+                lm.end_excluding_whitespaces
+            };
+            lm.start_index..end
+        })
+    }
+
+    /// Returns the metrics the paragraph would have at its current width without the limit of
+    /// [super::ParagraphStyle::max_lines()], for example to decide if a "read more" expander
+    /// should be shown and how far it would expand.
+    ///
+    /// If the text did not exceed the maximum lines, the metrics of this paragraph are returned.
+    /// Otherwise, a copy of the paragraph is laid out without the limit.
+    pub fn unlimited_metrics(&self) -> UnlimitedMetrics {
+        if !self.did_exceed_max_lines() {
+            return UnlimitedMetrics {
+                height: self.height(),
+                line_number: self.line_number(),
+            };
+        }
+        let mut metrics = UnlimitedMetrics {
+            height: 0.0,
+            line_number: 0,
+        };
+        unsafe {
+            sb::C_Paragraph_unlimitedMetrics(
+                self.native_mut_force(),
+                &mut metrics.height,
+                &mut metrics.line_number,
+            )
+        }
+        metrics
+    }
+
+    /// Returns the runs of the paragraph's text that have the same bidirectional embedding
+    /// level, in logical order. The ranges are byte offsets into the UTF-8 text that was added to
+    /// the builder, with placeholders encoded as `U+FFFC`.
+    ///
+    /// The levels are resolved with the Unicode Bidirectional Algorithm, starting with the text
+    /// direction of the paragraph style, the same way the paragraph's text is shaped.
+    pub fn bidi_regions(&self) -> Vec<BidiRegion> {
+        let (text, direction) = self.text_and_direction();
+        let base_level = match direction {
+            TextDirection::LTR => 0,
+            TextDirection::RTL => 1,
+        };
+        let mut regions = Vec::new();
+        if let Some(mut iter) = Shaper::new_bidi_run_iterator(text, base_level) {
+            let mut start = 0;
+            while !iter.at_end() {
+                iter.consume();
+                let end = iter.end_of_current_run();
+                regions.push(BidiRegion {
+                    range: start..end,
+                    level: iter.current_level(),
+                });
+                start = end;
+            }
+        }
+        regions
+    }
+
+    fn text_and_direction(&self) -> (&str, TextDirection) {
+        let mut text = ptr::null();
+        let mut len = 0;
+        let mut direction = TextDirection::LTR;
+        unsafe {
+            sb::C_Paragraph_getText(self.native(), &mut text, &mut len, &mut direction);
+            // the text of a paragraph is always built from `str`s.
+            let bytes = slice::from_raw_parts(text as *const u8, len);
+            (str::from_utf8_unchecked(bytes), direction)
+        }
+    }
+
     /// Manually mark this paragraph as needing to have internal values recalculated. This should usually
     /// never need to be called by a consumer of this library.
     pub fn mark_dirty(&self) {
@@ -156,6 +284,36 @@ impl Paragraph {
     }
 }
 
+/// The height and number of lines of a [Paragraph] that is not limited by its maximum number of
+/// lines, see [Paragraph::unlimited_metrics()].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct UnlimitedMetrics {
+    /// The height the text needs.
+    pub height: scalar,
+    /// The number of lines the text needs.
+    pub line_number: usize,
+}
+
+/// A range of text with the same bidirectional embedding level, see [Paragraph::bidi_regions()].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BidiRegion {
+    /// The range of the region in the UTF-8 text.
+    pub range: Range<usize>,
+    /// The resolved embedding level, even levels are left-to-right, odd levels right-to-left.
+    pub level: u8,
+}
+
+impl BidiRegion {
+    /// The direction of the text in this region, derived from [Self::level].
+    pub fn direction(&self) -> TextDirection {
+        if self.level % 2 == 0 {
+            TextDirection::LTR
+        } else {
+            TextDirection::RTL
+        }
+    }
+}
+
 /// An array of bounding boxes returned by [Paragraph]. See [TextBox] for more information.
 pub type TextBoxes = Handle<sb::TextBoxes>;
 
@@ -230,6 +388,45 @@ impl<'a> Borrows<'a, Handle<sb::LineMetricsVector>> {
             std::slice::from_raw_parts(ptr as *const LineMetrics, count)
         }
     }
+
+    /// Copies the metrics of all lines, so that they can be kept independently of the
+    /// paragraph. See [LineMetrics::to_owned_metrics()].
+    pub fn to_owned_metrics(&self) -> Vec<OwnedLineMetrics> {
+        self.iter().map(OwnedLineMetrics::from).collect()
+    }
+}
+
+/// Creates a [ParagraphBuilder] that uses the default font manager and has `text_style` pushed.
+#[cfg(test)]
+pub(crate) fn test_paragraph_builder(
+    paragraph_style: &super::ParagraphStyle,
+    text_style: &super::TextStyle,
+) -> super::ParagraphBuilder {
+    use super::{FontCollection, ParagraphBuilder};
+    use crate::{icu, FontMgr};
+
+    icu::init();
+
+    let mut font_collection = FontCollection::new();
+    font_collection.set_default_font_manager(FontMgr::new(), None);
+    let mut paragraph_builder = ParagraphBuilder::new(paragraph_style, font_collection);
+    paragraph_builder.push_style(text_style);
+    paragraph_builder
+}
+
+/// Builds a [Paragraph] from `text` and lays it out with `width`.
+#[cfg(test)]
+pub(crate) fn layout_test_paragraph(
+    text: &str,
+    paragraph_style: &super::ParagraphStyle,
+    text_style: &super::TextStyle,
+    width: scalar,
+) -> Paragraph {
+    let mut paragraph_builder = test_paragraph_builder(paragraph_style, text_style);
+    paragraph_builder.add_text(text);
+    let mut paragraph = paragraph_builder.build();
+    paragraph.layout(width);
+    paragraph
 }
 
 #[test]
@@ -259,3 +456,108 @@ fn test_line_metrics() {
 
     static LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Curabitur at leo at nulla tincidunt placerat. Proin eget purus augue. Quisque et est ullamcorper, pellentesque felis nec, pulvinar massa. Aliquam imperdiet, nulla ut dictum euismod, purus dui pulvinar risus, eu suscipit elit neque ac est. Nullam eleifend justo quis placerat ultricies. Vestibulum ut elementum velit. Praesent et dolor sit amet purus bibendum mattis. Aliquam erat volutpat.";
 }
+
+#[test]
+#[serial_test::serial]
+fn test_owned_line_metrics() {
+    use crate::textlayout::{ParagraphStyle, TextStyle};
+
+    let mut paragraph = layout_test_paragraph(
+        "one two three four five six seven eight nine ten",
+        &ParagraphStyle::new(),
+        &TextStyle::new(),
+        64.0,
+    );
+
+    let owned = {
+        let line_metrics = paragraph.get_line_metrics();
+        let owned = line_metrics.to_owned_metrics();
+        assert_eq!(owned.len(), line_metrics.iter().count());
+        for (lm, owned) in line_metrics.iter().zip(&owned) {
+            assert_eq!(lm.to_owned_metrics(), *owned);
+        }
+        owned
+    };
+    // the owned metrics do not borrow the paragraph.
+    paragraph.layout(1000.0);
+    assert!(owned.len() > paragraph.line_number());
+    assert!(owned.iter().all(|lm| lm.width <= 64.0));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_line_number_at_and_text_range() {
+    use crate::textlayout::{ParagraphStyle, TextStyle};
+
+    let paragraph = layout_test_paragraph(
+        "one  \ntwo",
+        &ParagraphStyle::new(),
+        &TextStyle::new(),
+        1000.0,
+    );
+
+    assert_eq!(paragraph.line_number(), 2);
+    assert_eq!(paragraph.get_line_number_at(0), Some(0));
+    assert_eq!(paragraph.get_line_number_at(5), Some(0));
+    assert_eq!(paragraph.get_line_number_at(6), Some(1));
+    assert_eq!(paragraph.get_line_number_at(9), Some(1));
+    assert_eq!(paragraph.get_line_number_at(10), None);
+    assert_eq!(paragraph.get_line_number_at(100), None);
+
+    assert_eq!(paragraph.get_actual_text_range(0, false), Some(0..3));
+    assert_eq!(paragraph.get_actual_text_range(0, true), Some(0..6));
+    assert_eq!(paragraph.get_actual_text_range(1, false), Some(6..9));
+    assert_eq!(paragraph.get_actual_text_range(2, true), None);
+    assert_eq!(paragraph.get_line_metrics_at(1).unwrap().line_number, 1);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_unlimited_metrics() {
+    use crate::textlayout::{ParagraphStyle, TextStyle};
+
+    let build = |max_lines: Option<usize>| {
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_max_lines(max_lines);
+        layout_test_paragraph(
+            "one two three four five six seven eight nine ten",
+            &paragraph_style,
+            &TextStyle::new(),
+            64.0,
+        )
+    };
+
+    let unlimited = build(None);
+    assert!(unlimited.line_number() > 2);
+    let expected = UnlimitedMetrics {
+        height: unlimited.height(),
+        line_number: unlimited.line_number(),
+    };
+    assert_eq!(unlimited.unlimited_metrics(), expected);
+
+    let truncated = build(Some(2));
+    assert!(truncated.did_exceed_max_lines());
+    assert_eq!(truncated.line_number(), 2);
+    assert_eq!(truncated.unlimited_metrics(), expected);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_bidi_regions() {
+    use crate::textlayout::{ParagraphStyle, TextStyle};
+
+    let (ltr, rtl) = ("abc ", "العربية");
+    let paragraph = layout_test_paragraph(
+        &[ltr, rtl].concat(),
+        &ParagraphStyle::new(),
+        &TextStyle::new(),
+        1000.0,
+    );
+
+    let regions = paragraph.bidi_regions();
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].range, 0..ltr.len());
+    assert_eq!(regions[0].direction(), TextDirection::LTR);
+    assert_eq!(regions[1].range, ltr.len()..ltr.len() + rtl.len());
+    assert_eq!(regions[1].direction(), TextDirection::RTL);
+}
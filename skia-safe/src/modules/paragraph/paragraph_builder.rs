@@ -1,4 +1,6 @@
-use super::{FontCollection, Paragraph, ParagraphStyle, PlaceholderStyle, TextStyle};
+use super::{
+    FontCollection, Paragraph, ParagraphStyle, PlaceholderStyle, TextDirection, TextStyle,
+};
 use crate::prelude::*;
 use skia_bindings as sb;
 use std::os::raw;
@@ -19,11 +21,30 @@ impl ParagraphBuilder {
         self
     }
 
+    /// Removes the style on top of the style stack. Popping an empty stack is ignored.
     pub fn pop(&mut self) -> &mut Self {
         unsafe { sb::C_ParagraphBuilder_pop(self.native_mut()) }
         self
     }
 
+    /// Pops styles until the style stack has at most `depth` entries, for example to recover from
+    /// unbalanced markup by restoring a depth that was recorded with [Self::style_stack_depth()].
+    pub fn pop_to(&mut self, depth: usize) -> &mut Self {
+        for _ in depth..self.style_stack_depth() {
+            self.pop();
+        }
+        self
+    }
+
+    /// The number of styles on the style stack. The text style of the paragraph style is pushed
+    /// when the builder is created and by [Self::set_paragraph_style()], so the stack of a new
+    /// builder has a depth of `1`.
+    pub fn style_stack_depth(&self) -> usize {
+        unsafe { sb::C_ParagraphBuilder_styleStackDepth(self.native()) }
+    }
+
+    /// Returns the style on top of the style stack, or the text style of the paragraph style if
+    /// the stack is empty. Added text is styled with this style.
     pub fn peek_style(&mut self) -> TextStyle {
         let mut ts = TextStyle::default();
         unsafe { sb::C_ParagraphBuilder_peekStyle(self.native_mut(), ts.native_mut()) }
@@ -42,6 +63,23 @@ impl ParagraphBuilder {
         self
     }
 
+    /// Adds text that is laid out in `direction`, regardless of the directions of its
+    /// characters.
+    ///
+    /// The text is enclosed in the Unicode bidirectional override characters `U+202D` (LRO) or
+    /// `U+202E` (RLO), and `U+202C` (PDF), which are added to the paragraph's text, too.
+    pub fn add_text_with_direction_override(
+        &mut self,
+        str: impl AsRef<str>,
+        direction: TextDirection,
+    ) -> &mut Self {
+        let override_char = match direction {
+            TextDirection::LTR => '\u{202D}',
+            TextDirection::RTL => '\u{202E}',
+        };
+        self.add_text(format!("{}{}\u{202C}", override_char, str.as_ref()))
+    }
+
     pub fn add_placeholder(&mut self, placeholder_style: &PlaceholderStyle) -> &mut Self {
         unsafe {
             sb::C_ParagraphBuilder_addPlaceholder(self.native_mut(), placeholder_style.native())
@@ -65,3 +103,51 @@ impl ParagraphBuilder {
         .unwrap()
     }
 }
+
+#[test]
+fn test_style_stack() {
+    let mut builder = ParagraphBuilder::new(&ParagraphStyle::default(), FontCollection::new());
+    let base = builder.style_stack_depth();
+    assert_eq!(base, 1);
+
+    let mut style = TextStyle::new();
+    style.set_font_size(42.0);
+    builder.push_style(&style).push_style(&TextStyle::new());
+    assert_eq!(builder.style_stack_depth(), base + 2);
+
+    builder.pop();
+    assert_eq!(builder.peek_style().font_size(), 42.0);
+
+    builder.pop_to(base);
+    assert_eq!(builder.style_stack_depth(), base);
+    // popping below the requested depth is a no-op.
+    builder.pop_to(base + 1);
+    assert_eq!(builder.style_stack_depth(), base);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_direction_override() {
+    let mut paragraph_style = ParagraphStyle::new();
+    paragraph_style.set_text_direction(TextDirection::RTL);
+    assert_eq!(paragraph_style.text_direction(), TextDirection::RTL);
+
+    let build = |direction_override: Option<TextDirection>| {
+        let mut paragraph_builder =
+            super::paragraph::test_paragraph_builder(&paragraph_style, &TextStyle::new());
+        match direction_override {
+            Some(direction) => paragraph_builder.add_text_with_direction_override("abc", direction),
+            None => paragraph_builder.add_text("abc"),
+        };
+        paragraph_builder.build()
+    };
+
+    let regions = build(None).bidi_regions();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].level, 2);
+
+    let regions = build(Some(TextDirection::RTL)).bidi_regions();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].range, 0.."\u{202E}abc\u{202C}".len());
+    assert_eq!(regions[0].direction(), TextDirection::RTL);
+}
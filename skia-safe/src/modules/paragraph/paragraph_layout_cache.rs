@@ -0,0 +1,196 @@
+use crate::scalar;
+use crate::textlayout::Paragraph;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+/// Caches laid out [Paragraph]s by their text, a key that identifies their styles, and the
+/// width they were laid out with.
+///
+/// Skia's own [crate::textlayout::ParagraphCache] only caches the shaping results of the
+/// paragraphs of a [crate::textlayout::FontCollection], and every [Paragraph] still needs to be
+/// built and laid out. This cache returns the same [Paragraph] for identical content and
+/// constraints, which avoids both, for example when list items are laid out in every frame.
+///
+/// The least recently used paragraphs are removed when the estimated memory used by the cached
+/// paragraphs exceeds the memory budget. The memory is estimated from the length of the texts,
+/// because Skia does not report the memory a [Paragraph] uses.
+///
+/// Paragraphs are identified by a 64 bit hash of the text and the style key, so that the texts
+/// don't need to be stored, which makes the (unlikely) collisions of different content possible.
+pub struct ParagraphLayoutCache {
+    entries: HashMap<LayoutKey, Entry>,
+    by_last_use: BTreeMap<u64, LayoutKey>,
+    use_counter: u64,
+    memory_budget: usize,
+    memory_used: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct LayoutKey {
+    content: u64,
+    width: u32,
+}
+
+struct Entry {
+    paragraph: Paragraph,
+    memory: usize,
+    last_use: u64,
+}
+
+impl ParagraphLayoutCache {
+    /// The estimated memory a [Paragraph] uses, in addition to the memory per byte of text.
+    pub const ESTIMATED_PARAGRAPH_MEMORY: usize = 1024;
+    /// The estimated memory a [Paragraph] uses per byte of its text, which includes the glyphs,
+    /// their positions, and the clusters.
+    pub const ESTIMATED_MEMORY_PER_TEXT_BYTE: usize = 64;
+
+    /// Creates a cache that keeps paragraphs until their estimated memory exceeds
+    /// `memory_budget` bytes.
+    pub fn new(memory_budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            by_last_use: BTreeMap::new(),
+            use_counter: 0,
+            memory_budget,
+            memory_used: 0,
+        }
+    }
+
+    /// Returns the paragraph for `text` and `style_key` that is laid out with `width`.
+    ///
+    /// If it is not cached, the paragraph is built by calling `build` with `text`, is laid out,
+    /// and is then cached. `style_key` must identify everything `build` uses besides `text`, for
+    /// example the paragraph and text styles.
+    pub fn get_or_layout(
+        &mut self,
+        text: &str,
+        style_key: &impl Hash,
+        width: scalar,
+        build: impl FnOnce(&str) -> Paragraph,
+    ) -> &Paragraph {
+        let key = {
+            let mut hasher = DefaultHasher::new();
+            text.hash(&mut hasher);
+            style_key.hash(&mut hasher);
+            LayoutKey {
+                content: hasher.finish(),
+                width: width.to_bits(),
+            }
+        };
+
+        self.use_counter += 1;
+        let last_use = self.use_counter;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            self.by_last_use.remove(&entry.last_use);
+            entry.last_use = last_use;
+        } else {
+            let mut paragraph = build(text);
+            paragraph.layout(width);
+            let memory = Self::ESTIMATED_PARAGRAPH_MEMORY
+                + text.len() * Self::ESTIMATED_MEMORY_PER_TEXT_BYTE;
+            self.memory_used += memory;
+            self.entries.insert(
+                key,
+                Entry {
+                    paragraph,
+                    memory,
+                    last_use,
+                },
+            );
+        }
+        self.by_last_use.insert(last_use, key);
+
+        // The paragraph that is returned is the most recently used one and is never removed, even
+        // if it exceeds the budget on its own.
+        self.evict(1);
+        &self.entries[&key].paragraph
+    }
+
+    /// The number of cached paragraphs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The estimated memory in bytes the cached paragraphs use.
+    pub fn memory_used(&self) -> usize {
+        self.memory_used
+    }
+
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    /// Sets the memory budget and removes the least recently used paragraphs until the
+    /// estimated memory fits in it.
+    pub fn set_memory_budget(&mut self, memory_budget: usize) {
+        self.memory_budget = memory_budget;
+        self.evict(0);
+    }
+
+    /// Removes all paragraphs.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.by_last_use.clear();
+        self.memory_used = 0;
+    }
+
+    fn evict(&mut self, keep: usize) {
+        while self.memory_used > self.memory_budget && self.entries.len() > keep {
+            let (&last_use, &key) = self.by_last_use.iter().next().unwrap();
+            self.by_last_use.remove(&last_use);
+            let entry = self.entries.remove(&key).unwrap();
+            self.memory_used -= entry.memory;
+        }
+    }
+}
+
+#[test]
+#[serial_test::serial]
+fn test_layout_cache() {
+    use crate::textlayout::{ParagraphStyle, TextStyle};
+
+    let builds = std::cell::Cell::new(0);
+    let mut build = |text: &str| {
+        builds.set(builds.get() + 1);
+        let mut paragraph_builder =
+            super::paragraph::test_paragraph_builder(&ParagraphStyle::new(), &TextStyle::new());
+        paragraph_builder.add_text(text);
+        paragraph_builder.build()
+    };
+
+    let entry_memory = |text: &str| {
+        ParagraphLayoutCache::ESTIMATED_PARAGRAPH_MEMORY
+            + text.len() * ParagraphLayoutCache::ESTIMATED_MEMORY_PER_TEXT_BYTE
+    };
+    let (a, b, c) = ("first", "second", "third");
+    let mut cache = ParagraphLayoutCache::new(entry_memory(a) + entry_memory(b));
+
+    assert_eq!(
+        cache.get_or_layout(a, &0, 100.0, &mut build).max_width(),
+        100.0
+    );
+    cache.get_or_layout(b, &0, 100.0, &mut build);
+    cache.get_or_layout(a, &0, 100.0, &mut build);
+    assert_eq!(builds.get(), 2);
+    assert_eq!(cache.memory_used(), entry_memory(a) + entry_memory(b));
+
+    // different constraints and styles are cached separately.
+    cache.get_or_layout(a, &0, 50.0, &mut build);
+    cache.get_or_layout(a, &1, 100.0, &mut build);
+    assert_eq!(builds.get(), 4);
+    assert_eq!(cache.len(), 2);
+
+    cache.get_or_layout(c, &0, 100.0, &mut build);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.memory_used() <= cache.memory_budget());
+
+    cache.set_memory_budget(0);
+    assert!(cache.is_empty());
+    assert_eq!(cache.memory_used(), 0);
+}
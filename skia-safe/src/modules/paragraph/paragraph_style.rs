@@ -74,6 +74,8 @@ impl StrutStyle {
         self
     }
 
+    /// Set the height of the strut as a multiple of the font size. This will not take effect
+    /// unless you have set `height_override` to `true`.
     pub fn set_height(&mut self, height: scalar) -> &mut Self {
         self.native_mut().fHeight = height;
         self
@@ -83,6 +85,18 @@ impl StrutStyle {
         self.native().fHeight
     }
 
+    /// Set whether the height of the strut should be explicitly overridden.
+    pub fn set_height_override(&mut self, height_override: bool) -> &mut Self {
+        self.native_mut().fHeightOverride = height_override;
+        self
+    }
+
+    /// Returns true if the strut's height is explicitly overridden by the value set by
+    /// `set_height`.
+    pub fn height_override(&self) -> bool {
+        self.native().fHeightOverride
+    }
+
     pub fn set_leading(&mut self, leading: scalar) -> &mut Self {
         self.native_mut().fLeading = leading;
         self
@@ -109,6 +123,8 @@ impl StrutStyle {
         self.native_mut().fForceHeight = force_height;
         self
     }
+
+    // TODO: half_leading / set_half_leading(), not supported by the textlayout module of m87.
 }
 
 // Can't use Handle<> here, std::u16string maintains an interior pointer.
@@ -167,6 +183,12 @@ impl ParagraphStyle {
         self
     }
 
+    /// The base direction of the paragraph, [TextDirection::LTR] by default.
+    ///
+    /// The bidirectional levels of the text are resolved starting with this direction, it is
+    /// not detected from the first strong character of the text. The direction of parts of the
+    /// text can be forced with
+    /// [crate::textlayout::ParagraphBuilder::add_text_with_direction_override()].
     pub fn text_direction(&self) -> TextDirection {
         self.native().fTextDirection
     }
@@ -217,6 +239,8 @@ impl ParagraphStyle {
         self
     }
 
+    /// Whether the height of the text style is applied before the first and after the last line,
+    /// [TextHeightBehavior::All] by default.
     pub fn text_height_behavior(&self) -> TextHeightBehavior {
         self.native().fTextHeightBehavior
     }
@@ -226,6 +250,34 @@ impl ParagraphStyle {
         self
     }
 
+    /// Returns `true` if the height of the text style is applied to the ascent of the first
+    /// line, which corresponds to `applyHeightToFirstAscent` of Flutter's `TextHeightBehavior`.
+    pub fn apply_height_to_first_ascent(&self) -> bool {
+        match self.text_height_behavior() {
+            TextHeightBehavior::All | TextHeightBehavior::DisableLastDescent => true,
+            TextHeightBehavior::DisableFirstAscent | TextHeightBehavior::DisableAll => false,
+        }
+    }
+
+    pub fn set_apply_height_to_first_ascent(&mut self, apply: bool) -> &mut Self {
+        let last = self.apply_height_to_last_descent();
+        self.set_text_height_behavior(text_height_behavior(apply, last))
+    }
+
+    /// Returns `true` if the height of the text style is applied to the descent of the last
+    /// line, which corresponds to `applyHeightToLastDescent` of Flutter's `TextHeightBehavior`.
+    pub fn apply_height_to_last_descent(&self) -> bool {
+        match self.text_height_behavior() {
+            TextHeightBehavior::All | TextHeightBehavior::DisableFirstAscent => true,
+            TextHeightBehavior::DisableLastDescent | TextHeightBehavior::DisableAll => false,
+        }
+    }
+
+    pub fn set_apply_height_to_last_descent(&mut self, apply: bool) -> &mut Self {
+        let first = self.apply_height_to_first_ascent();
+        self.set_text_height_behavior(text_height_behavior(first, apply))
+    }
+
     pub fn unlimited_lines(&self) -> bool {
         self.max_lines().is_none()
     }
@@ -256,3 +308,59 @@ impl ParagraphStyle {
         self
     }
 }
+
+fn text_height_behavior(first_ascent: bool, last_descent: bool) -> TextHeightBehavior {
+    match (first_ascent, last_descent) {
+        (true, true) => TextHeightBehavior::All,
+        (false, true) => TextHeightBehavior::DisableFirstAscent,
+        (true, false) => TextHeightBehavior::DisableLastDescent,
+        (false, false) => TextHeightBehavior::DisableAll,
+    }
+}
+
+#[test]
+fn test_apply_height_flags() {
+    let mut style = ParagraphStyle::new();
+    assert_eq!(style.text_height_behavior(), TextHeightBehavior::All);
+    style.set_apply_height_to_first_ascent(false);
+    assert_eq!(
+        style.text_height_behavior(),
+        TextHeightBehavior::DisableFirstAscent
+    );
+    style.set_apply_height_to_last_descent(false);
+    assert_eq!(style.text_height_behavior(), TextHeightBehavior::DisableAll);
+    style.set_apply_height_to_first_ascent(true);
+    assert_eq!(
+        style.text_height_behavior(),
+        TextHeightBehavior::DisableLastDescent
+    );
+    assert!(style.apply_height_to_first_ascent());
+    assert!(!style.apply_height_to_last_descent());
+}
+
+#[test]
+#[serial_test::serial]
+fn test_text_height_behavior() {
+    let mut text_style = TextStyle::new();
+    text_style.set_height_override(true).set_height(3.0);
+    let height = |behavior: TextHeightBehavior| {
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_text_height_behavior(behavior);
+        super::paragraph::layout_test_paragraph(
+            "first\nlast",
+            &paragraph_style,
+            &text_style,
+            1000.0,
+        )
+        .height()
+    };
+
+    let all = height(TextHeightBehavior::All);
+    let without_first_ascent = height(TextHeightBehavior::DisableFirstAscent);
+    let without_last_descent = height(TextHeightBehavior::DisableLastDescent);
+    let without_both = height(TextHeightBehavior::DisableAll);
+    assert!(without_first_ascent < all);
+    assert!(without_last_descent < all);
+    assert!(without_both < without_first_ascent);
+    assert!(without_both < without_last_descent);
+}
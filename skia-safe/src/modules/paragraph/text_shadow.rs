@@ -6,6 +6,8 @@ use skia_bindings as sb;
 pub struct TextShadow {
     pub color: Color,
     pub offset: Point,
+    /// Despite its name, this value is used as the standard deviation (sigma) of the Gaussian
+    /// blur that is applied to the shadow. See [TextShadow::sigma()].
     pub blur_radius: f64,
 }
 
@@ -39,7 +41,55 @@ impl TextShadow {
         })
     }
 
+    /// Creates a shadow that is blurred with a Gaussian blur of the standard deviation `sigma`.
+    pub fn new_with_sigma(color: impl Into<Color>, offset: impl Into<Point>, sigma: f64) -> Self {
+        Self::new(color, offset, sigma)
+    }
+
+    /// Creates a shadow from a blur radius in pixels as it is specified by CSS `text-shadow`.
+    ///
+    /// CSS defines the resulting blur as a Gaussian blur with a standard deviation of half the
+    /// blur radius.
+    pub fn from_blur_radius(
+        color: impl Into<Color>,
+        offset: impl Into<Point>,
+        blur_radius: f64,
+    ) -> Self {
+        Self::new_with_sigma(color, offset, blur_radius_to_sigma(blur_radius))
+    }
+
+    /// The standard deviation of the Gaussian blur that is applied to the shadow.
+    pub fn sigma(&self) -> f64 {
+        self.blur_radius
+    }
+
+    pub fn set_sigma(&mut self, sigma: f64) -> &mut Self {
+        self.blur_radius = sigma;
+        self
+    }
+
+    /// The blur radius in pixels as CSS `text-shadow` would specify it.
+    pub fn css_blur_radius(&self) -> f64 {
+        self.blur_radius * 2.0
+    }
+
     pub fn has_shadow(&self) -> bool {
         unsafe { self.native().hasShadow() }
     }
 }
+
+fn blur_radius_to_sigma(blur_radius: f64) -> f64 {
+    if blur_radius > 0.0 {
+        blur_radius / 2.0
+    } else {
+        0.0
+    }
+}
+
+#[test]
+fn css_blur_radius_round_trips() {
+    let shadow = TextShadow::from_blur_radius(Color::BLACK, (1.0, 2.0), 6.0);
+    assert_eq!(shadow.sigma(), 3.0);
+    assert_eq!(shadow.css_blur_radius(), 6.0);
+    assert_eq!(TextShadow::from_blur_radius(Color::BLACK, (0.0, 0.0), -1.0).sigma(), 0.0);
+}
@@ -47,15 +47,24 @@ impl TextDecoration {
 
 /// Decoration configuration for a piece of text.
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Decoration {
     /// The kind of decoration (underline, overline, or strikethrough).
     pub ty: TextDecoration,
     /// The "mode" for the decoration - if it is visible even for whitespace characters.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_support::text_decoration_mode")
+    )]
     pub mode: TextDecorationMode,
     /// The color of the decoration, see documentation for [Color]. This can be independent
     /// of the color of the text itself.
     pub color: Color,
     /// The style of the text decoration. See documentation for [TextDecorationStyle].
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_support::text_decoration_style")
+    )]
     pub style: TextDecorationStyle,
     /// The thickness, expressed as a multiple of the weight of the text.
     pub thickness_multiplier: scalar,
@@ -104,6 +113,7 @@ impl FontFeature {
 
 /// The style for a [Placeholder].
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlaceholderStyle {
     /// The width of the placeholder.
     pub width: scalar,
@@ -111,10 +121,15 @@ pub struct PlaceholderStyle {
     pub height: scalar,
     /// Where to vertically align the placeholder relative to the surrounding text. See [PlaceholderAlignment]
     /// for more information.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_support::placeholder_alignment")
+    )]
     pub alignment: PlaceholderAlignment,
     /// Whether the placeholder is placed relative to the alphabetic baseline (i.e. where the base
     /// of glyphs for characters such as a and o sit) or the ideographic baseline (i.e. the lowest
     /// point in the text, below the lowest point of glyphs for characters such as j or p).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::text_baseline"))]
     pub baseline: TextBaseline,
     /// The offset from the text's baseline.
     pub baseline_offset: scalar,
@@ -357,6 +372,24 @@ impl TextStyle {
         self
     }
 
+    /// Appends font families that are not yet in the list of font families.
+    ///
+    /// For each character, the families are tried in order before the fonts of the
+    /// [super::FontCollection]'s fallback manager, so appending an emoji family, like one that
+    /// is registered with a [super::TypefaceFontProvider], renders emoji with that family on all
+    /// platforms instead of with the system's emoji font. To prefer different families for
+    /// different scripts, push text styles with different fallback families for these runs.
+    pub fn add_fallback_font_families(&mut self, families: &[impl AsRef<str>]) -> &mut Self {
+        let mut all: Vec<String> = self.font_families().iter().map(String::from).collect();
+        for family in families {
+            let family = family.as_ref();
+            if !all.iter().any(|f| f == family) {
+                all.push(family.into());
+            }
+        }
+        self.set_font_families(&all)
+    }
+
     /// Set the height of the text. This will not take effect unless you have set
     /// `height_override` to `true`.
     pub fn set_height(&mut self, height: scalar) -> &mut Self {
@@ -594,6 +627,15 @@ mod tests {
         StyleType, TextDecorationMode, TextDecorationStyle,
     };
 
+    #[test]
+    fn add_fallback_font_families() {
+        let mut style = super::TextStyle::new();
+        style.set_font_families(&["Roboto", "Noto Sans"]);
+        style.add_fallback_font_families(&["Twemoji", "Roboto"]);
+        let families: Vec<&str> = style.font_families().iter().collect();
+        assert_eq!(families, ["Roboto", "Noto Sans", "Twemoji"]);
+    }
+
     #[test]
     fn text_decoration_style_naming() {
         let _ = TextDecorationStyle::Solid;
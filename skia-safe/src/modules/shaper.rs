@@ -1,6 +1,7 @@
 use crate::prelude::*;
-use crate::{scalar, Font, FontMgr, FourByteTag, Point, TextBlob};
+use crate::{scalar, Font, FontMgr, FourByteTag, GlyphId, Point, TextBlob};
 pub use run_handler::RunHandler;
+use run_handler::{Buffer, RunInfo};
 use skia_bindings as sb;
 use skia_bindings::{
     RustRunHandler, SkShaper, SkShaper_BiDiRunIterator, SkShaper_FontRunIterator,
@@ -9,6 +10,7 @@ use skia_bindings::{
 };
 use std::ffi::CStr;
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::os::raw;
 
 pub type Shaper = RefHandle<SkShaper>;
@@ -664,6 +666,110 @@ impl Shaper {
     }
 }
 
+/// A line of text that was shaped by [Shaper::shape_lines()].
+#[derive(Debug)]
+pub struct ShapedLine {
+    /// The glyphs of the line, positioned relative to the origin of the first line, or [None]
+    /// if the line contains no glyphs.
+    pub blob: Option<TextBlob>,
+    /// The range of the line in the text, in bytes.
+    pub utf8_range: Range<usize>,
+    /// The top of the line.
+    pub top: scalar,
+    /// The top of the next line.
+    pub bottom: scalar,
+}
+
+impl Shaper {
+    /// Shapes and wraps text into lines that each may have a different width, for example to flow
+    /// text around a floating image. `line_width` returns the width that is available for the
+    /// line with the given index.
+    ///
+    /// skparagraph lays out all lines of a paragraph with the same width, so this shapes the
+    /// remaining text once per line and keeps only the first line of each pass. Prefer
+    /// [Self::shape_text_blob()] if all lines have the same width.
+    pub fn shape_lines(
+        &self,
+        text: &str,
+        font: &Font,
+        left_to_right: bool,
+        mut line_width: impl FnMut(usize) -> scalar,
+    ) -> Vec<ShapedLine> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut top = 0.0;
+        while start < text.len() {
+            let remaining = &text[start..];
+            let mut first_line = FirstLineRunHandler::default();
+            self.shape(
+                remaining,
+                font,
+                left_to_right,
+                line_width(lines.len()),
+                &mut first_line,
+            );
+            // a line contains at least one character, even if it does not fit.
+            let len = match first_line.end {
+                0 => remaining.chars().next().unwrap().len_utf8(),
+                end => end,
+            };
+            let line_text = &remaining[..len];
+            let (blob, bottom) = match self.shape_text_blob(
+                line_text,
+                font,
+                left_to_right,
+                scalar::INFINITY,
+                (0.0, top),
+            ) {
+                Some((blob, end_point)) => (Some(blob), end_point.y),
+                None => (None, top + font.spacing()),
+            };
+            lines.push(ShapedLine {
+                blob,
+                utf8_range: start..start + len,
+                top,
+                bottom,
+            });
+            start += len;
+            top = bottom;
+        }
+        lines
+    }
+}
+
+/// Records the end of the text of the first line.
+#[derive(Default)]
+struct FirstLineRunHandler {
+    line: usize,
+    end: usize,
+    glyphs: Vec<GlyphId>,
+    positions: Vec<Point>,
+}
+
+impl RunHandler for FirstLineRunHandler {
+    fn begin_line(&mut self) {
+        self.line += 1;
+    }
+
+    fn run_info(&mut self, info: &RunInfo) {
+        if self.line == 1 {
+            self.end = self.end.max(info.utf8_range.end);
+        }
+    }
+
+    fn commit_run_info(&mut self) {}
+
+    fn run_buffer(&mut self, info: &RunInfo) -> Buffer {
+        self.glyphs.resize(info.glyph_count, 0);
+        self.positions.resize(info.glyph_count, Point::default());
+        Buffer::new(&mut self.glyphs, &mut self.positions, None)
+    }
+
+    fn commit_run_buffer(&mut self, _: &RunInfo) {}
+
+    fn commit_line(&mut self) {}
+}
+
 pub mod icu {
 
     /// On Windows, this function writes the file `icudtl.dat` into the current
@@ -706,3 +812,25 @@ pub mod icu {
         assert!(bounds.width() > 0.0 && bounds.height() > 0.0);
     }
 }
+
+#[test]
+#[serial_test::serial]
+fn test_shape_lines() {
+    icu::init();
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(4);
+    let font = Font::default();
+    let shaper = Shaper::new(None);
+
+    let lines = shaper.shape_lines(&text, &font, true, |line| [60.0, 300.0][line % 2]);
+
+    assert!(lines.len() > 2);
+    assert_eq!(lines[0].utf8_range.start, 0);
+    assert_eq!(lines.last().unwrap().utf8_range.end, text.len());
+    for pair in lines.windows(2) {
+        assert_eq!(pair[0].utf8_range.end, pair[1].utf8_range.start);
+        assert_eq!(pair[0].bottom, pair[1].top);
+        assert!(pair[0].top < pair[0].bottom);
+    }
+    assert!(lines[0].utf8_range.len() < lines[1].utf8_range.len());
+    assert!(lines.iter().all(|line| line.blob.is_some()));
+}
@@ -165,6 +165,12 @@ pub trait NativeRefCountedBase {
     fn ref_counted_base(&self) -> &Self::Base {
         unsafe { &*(self as *const _ as *const Self::Base) }
     }
+
+    /// Returns `true` if the instance is bound to the thread that uses its GPU context, in which
+    /// case it must not be sent to another thread, even if it is not shared.
+    fn is_thread_affine(&self) -> bool {
+        false
+    }
 }
 
 impl<Native, Base: NativeRefCounted> NativeRefCounted for Native
@@ -852,6 +858,18 @@ impl<'a, H> DerefMut for Borrows<'a, H> {
 }
 
 impl<'a, H> Borrows<'a, H> {
+    /// Returns a reference to the handle, which is the same as dereferencing it, but can be
+    /// clearer when the handle's own methods are shadowed.
+    pub fn get(&self) -> &H {
+        &self.0
+    }
+
+    /// Derives a value from the handle that is bound to the same dependency, for example a
+    /// different view of the same borrowed pixels.
+    pub fn map<R>(&self, f: impl FnOnce(&H) -> R) -> Borrows<'a, R> {
+        Borrows(f(&self.0), PhantomData)
+    }
+
     /// Notify that the borrowed dependency is not referred to anymore and return the handle.
     /// # Safety
     /// The borrowed dependency must be removed before calling `release()`.
@@ -870,6 +888,21 @@ impl<T: Sized> BorrowsFrom for T {
     }
 }
 
+#[cfg(test)]
+mod borrows_tests {
+    use super::BorrowsFrom;
+
+    #[test]
+    fn map_derives_a_value_bound_to_the_same_dependency() {
+        let dependency = [1u8, 2, 3];
+        let borrows = dependency.len().borrows(&dependency);
+        assert_eq!(*borrows.get(), 3);
+        let mapped = borrows.map(|len| len * 2);
+        assert_eq!(*mapped, 6);
+        assert_eq!(*borrows, 3);
+    }
+}
+
 /// Declares a baseclass for a native type.
 pub trait NativeBase<Base> {
     fn base(&self) -> &Base {
@@ -900,10 +933,11 @@ pub trait ConditionallySend: Sized {
 }
 
 /// `RCHandle<H>` is conditionally Send and can be sent to
-/// another thread when its reference count is 1.
+/// another thread when its reference count is 1 and it is not bound to
+/// the thread of a GPU context.
 impl<H: NativeRefCountedBase> ConditionallySend for RCHandle<H> {
     fn can_send(&self) -> bool {
-        self.native().unique()
+        self.native().unique() && !self.native().is_thread_affine()
     }
 
     fn wrap_send(self) -> Result<Sendable<Self>, Self> {
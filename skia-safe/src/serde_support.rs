@@ -0,0 +1,297 @@
+//! Serde support for types that can not derive `Serialize` and `Deserialize`.
+//!
+//! The enums generated by bindgen live in skia-bindings, so they are (de)serialized by their
+//! variant names through the `with` modules defined here.
+
+use crate::{font_style, scalar, FontStyle, Matrix};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! serde_enum {
+    ($module:ident, $ty:ty, [$($variant:ident),+]) => {
+        pub(crate) mod $module {
+            use serde::{de, Deserialize, Deserializer, Serializer};
+
+            type Enum = $ty;
+            const VARIANTS: &[&str] = &[$(stringify!($variant)),+];
+
+            pub fn serialize<S: Serializer>(value: &Enum, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(match value {
+                    $(Enum::$variant => stringify!($variant)),+
+                })
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Enum, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                match name.as_str() {
+                    $(stringify!($variant) => Ok(Enum::$variant),)+
+                    _ => Err(de::Error::unknown_variant(&name, VARIANTS)),
+                }
+            }
+
+            #[allow(dead_code)]
+            pub mod option {
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                #[derive(Serialize, Deserialize)]
+                #[serde(transparent)]
+                struct Value(#[serde(with = "super")] super::Enum);
+
+                pub fn serialize<S: Serializer>(
+                    value: &Option<super::Enum>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    value.map(Value).serialize(serializer)
+                }
+
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<super::Enum>, D::Error> {
+                    Ok(Option::<Value>::deserialize(deserializer)?.map(|v| v.0))
+                }
+            }
+        }
+    };
+}
+
+serde_enum!(slant, crate::font_style::Slant, [Upright, Italic, Oblique]);
+serde_enum!(
+    paint_style,
+    crate::paint::Style,
+    [Fill, Stroke, StrokeAndFill]
+);
+serde_enum!(paint_cap, crate::paint::Cap, [Butt, Round, Square]);
+serde_enum!(paint_join, crate::paint::Join, [Miter, Round, Bevel]);
+serde_enum!(tile_mode, crate::TileMode, [Clamp, Repeat, Mirror, Decal]);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(
+    text_decoration_style,
+    crate::textlayout::TextDecorationStyle,
+    [Solid, Double, Dotted, Dashed, Wavy]
+);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(
+    text_decoration_mode,
+    crate::textlayout::TextDecorationMode,
+    [Gaps, Through]
+);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(
+    placeholder_alignment,
+    crate::textlayout::PlaceholderAlignment,
+    [Baseline, AboveBaseline, BelowBaseline, Top, Bottom, Middle]
+);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(
+    text_baseline,
+    crate::textlayout::TextBaseline,
+    [Alphabetic, Ideographic]
+);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(
+    text_align,
+    crate::textlayout::TextAlign,
+    [Left, Right, Center, Justify, Start, End]
+);
+
+#[cfg(feature = "textlayout")]
+serde_enum!(text_direction, crate::textlayout::TextDirection, [RTL, LTR]);
+
+/// (De)serializes a [Color] as a `"#RRGGBB"` or `"#AARRGGBB"` string. Deserialization also
+/// accepts the `"#RGB"` shorthand and plain ARGB numbers.
+pub(crate) mod hex_color {
+    use crate::prelude::*;
+    use crate::Color;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        let argb = color.into_native();
+        if color.a() == 0xff {
+            serializer.serialize_str(&format!("#{:06X}", argb & 0xff_ffff))
+        } else {
+            serializer.serialize_str(&format!("#{:08X}", argb))
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Argb(u32),
+            Hex(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Argb(argb) => Ok(Color::new(argb)),
+            Repr::Hex(hex) => parse(&hex).ok_or_else(|| {
+                de::Error::invalid_value(
+                    de::Unexpected::Str(&hex),
+                    &"a #RGB, #RRGGBB or #AARRGGBB color",
+                )
+            }),
+        }
+    }
+
+    fn parse(hex: &str) -> Option<Color> {
+        let digits = hex.strip_prefix('#')?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        match digits.len() {
+            3 => {
+                let expand = |v: u32| (v & 0xf) * 0x11;
+                Some(Color::from_rgb(
+                    expand(value >> 8) as u8,
+                    expand(value >> 4) as u8,
+                    expand(value) as u8,
+                ))
+            }
+            6 => Some(Color::new(0xff00_0000 | value)),
+            8 => Some(Color::new(value)),
+            _ => None,
+        }
+    }
+
+    #[derive(serde::Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub(crate) struct HexColor(#[serde(with = "self")] pub Color);
+
+    /// [hex_color](self) for optional colors.
+    pub(crate) mod option {
+        use super::HexColor;
+        use crate::Color;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            color: &Option<Color>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            color.map(HexColor).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Color>, D::Error> {
+            Ok(Option::<HexColor>::deserialize(deserializer)?.map(|c| c.0))
+        }
+    }
+
+    /// [hex_color](self) for color lists.
+    pub(crate) mod vec {
+        use super::HexColor;
+        use crate::Color;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            colors: &[Color],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(colors.iter().copied().map(HexColor))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Color>, D::Error> {
+            Ok(Vec::<HexColor>::deserialize(deserializer)?
+                .into_iter()
+                .map(|c| c.0)
+                .collect())
+        }
+    }
+}
+
+/// A [Matrix] is (de)serialized as its 9 scalar values in row major order.
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut values = [0.0; 9];
+        self.get_9(&mut values);
+        values.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = <[scalar; 9]>::deserialize(deserializer)?;
+        Ok(Matrix::new_all(
+            v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7], v[8],
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FontStyle")]
+struct FontStyleFields {
+    weight: font_style::Weight,
+    width: font_style::Width,
+    #[serde(with = "slant")]
+    slant: font_style::Slant,
+}
+
+impl Serialize for FontStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FontStyleFields {
+            weight: self.weight(),
+            width: self.width(),
+            slant: self.slant(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FontStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = FontStyleFields::deserialize(deserializer)?;
+        Ok(FontStyle::new(fields.weight, fields.width, fields.slant))
+    }
+}
+
+/// [crate::textlayout::TextDecoration] is (de)serialized as its bits.
+#[cfg(feature = "textlayout")]
+impl Serialize for crate::textlayout::TextDecoration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.bits().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "textlayout")]
+impl<'de> Deserialize<'de> for crate::textlayout::TextDecoration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = u32::deserialize(deserializer)?;
+        Self::from_bits(bits).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(bits.into()),
+                &"text decoration bits",
+            )
+        })
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    use crate::{Color, Rect};
+
+    let rect = Rect::new(1.0, 2.0, 3.0, 4.0);
+    let json = serde_json::to_string(&rect).unwrap();
+    assert_eq!(serde_json::from_str::<Rect>(&json).unwrap(), rect);
+
+    let color = Color::from_argb(1, 2, 3, 4);
+    let json = serde_json::to_string(&color).unwrap();
+    assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+
+    let matrix = Matrix::new_trans((5.0, 6.0));
+    let json = serde_json::to_string(&matrix).unwrap();
+    assert_eq!(json, "[1.0,0.0,5.0,0.0,1.0,6.0,0.0,0.0,1.0]");
+    assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), matrix);
+
+    let font_style = FontStyle::bold_italic();
+    let json = serde_json::to_string(&font_style).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FontStyle>(&json).unwrap(),
+        font_style
+    );
+}
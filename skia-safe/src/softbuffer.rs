@@ -0,0 +1,84 @@
+//! Presentation of raster surfaces in windows with [softbuffer](https://crates.io/crates/softbuffer).
+//!
+//! [WindowPresenter] renders directly into the pixel buffer softbuffer provides for a window, so
+//! the pixels are not copied before they are presented.
+//!
+//! softbuffer's pixels are `0RGB` 32 bit integers, which Skia can only render to on little
+//! endian targets, so this module is not available on big endian targets.
+
+use crate::{AlphaType, Canvas, ColorType, ISize, ImageInfo, Surface};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use softbuffer_rs::{Context, SoftBufferError};
+use std::num::NonZeroU32;
+use std::slice;
+
+/// Presents the pixels that are drawn with a [Canvas] in a window.
+pub struct WindowPresenter {
+    surface: softbuffer_rs::Surface,
+    size: ISize,
+    // The surface refers to the context, so it must be dropped first.
+    _context: Context,
+}
+
+impl WindowPresenter {
+    /// Creates a presenter for `window`.
+    ///
+    /// # Safety
+    /// `window` must outlive the presenter.
+    pub unsafe fn new<W: HasRawWindowHandle + HasRawDisplayHandle>(
+        window: &W,
+    ) -> Result<Self, SoftBufferError> {
+        let context = Context::new(window)?;
+        let surface = softbuffer_rs::Surface::new(&context, window)?;
+        Ok(Self {
+            surface,
+            size: ISize::default(),
+            _context: context,
+        })
+    }
+
+    /// Lets `draw` render the next frame and presents it.
+    ///
+    /// `size` is the size of the window's client area in physical pixels, for example
+    /// `window.inner_size()` with winit. When it changed since the last frame, the pixel buffer
+    /// is resized before `draw` is called. Nothing is drawn if `size` is empty, which is the case
+    /// when a window is minimized on some platforms.
+    pub fn present(
+        &mut self,
+        size: impl Into<ISize>,
+        draw: impl FnOnce(&mut Canvas),
+    ) -> Result<(), SoftBufferError> {
+        let size = size.into();
+        let (width, height) = match (non_zero(size.width), non_zero(size.height)) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return Ok(()),
+        };
+        if size != self.size {
+            self.surface.resize(width, height)?;
+            self.size = size;
+        }
+
+        let mut buffer = self.surface.buffer_mut()?;
+        {
+            let info = ImageInfo::new(size, ColorType::BGRA8888, AlphaType::Opaque, None);
+            let row_bytes = info.min_row_bytes();
+            let pixels = unsafe {
+                slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 4)
+            };
+            let mut surface = Surface::new_raster_direct(&info, pixels, row_bytes, None)
+                .expect("Failed to wrap the window's pixel buffer");
+            draw(surface.canvas());
+        }
+        // softbuffer expects the upper 8 bits, where Skia stores the alpha channel, to be zero.
+        buffer.iter_mut().for_each(|pixel| *pixel &= 0x00ff_ffff);
+        buffer.present()
+    }
+}
+
+fn non_zero(value: i32) -> Option<NonZeroU32> {
+    if value > 0 {
+        NonZeroU32::new(value as u32)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,464 @@
+//! Declarative style descriptions that can be loaded with serde, for example from design tokens
+//! stored in JSON, and turned into [Paint]s and text layout styles.
+//!
+//! Colors are written as `"#RRGGBB"` or `"#AARRGGBB"` strings, enum values by their variant
+//! names and all fields are optional.
+//!
+//! ```rust,ignore
+//! let style_sheet: StyleSheet = serde_json::from_str(r##"{
+//!     "paints": {
+//!         "accent": {
+//!             "gradient": {
+//!                 "type": "linear",
+//!                 "start": { "x": 0, "y": 0 },
+//!                 "end": { "x": 100, "y": 0 },
+//!                 "colors": ["#FF0000", "#0000FF"]
+//!             },
+//!             "shadow": { "color": "#80000000", "offset": { "x": 0, "y": 2 }, "sigma": 3 }
+//!         }
+//!     }
+//! }"##)?;
+//! let paint = style_sheet.paint("accent").expect("invalid or missing paint");
+//! ```
+
+use crate::{
+    effects::{gradient_shader, image_filters},
+    paint, scalar, Color, Paint, Point, Shader, TileMode,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A named collection of style descriptions.
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleSheet {
+    pub paints: BTreeMap<String, PaintDescription>,
+    #[cfg(feature = "textlayout")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub text_styles: BTreeMap<String, TextStyleDescription>,
+    #[cfg(feature = "textlayout")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub paragraph_styles: BTreeMap<String, ParagraphStyleDescription>,
+}
+
+impl StyleSheet {
+    /// Builds the paint with the given name. Returns [None] if there is no such paint or if its
+    /// description is invalid.
+    pub fn paint(&self, name: &str) -> Option<Paint> {
+        self.paints.get(name)?.to_paint()
+    }
+
+    /// Builds the text style with the given name. Returns [None] if there is no such style or if
+    /// its description is invalid.
+    #[cfg(feature = "textlayout")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub fn text_style(&self, name: &str) -> Option<crate::textlayout::TextStyle> {
+        self.text_styles.get(name)?.to_text_style()
+    }
+
+    /// Builds the paragraph style with the given name. Returns [None] if there is no such style or
+    /// if its description is invalid.
+    #[cfg(feature = "textlayout")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub fn paragraph_style(&self, name: &str) -> Option<crate::textlayout::ParagraphStyle> {
+        self.paragraph_styles.get(name)?.to_paragraph_style()
+    }
+}
+
+/// Describes a [Paint].
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaintDescription {
+    /// The color of the paint, its alpha is also applied to the gradient.
+    #[serde(with = "crate::serde_support::hex_color")]
+    pub color: Color,
+    pub gradient: Option<GradientDescription>,
+    #[serde(with = "crate::serde_support::paint_style")]
+    pub style: paint::Style,
+    pub stroke_width: scalar,
+    #[serde(with = "crate::serde_support::paint_cap")]
+    pub stroke_cap: paint::Cap,
+    #[serde(with = "crate::serde_support::paint_join")]
+    pub stroke_join: paint::Join,
+    /// Unlike [Paint::default()], anti aliasing is enabled by default.
+    pub anti_alias: bool,
+    /// A drop shadow that is drawn below the content.
+    pub shadow: Option<ShadowDescription>,
+}
+
+impl Default for PaintDescription {
+    fn default() -> Self {
+        PaintDescription {
+            color: Color::BLACK,
+            gradient: None,
+            style: paint::Style::Fill,
+            stroke_width: 0.0,
+            stroke_cap: paint::Cap::Butt,
+            stroke_join: paint::Join::Miter,
+            anti_alias: true,
+            shadow: None,
+        }
+    }
+}
+
+impl PaintDescription {
+    /// Builds the paint. Returns [None] if the gradient or shadow can not be created.
+    pub fn to_paint(&self) -> Option<Paint> {
+        let mut paint = Paint::default();
+        paint
+            .set_color(self.color)
+            .set_style(self.style)
+            .set_stroke_width(self.stroke_width)
+            .set_stroke_cap(self.stroke_cap)
+            .set_stroke_join(self.stroke_join)
+            .set_anti_alias(self.anti_alias);
+        if let Some(gradient) = &self.gradient {
+            paint.set_shader(gradient.to_shader()?);
+        }
+        if let Some(shadow) = &self.shadow {
+            paint.set_image_filter(image_filters::drop_shadow(
+                shadow.offset,
+                (shadow.sigma, shadow.sigma),
+                shadow.color,
+                None,
+                None,
+            )?);
+        }
+        Some(paint)
+    }
+}
+
+/// Describes a gradient [Shader].
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GradientDescription {
+    #[serde(flatten)]
+    pub kind: GradientKind,
+    #[serde(with = "crate::serde_support::hex_color::vec")]
+    pub colors: Vec<Color>,
+    /// The relative positions of the colors in the range `0..=1`. If [None], the colors are
+    /// distributed evenly.
+    #[serde(default)]
+    pub positions: Option<Vec<scalar>>,
+    #[serde(
+        default = "default_tile_mode",
+        with = "crate::serde_support::tile_mode"
+    )]
+    pub tile_mode: TileMode,
+}
+
+fn default_tile_mode() -> TileMode {
+    TileMode::Clamp
+}
+
+/// The geometry of a [GradientDescription].
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GradientKind {
+    Linear {
+        start: Point,
+        end: Point,
+    },
+    Radial {
+        center: Point,
+        radius: scalar,
+    },
+    /// A sweep gradient around `center`, angles are in degrees, clockwise from the positive x
+    /// axis.
+    Sweep {
+        center: Point,
+        #[serde(default)]
+        start_angle: scalar,
+        #[serde(default = "full_turn")]
+        end_angle: scalar,
+    },
+}
+
+fn full_turn() -> scalar {
+    360.0
+}
+
+impl GradientDescription {
+    /// Builds the shader. Returns [None] if the number of positions does not match the number of
+    /// colors, or if Skia rejects the gradient.
+    pub fn to_shader(&self) -> Option<Shader> {
+        let colors: &[Color] = &self.colors;
+        let positions = self.positions.as_deref();
+        if positions.map_or(false, |p| p.len() != colors.len()) {
+            return None;
+        }
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                gradient_shader::linear((start, end), colors, positions, self.tile_mode, None, None)
+            }
+            GradientKind::Radial { center, radius } => gradient_shader::radial(
+                center,
+                radius,
+                colors,
+                positions,
+                self.tile_mode,
+                None,
+                None,
+            ),
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => gradient_shader::sweep(
+                center,
+                colors,
+                positions,
+                self.tile_mode,
+                (start_angle, end_angle),
+                None,
+                None,
+            ),
+        }
+    }
+}
+
+/// Describes a blurred shadow.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShadowDescription {
+    #[serde(with = "crate::serde_support::hex_color")]
+    pub color: Color,
+    pub offset: Point,
+    /// The standard deviation of the blur.
+    pub sigma: scalar,
+}
+
+impl Default for ShadowDescription {
+    fn default() -> Self {
+        ShadowDescription {
+            color: Color::BLACK,
+            offset: Point::default(),
+            sigma: 0.0,
+        }
+    }
+}
+
+#[cfg(feature = "textlayout")]
+pub use self::text::*;
+
+#[cfg(feature = "textlayout")]
+mod text {
+    use super::{PaintDescription, ShadowDescription};
+    use crate::serde_support::hex_color;
+    use crate::textlayout::{
+        Decoration, ParagraphStyle, TextAlign, TextDecoration, TextDecorationMode,
+        TextDecorationStyle, TextDirection, TextShadow, TextStyle,
+    };
+    use crate::{scalar, Color, FontStyle};
+    use serde::{Deserialize, Serialize};
+
+    /// Describes a [TextStyle]. Fields that are [None] keep the defaults of [TextStyle::new()].
+    #[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub struct TextStyleDescription {
+        #[serde(with = "hex_color::option")]
+        pub color: Option<Color>,
+        /// Paints the glyphs, overrides `color`.
+        pub foreground: Option<PaintDescription>,
+        pub background: Option<PaintDescription>,
+        pub font_families: Vec<String>,
+        pub font_size: Option<scalar>,
+        pub font_style: Option<FontStyle>,
+        pub height: Option<scalar>,
+        pub letter_spacing: Option<scalar>,
+        pub word_spacing: Option<scalar>,
+        pub decoration: Option<DecorationDescription>,
+        pub shadows: Vec<ShadowDescription>,
+    }
+
+    impl TextStyleDescription {
+        /// Builds the text style. Returns [None] if one of the paints can not be created.
+        pub fn to_text_style(&self) -> Option<TextStyle> {
+            let mut style = TextStyle::new();
+            if let Some(color) = self.color {
+                style.set_color(color);
+            }
+            if let Some(foreground) = &self.foreground {
+                style.set_foreground_color(foreground.to_paint()?);
+            }
+            if let Some(background) = &self.background {
+                style.set_background_color(background.to_paint()?);
+            }
+            if !self.font_families.is_empty() {
+                style.set_font_families(self.font_families.as_slice());
+            }
+            if let Some(font_size) = self.font_size {
+                style.set_font_size(font_size);
+            }
+            if let Some(font_style) = self.font_style {
+                style.set_font_style(font_style);
+            }
+            if let Some(height) = self.height {
+                style.set_height(height).set_height_override(true);
+            }
+            if let Some(letter_spacing) = self.letter_spacing {
+                style.set_letter_spacing(letter_spacing);
+            }
+            if let Some(word_spacing) = self.word_spacing {
+                style.set_word_spacing(word_spacing);
+            }
+            if let Some(decoration) = &self.decoration {
+                *style.decoration_mut() = decoration.to_decoration();
+            }
+            for shadow in &self.shadows {
+                style.add_shadow(TextShadow::new_with_sigma(
+                    shadow.color,
+                    shadow.offset,
+                    shadow.sigma.into(),
+                ));
+            }
+            Some(style)
+        }
+    }
+
+    /// Describes a text [Decoration].
+    #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub struct DecorationDescription {
+        pub underline: bool,
+        pub overline: bool,
+        pub line_through: bool,
+        #[serde(with = "crate::serde_support::text_decoration_mode")]
+        pub mode: TextDecorationMode,
+        /// The color of the decoration, [None] uses the color of the text.
+        #[serde(with = "hex_color::option")]
+        pub color: Option<Color>,
+        #[serde(with = "crate::serde_support::text_decoration_style")]
+        pub style: TextDecorationStyle,
+        pub thickness_multiplier: scalar,
+    }
+
+    impl Default for DecorationDescription {
+        fn default() -> Self {
+            DecorationDescription {
+                underline: false,
+                overline: false,
+                line_through: false,
+                mode: TextDecorationMode::Through,
+                color: None,
+                style: TextDecorationStyle::Solid,
+                thickness_multiplier: 1.0,
+            }
+        }
+    }
+
+    impl DecorationDescription {
+        pub fn to_decoration(&self) -> Decoration {
+            let mut ty = TextDecoration::NO_DECORATION;
+            ty.set(TextDecoration::UNDERLINE, self.underline);
+            ty.set(TextDecoration::OVERLINE, self.overline);
+            ty.set(TextDecoration::LINE_THROUGH, self.line_through);
+            Decoration {
+                ty,
+                mode: self.mode,
+                // a transparent decoration color is replaced by the text color.
+                color: self.color.unwrap_or(Color::TRANSPARENT),
+                style: self.style,
+                thickness_multiplier: self.thickness_multiplier,
+            }
+        }
+    }
+
+    /// Describes a [ParagraphStyle]. Fields that are [None] keep the defaults of
+    /// [ParagraphStyle::new()].
+    #[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "textlayout")))]
+    pub struct ParagraphStyleDescription {
+        pub text_style: Option<TextStyleDescription>,
+        #[serde(with = "crate::serde_support::text_align::option")]
+        pub text_align: Option<TextAlign>,
+        #[serde(with = "crate::serde_support::text_direction::option")]
+        pub text_direction: Option<TextDirection>,
+        pub max_lines: Option<usize>,
+        pub ellipsis: Option<String>,
+        pub height: Option<scalar>,
+    }
+
+    impl ParagraphStyleDescription {
+        /// Builds the paragraph style. Returns [None] if the text style can not be created.
+        pub fn to_paragraph_style(&self) -> Option<ParagraphStyle> {
+            let mut style = ParagraphStyle::new();
+            if let Some(text_style) = &self.text_style {
+                style.set_text_style(&text_style.to_text_style()?);
+            }
+            if let Some(text_align) = self.text_align {
+                style.set_text_align(text_align);
+            }
+            if let Some(text_direction) = self.text_direction {
+                style.set_text_direction(text_direction);
+            }
+            if let Some(max_lines) = self.max_lines {
+                style.set_max_lines(max_lines);
+            }
+            if let Some(ellipsis) = &self.ellipsis {
+                style.set_ellipsis(ellipsis);
+            }
+            if let Some(height) = self.height {
+                style.set_height(height);
+            }
+            Some(style)
+        }
+    }
+}
+
+#[test]
+fn test_paint_from_json() {
+    let style_sheet: StyleSheet = serde_json::from_str(
+        r##"{
+            "paints": {
+                "outline": {
+                    "color": "#80FF0000",
+                    "style": "Stroke",
+                    "stroke_width": 2
+                },
+                "accent": {
+                    "gradient": {
+                        "type": "sweep",
+                        "center": { "x": 50, "y": 50 },
+                        "colors": ["#F00", "#0000FF"],
+                        "tile_mode": "Repeat"
+                    },
+                    "shadow": { "color": "#40000000", "offset": { "x": 0, "y": 2 }, "sigma": 3 }
+                },
+                "invalid": {
+                    "gradient": {
+                        "type": "linear",
+                        "start": { "x": 0, "y": 0 },
+                        "end": { "x": 1, "y": 0 },
+                        "colors": ["#FFFFFF", "#000000"],
+                        "positions": [0]
+                    }
+                }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let outline = style_sheet.paint("outline").unwrap();
+    assert_eq!(outline.color(), Color::from_argb(0x80, 0xff, 0, 0));
+    assert_eq!(outline.style(), paint::Style::Stroke);
+    assert_eq!(outline.stroke_width(), 2.0);
+    assert!(outline.is_anti_alias());
+
+    let accent = style_sheet.paint("accent").unwrap();
+    assert!(accent.shader().is_some());
+    assert!(accent.image_filter().is_some());
+    let gradient = style_sheet.paints["accent"].gradient.as_ref().unwrap();
+    assert_eq!(gradient.colors, [Color::RED, Color::BLUE]);
+    assert_eq!(gradient.tile_mode, TileMode::Repeat);
+
+    assert!(style_sheet.paint("invalid").is_none());
+    assert!(style_sheet.paint("missing").is_none());
+
+    let json = serde_json::to_string(&style_sheet.paints["outline"]).unwrap();
+    assert!(json.contains(r##""color":"#80FF0000""##));
+    let round_trip: PaintDescription = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip, style_sheet.paints["outline"]);
+}
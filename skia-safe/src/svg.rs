@@ -1,9 +1,11 @@
 pub mod canvas;
 
+#[cfg(feature = "gpu")]
+use crate::{gpu, Budgeted, ImageInfo};
 use crate::{
     interop::RustStream,
-    prelude::{NativeAccess, NativeDrop, NativeRefCounted},
-    RCHandle,
+    prelude::{NativeAccess, NativeDrop, NativeRefCounted, NativeTransmutable},
+    Color, ISize, Image, RCHandle, Size, Surface,
 };
 use std::{error::Error, fmt, io};
 
@@ -71,4 +73,73 @@ impl SvgDom {
     pub fn render(&self, canvas: &mut crate::Canvas) {
         unsafe { sb::SkSVGDOM::render(self.native() as &_, canvas.native_mut()) }
     }
+
+    /// The size of the viewport the SVG is rendered into. Defaults to the intrinsic size of the
+    /// SVG document.
+    pub fn container_size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_SkSVGDOM_containerSize(self.native(), size.native_mut()) };
+        size
+    }
+
+    pub fn set_container_size(&mut self, size: impl Into<Size>) -> &mut Self {
+        let size = size.into();
+        unsafe { sb::C_SkSVGDOM_setContainerSize(self.native_mut(), size.native()) };
+        self
+    }
+
+    /// Renders the SVG into a new raster [Image].
+    ///
+    /// The SVG is scaled to fit `size`, which defaults to its container size (see
+    /// [Self::container_size()]), and is drawn over `background`, which defaults to transparent.
+    /// Returns [None] if the surface could not be created.
+    pub fn render_to_image(
+        &self,
+        size: impl Into<Option<ISize>>,
+        background: impl Into<Option<Color>>,
+    ) -> Option<Image> {
+        let size = size.into().unwrap_or_else(|| self.container_size().to_ceil());
+        let surface = Surface::new_raster_n32_premul(size)?;
+        Some(surface.render_to_image(background.into(), |canvas| {
+            self.render_scaled(canvas, size)
+        }))
+    }
+
+    /// Renders the SVG into a new texture-backed [Image].
+    ///
+    /// Same as [Self::render_to_image()], but the SVG is rendered on the GPU using `context`.
+    #[cfg(feature = "gpu")]
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "gpu")))]
+    pub fn render_to_texture_image(
+        &self,
+        context: &mut gpu::RecordingContext,
+        size: impl Into<Option<ISize>>,
+        background: impl Into<Option<Color>>,
+    ) -> Option<Image> {
+        let size = size.into().unwrap_or_else(|| self.container_size().to_ceil());
+        let surface = Surface::new_render_target(
+            context,
+            Budgeted::Yes,
+            &ImageInfo::new_n32_premul(size, None),
+            None,
+            gpu::SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )?;
+        Some(surface.render_to_image(background.into(), |canvas| {
+            self.render_scaled(canvas, size)
+        }))
+    }
+
+    fn render_scaled(&self, canvas: &mut crate::Canvas, size: ISize) {
+        let container_size = self.container_size();
+        if container_size.is_empty() {
+            return;
+        }
+        canvas.scale((
+            size.width as f32 / container_size.width,
+            size.height as f32 / container_size.height,
+        ));
+        self.render(canvas)
+    }
 }
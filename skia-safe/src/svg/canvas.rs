@@ -10,6 +10,7 @@ use std::ptr;
 pub struct Canvas {
     canvas: *mut SkCanvas,
     stream: Pin<Box<DynamicMemoryWStream>>,
+    decimal_precision: Option<usize>,
 }
 
 impl Drop for Canvas {
@@ -36,14 +37,21 @@ impl DerefMut for Canvas {
 
 bitflags! {
     #[derive(Default)]
+    /// Options that control how the SVG is generated.
     pub struct Flags : u32 {
+        /// Writes text as paths instead of `<text>` elements, so that the SVG renders the same
+        /// without the fonts that were used, at the cost of size and selectable text.
         const CONVERT_TEXT_TO_PATHS = sb::SkSVGCanvas_kConvertTextToPaths_Flag as _;
+        /// Writes the XML without indentation and line breaks, which makes it smaller.
         const NO_PRETTY_XML = sb::SkSVGCanvas_kNoPrettyXML_Flag as _;
     }
 }
 
 impl Canvas {
     /// Creates a new SVG canvas.
+    ///
+    /// Without [Flags::CONVERT_TEXT_TO_PATHS], text is written as `<text>` elements with the
+    /// `font-family` of the typeface, so that it stays editable.
     pub fn new(bounds: impl AsRef<Rect>, flags: impl Into<Option<Flags>>) -> Canvas {
         let bounds = bounds.as_ref();
         let flags = flags.into().unwrap_or_default();
@@ -55,7 +63,22 @@ impl Canvas {
                 flags.bits(),
             )
         };
-        Canvas { canvas, stream }
+        Canvas {
+            canvas,
+            stream,
+            decimal_precision: None,
+        }
+    }
+
+    /// Sets the maximum number of decimal places of the numbers in the attributes of the
+    /// generated SVG, for example of coordinates and transformation matrices.
+    ///
+    /// Skia writes numbers with up to 8 significant digits. Rounding them to fewer decimal
+    /// places makes the SVG smaller. `None`, which is the default, keeps the numbers as they
+    /// are. `font-family` and `href` attributes are never changed.
+    pub fn set_decimal_precision(&mut self, decimals: impl Into<Option<usize>>) -> &mut Self {
+        self.decimal_precision = decimals.into();
+        self
     }
 
     /// Ends the Canvas drawing and returns the resulting SVG.
@@ -68,12 +91,143 @@ impl Canvas {
             sb::C_SkCanvas_delete(self.canvas);
         }
         self.canvas = ptr::null_mut();
-        self.stream.detach_as_data()
+        let data = self.stream.detach_as_data();
+        match self.decimal_precision {
+            Some(decimals) => {
+                let svg = round_decimals(&String::from_utf8_lossy(data.as_bytes()), decimals);
+                Data::new_copy(svg.as_bytes())
+            }
+            None => data,
+        }
+    }
+}
+
+/// Rounds the numbers with a fractional part in the attribute values of `svg` to `decimals`
+/// places.
+fn round_decimals(svg: &str, decimals: usize) -> String {
+    let mut out = String::with_capacity(svg.len());
+    let mut rest = svg;
+    while let Some(tag_start) = rest.find('<') {
+        let (text, tag) = rest.split_at(tag_start);
+        out.push_str(text);
+        rest = round_decimals_in_tag(tag, decimals, &mut out);
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Copies the tag at the start of `tag` to `out`, rounds the numbers in its attribute values and
+/// returns the remaining text.
+fn round_decimals_in_tag<'a>(mut tag: &'a str, decimals: usize, out: &mut String) -> &'a str {
+    loop {
+        let end = match tag.find(|c| c == '>' || c == '"') {
+            Some(end) => end,
+            None => {
+                out.push_str(tag);
+                return "";
+            }
+        };
+        let (before, after) = tag.split_at(end + 1);
+        out.push_str(before);
+        if before.ends_with('>') {
+            return after;
+        }
+
+        let value_end = after.find('"').unwrap_or_else(|| after.len());
+        let (value, after) = after.split_at(value_end);
+        let name = before[..before.len() - 1]
+            .trim_end_matches(|c: char| c == '=' || c.is_whitespace())
+            .rsplit(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+        if name == "font-family" || name.ends_with("href") {
+            out.push_str(value);
+        } else {
+            round_decimals_in_value(value, decimals, out);
+        }
+        tag = after;
+        if let Some(stripped) = tag.strip_prefix('"') {
+            out.push('"');
+            tag = stripped;
+        }
+    }
+}
+
+fn round_decimals_in_value(value: &str, decimals: usize, out: &mut String) {
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i] == b'#' {
+            // Colors like `#1E5A00` and references like `url(#clip_0)` are not numbers.
+            i += 1;
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'-')
+            {
+                i += 1;
+            }
+            out.push_str(&value[start..i]);
+            continue;
+        }
+        if bytes[i] == b'-' {
+            i += 1;
+        }
+        let digits_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let mut has_fraction = false;
+        if i < bytes.len() && bytes[i] == b'.' {
+            let fraction_start = i + 1;
+            i = fraction_start;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            has_fraction = i > fraction_start;
+        }
+        if i == digits_start || (i == digits_start + 1 && bytes[digits_start] == b'.') {
+            // Not a number, copy the character.
+            let c = value[start..].chars().next().unwrap();
+            out.push(c);
+            i = start + c.len_utf8();
+            continue;
+        }
+        if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+            let mut j = i + 1;
+            if j < bytes.len() && (bytes[j] == b'-' || bytes[j] == b'+') {
+                j += 1;
+            }
+            let exponent_start = j;
+            while j < bytes.len() && bytes[j].is_ascii_digit() {
+                j += 1;
+            }
+            if j > exponent_start {
+                has_fraction = true;
+                i = j;
+            }
+        }
+
+        let number = &value[start..i];
+        match number.parse::<f64>() {
+            Ok(v) if has_fraction => {
+                let rounded = format!("{:.*}", decimals, v);
+                let rounded = if rounded.contains('.') {
+                    rounded.trim_end_matches('0').trim_end_matches('.')
+                } else {
+                    &rounded
+                };
+                out.push_str(if rounded == "-0" { "0" } else { rounded });
+            }
+            _ => out.push_str(number),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::Canvas;
+    use crate::Rect;
+
     #[test]
     fn test_svg() {
         use crate::Paint;
@@ -87,6 +241,52 @@ mod tests {
         assert!(contents.contains(r#"</svg>"#));
     }
 
+    #[test]
+    fn test_svg_decimal_precision() {
+        use crate::Paint;
+
+        let mut canvas = Canvas::new(&Rect::from_size((20, 20)), None);
+        canvas.set_decimal_precision(2);
+        let paint = Paint::default();
+        canvas.draw_circle((10.123_456, 10.5), 3.333_333, &paint);
+        let data = canvas.end();
+        let contents = String::from_utf8_lossy(data.as_bytes());
+        assert!(contents.contains(r#"<ellipse cx="10.12" cy="10.5" rx="3.33" ry="3.33"/>"#));
+    }
+
+    #[test]
+    fn test_round_decimals() {
+        let svg = r#"<path font-family="A 1.25" d="M1.2345 -0.001L2e-05 3.5E2" id="c1"/>1.2345"#;
+        assert_eq!(
+            super::round_decimals(svg, 2),
+            r#"<path font-family="A 1.25" d="M1.23 0L0 350" id="c1"/>1.2345"#
+        );
+    }
+
+    #[test]
+    fn test_svg_decimal_precision_keeps_colors() {
+        use crate::{Color, Paint};
+
+        let mut canvas = Canvas::new(&Rect::from_size((20, 20)), None);
+        canvas.set_decimal_precision(2);
+        let mut paint = Paint::default();
+        paint.set_color(Color::from_rgb(0x1E, 0x5A, 0));
+        canvas.draw_circle((10.123_456, 10.0), 3.0, &paint);
+        paint.set_color(Color::from_rgb(0x2E, 0x34, 0x40));
+        canvas.draw_circle((5.0, 5.0), 1.0, &paint);
+        let data = canvas.end();
+        let contents = String::from_utf8_lossy(data.as_bytes());
+        assert!(contents.contains(r##"fill="#1E5A00""##));
+        assert!(contents.contains(r##"fill="#2E3440""##));
+        assert!(contents.contains(r#"cx="10.12""#));
+
+        let svg = r##"<rect fill="#1E5A00" clip-path="url(#clip_1e5)" x="1.2345"/>"##;
+        assert_eq!(
+            super::round_decimals(svg, 2),
+            r##"<rect fill="#1E5A00" clip-path="url(#clip_1e5)" x="1.23"/>"##
+        );
+    }
+
     #[test]
     fn test_svg_without_ending() {
         use crate::Paint;
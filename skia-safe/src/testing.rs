@@ -0,0 +1,232 @@
+//! Utilities for rendering regression tests that compare rendered images against golden images.
+//!
+//! ```rust,ignore
+//! use skia_safe::testing::{compare_images, CompareOptions};
+//!
+//! let options = CompareOptions {
+//!     max_channel_delta: 2,
+//!     ..Default::default()
+//! };
+//! let comparison = compare_images(&rendered, &golden, &options).expect("sizes differ");
+//! assert!(comparison.matches(&options), "{:?}", comparison);
+//! ```
+
+use crate::{image::CachingHint, AlphaType, ColorType, Data, ISize, Image, ImageInfo, Pixmap};
+use std::fmt;
+
+/// Tolerances used to compare two images.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CompareOptions {
+    /// The maximum difference of a color channel for which two pixels are considered equal.
+    pub max_channel_delta: u8,
+    /// Ignore the alpha channel when comparing pixels.
+    pub ignore_alpha: bool,
+    /// The number of pixels that may differ by more than `max_channel_delta`.
+    pub max_differing_pixels: usize,
+    /// The minimum structural similarity (see [Comparison::similarity]) the images must have.
+    pub min_similarity: f64,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        CompareOptions {
+            max_channel_delta: 0,
+            ignore_alpha: false,
+            max_differing_pixels: 0,
+            min_similarity: 0.0,
+        }
+    }
+}
+
+/// The result of comparing two images.
+#[derive(Clone)]
+pub struct Comparison {
+    /// The number of pixels that differ by more than the `max_channel_delta` tolerance.
+    pub differing_pixels: usize,
+    /// The largest difference found in any channel of any pixel.
+    pub max_channel_delta: u8,
+    /// The mean structural similarity (SSIM) of the luminance of both images, computed over 8x8
+    /// pixel blocks. `1.0` means identical.
+    pub similarity: f64,
+    /// An image that shows the differing pixels in red over a faded version of the first image.
+    pub diff: Image,
+}
+
+impl fmt::Debug for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Comparison")
+            .field("differing_pixels", &self.differing_pixels)
+            .field("max_channel_delta", &self.max_channel_delta)
+            .field("similarity", &self.similarity)
+            .finish()
+    }
+}
+
+impl Comparison {
+    /// Returns `true` if the result is within the tolerances of `options`.
+    pub fn matches(&self, options: &CompareOptions) -> bool {
+        self.differing_pixels <= options.max_differing_pixels
+            && self.similarity >= options.min_similarity
+    }
+}
+
+/// Compares two raster or lazy images. Returns [None] if their dimensions differ or their pixels
+/// can not be read.
+pub fn compare_images(a: &Image, b: &Image, options: &CompareOptions) -> Option<Comparison> {
+    if a.dimensions() != b.dimensions() {
+        return None;
+    }
+    let dimensions = a.dimensions();
+    let a = read_rgba(dimensions, |info, pixels, row_bytes| {
+        a.read_pixels(info, pixels, row_bytes, (0, 0), CachingHint::Disallow)
+    })?;
+    let b = read_rgba(dimensions, |info, pixels, row_bytes| {
+        b.read_pixels(info, pixels, row_bytes, (0, 0), CachingHint::Disallow)
+    })?;
+    compare_rgba(dimensions, &a, &b, options)
+}
+
+/// Compares two pixmaps. Returns [None] if their dimensions differ or their pixels can not be
+/// converted.
+pub fn compare_pixmaps(a: &Pixmap, b: &Pixmap, options: &CompareOptions) -> Option<Comparison> {
+    let dimensions = ISize::new(a.width(), a.height());
+    if dimensions != ISize::new(b.width(), b.height()) {
+        return None;
+    }
+    let a = read_rgba(dimensions, |info, pixels, row_bytes| {
+        a.read_pixels(info, pixels, row_bytes, (0, 0))
+    })?;
+    let b = read_rgba(dimensions, |info, pixels, row_bytes| {
+        b.read_pixels(info, pixels, row_bytes, (0, 0))
+    })?;
+    compare_rgba(dimensions, &a, &b, options)
+}
+
+fn rgba_info(dimensions: ISize) -> ImageInfo {
+    ImageInfo::new(dimensions, ColorType::RGBA8888, AlphaType::Unpremul, None)
+}
+
+fn read_rgba(
+    dimensions: ISize,
+    read: impl FnOnce(&ImageInfo, &mut [u8], usize) -> bool,
+) -> Option<Vec<u8>> {
+    let info = rgba_info(dimensions);
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+    if read(&info, &mut pixels, row_bytes) {
+        Some(pixels)
+    } else {
+        None
+    }
+}
+
+fn compare_rgba(
+    dimensions: ISize,
+    a: &[u8],
+    b: &[u8],
+    options: &CompareOptions,
+) -> Option<Comparison> {
+    let channels = if options.ignore_alpha { 3 } else { 4 };
+    let mut differing_pixels = 0;
+    let mut max_channel_delta = 0;
+    let mut diff = Vec::with_capacity(a.len());
+
+    for (pa, pb) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+        let delta = pa[..channels]
+            .iter()
+            .zip(&pb[..channels])
+            .map(|(ca, cb)| (i16::from(*ca) - i16::from(*cb)).abs() as u8)
+            .max()
+            .unwrap_or(0);
+        max_channel_delta = max_channel_delta.max(delta);
+        if delta > options.max_channel_delta {
+            differing_pixels += 1;
+            diff.extend_from_slice(&[0xff, 0, 0, 0xff]);
+        } else {
+            let faded = 0xc0 + luminance(pa) as u8 / 4;
+            diff.extend_from_slice(&[faded, faded, faded, 0xff]);
+        }
+    }
+
+    let info = rgba_info(dimensions);
+    let diff = Image::from_raster_data(&info, Data::new_copy(&diff), info.min_row_bytes())?;
+
+    Some(Comparison {
+        differing_pixels,
+        max_channel_delta,
+        similarity: similarity(dimensions, a, b),
+        diff,
+    })
+}
+
+fn luminance(rgba: &[u8]) -> f64 {
+    0.2126 * f64::from(rgba[0]) + 0.7152 * f64::from(rgba[1]) + 0.0722 * f64::from(rgba[2])
+}
+
+/// Mean SSIM of the luminance over non-overlapping 8x8 blocks.
+fn similarity(dimensions: ISize, a: &[u8], b: &[u8]) -> f64 {
+    const BLOCK: usize = 8;
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+    let (width, height) = (dimensions.width as usize, dimensions.height as usize);
+    let mut sum = 0.0;
+    let mut blocks = 0;
+
+    for by in (0..height).step_by(BLOCK) {
+        for bx in (0..width).step_by(BLOCK) {
+            let mut values = Vec::with_capacity(BLOCK * BLOCK);
+            for y in by..(by + BLOCK).min(height) {
+                for x in bx..(bx + BLOCK).min(width) {
+                    let i = (y * width + x) * 4;
+                    values.push((luminance(&a[i..i + 4]), luminance(&b[i..i + 4])));
+                }
+            }
+            let n = values.len() as f64;
+            let mean_a = values.iter().map(|v| v.0).sum::<f64>() / n;
+            let mean_b = values.iter().map(|v| v.1).sum::<f64>() / n;
+            let (mut var_a, mut var_b, mut covar) = (0.0, 0.0, 0.0);
+            for (va, vb) in &values {
+                var_a += (va - mean_a) * (va - mean_a);
+                var_b += (vb - mean_b) * (vb - mean_b);
+                covar += (va - mean_a) * (vb - mean_b);
+            }
+            let (var_a, var_b, covar) = (var_a / n, var_b / n, covar / n);
+            sum += ((2.0 * mean_a * mean_b + C1) * (2.0 * covar + C2))
+                / ((mean_a * mean_a + mean_b * mean_b + C1) * (var_a + var_b + C2));
+            blocks += 1;
+        }
+    }
+
+    if blocks == 0 {
+        1.0
+    } else {
+        sum / f64::from(blocks)
+    }
+}
+
+#[test]
+fn test_compare_surfaces() {
+    use crate::{Color, Paint, Rect, Surface};
+
+    let mut a = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    let mut b = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    a.canvas().clear(Color::WHITE);
+    b.canvas().clear(Color::WHITE);
+
+    let options = CompareOptions::default();
+    let same = compare_images(&a.image_snapshot(), &b.image_snapshot(), &options).unwrap();
+    assert!(same.matches(&options));
+    assert_eq!(same.similarity, 1.0);
+
+    let mut paint = Paint::default();
+    paint.set_color(Color::BLACK);
+    b.canvas()
+        .draw_rect(Rect::from_xywh(0.0, 0.0, 2.0, 2.0), &paint);
+    let different = compare_images(&a.image_snapshot(), &b.image_snapshot(), &options).unwrap();
+    assert_eq!(different.differing_pixels, 4);
+    assert_eq!(different.max_channel_delta, 0xff);
+    assert!(different.similarity < 1.0);
+    assert!(!different.matches(&options));
+    assert_eq!(different.diff.dimensions(), (16, 16).into());
+}
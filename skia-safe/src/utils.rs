@@ -5,6 +5,14 @@ pub use _3d::*;
 mod camera;
 pub use camera::*;
 
+pub mod canvas2d;
+pub mod damage_tracker;
+pub mod damage_verifier;
+pub mod data_uri;
+pub mod debug_canvas;
+pub mod embedder_cache;
+pub mod glyph_atlas;
+
 mod custom_typeface;
 pub use custom_typeface::*;
 
@@ -17,3 +25,4 @@ pub use null_canvas::*;
 pub mod parse_path;
 pub mod shadow_utils;
 pub mod text_utils;
+pub mod transform_3d;
@@ -0,0 +1,512 @@
+//! A stateful drawing API modeled after the HTML Canvas 2D context, built on top of [Canvas],
+//! [Paint] and [Font].
+//!
+//! ```rust,no_run
+//! # use skia_safe::{utils::canvas2d::Context2D, Color, Surface};
+//! let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+//! let mut ctx = Context2D::new(surface.canvas());
+//! ctx.set_fill_style(Color::RED);
+//! ctx.fill_rect(10.0, 10.0, 80.0, 80.0);
+//! ctx.begin_path();
+//! ctx.arc(50.0, 50.0, 20.0, 0.0, std::f32::consts::PI * 2.0, false);
+//! ctx.stroke();
+//! ```
+//!
+//! Unlike in HTML, the current path is defined in the coordinate system that is active when it
+//! is filled or stroked, and not when its segments are added.
+
+use crate::utils::text_utils::{Align, Baseline};
+use crate::{
+    paint, scalar, BlendMode, Canvas, ClipOp, Color, Font, Matrix, Paint, Path, Rect, Shader,
+};
+
+/// A fill or stroke style.
+#[derive(Clone)]
+pub enum Style {
+    Color(Color),
+    Shader(Shader),
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Style::Color(color)
+    }
+}
+
+impl From<Shader> for Style {
+    fn from(shader: Shader) -> Self {
+        Style::Shader(shader)
+    }
+}
+
+/// The result of [Context2D::measure_text()].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TextMetrics {
+    pub width: scalar,
+    pub bounds: Rect,
+}
+
+#[derive(Clone)]
+struct State {
+    fill_style: Style,
+    stroke_style: Style,
+    line_width: scalar,
+    line_cap: paint::Cap,
+    line_join: paint::Join,
+    miter_limit: scalar,
+    global_alpha: scalar,
+    font: Font,
+    text_align: Align,
+    text_baseline: Baseline,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            fill_style: Color::BLACK.into(),
+            stroke_style: Color::BLACK.into(),
+            line_width: 1.0,
+            line_cap: paint::Cap::Butt,
+            line_join: paint::Join::Miter,
+            miter_limit: 10.0,
+            global_alpha: 1.0,
+            font: Font::default(),
+            text_align: Align::Left,
+            text_baseline: Baseline::default(),
+        }
+    }
+}
+
+/// A HTML Canvas 2D like drawing context that draws into a [Canvas].
+///
+/// The drawing state (styles, line and text attributes) is kept by the context, the transform
+/// and the clip are kept by the canvas. [Self::save()] and [Self::restore()] save and restore
+/// both.
+pub struct Context2D<'a> {
+    canvas: &'a mut Canvas,
+    state: State,
+    saved: Vec<State>,
+    path: Path,
+}
+
+impl<'a> Context2D<'a> {
+    pub fn new(canvas: &'a mut Canvas) -> Self {
+        Context2D {
+            canvas,
+            state: State::default(),
+            saved: Vec::new(),
+            path: Path::default(),
+        }
+    }
+
+    /// The canvas this context draws into.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.canvas
+    }
+
+    pub fn save(&mut self) -> &mut Self {
+        self.saved.push(self.state.clone());
+        self.canvas.save();
+        self
+    }
+
+    /// Restores the most recently saved state. Does nothing if there is no saved state.
+    pub fn restore(&mut self) -> &mut Self {
+        if let Some(state) = self.saved.pop() {
+            self.state = state;
+            self.canvas.restore();
+        }
+        self
+    }
+
+    pub fn fill_style(&self) -> &Style {
+        &self.state.fill_style
+    }
+
+    pub fn set_fill_style(&mut self, style: impl Into<Style>) -> &mut Self {
+        self.state.fill_style = style.into();
+        self
+    }
+
+    pub fn stroke_style(&self) -> &Style {
+        &self.state.stroke_style
+    }
+
+    pub fn set_stroke_style(&mut self, style: impl Into<Style>) -> &mut Self {
+        self.state.stroke_style = style.into();
+        self
+    }
+
+    pub fn line_width(&self) -> scalar {
+        self.state.line_width
+    }
+
+    pub fn set_line_width(&mut self, width: scalar) -> &mut Self {
+        self.state.line_width = width;
+        self
+    }
+
+    pub fn line_cap(&self) -> paint::Cap {
+        self.state.line_cap
+    }
+
+    pub fn set_line_cap(&mut self, cap: paint::Cap) -> &mut Self {
+        self.state.line_cap = cap;
+        self
+    }
+
+    pub fn line_join(&self) -> paint::Join {
+        self.state.line_join
+    }
+
+    pub fn set_line_join(&mut self, join: paint::Join) -> &mut Self {
+        self.state.line_join = join;
+        self
+    }
+
+    pub fn miter_limit(&self) -> scalar {
+        self.state.miter_limit
+    }
+
+    pub fn set_miter_limit(&mut self, limit: scalar) -> &mut Self {
+        self.state.miter_limit = limit;
+        self
+    }
+
+    pub fn global_alpha(&self) -> scalar {
+        self.state.global_alpha
+    }
+
+    /// Sets the alpha that is applied to everything that is drawn, clamped to `0.0..=1.0`.
+    pub fn set_global_alpha(&mut self, alpha: scalar) -> &mut Self {
+        self.state.global_alpha = alpha.max(0.0).min(1.0);
+        self
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.state.font
+    }
+
+    pub fn set_font(&mut self, font: Font) -> &mut Self {
+        self.state.font = font;
+        self
+    }
+
+    /// The horizontal alignment of text relative to the position given to
+    /// [Context2D::fill_text()].
+    pub fn text_align(&self) -> Align {
+        self.state.text_align
+    }
+
+    pub fn set_text_align(&mut self, align: Align) -> &mut Self {
+        self.state.text_align = align;
+        self
+    }
+
+    /// The vertical alignment of text relative to the position given to
+    /// [Context2D::fill_text()].
+    pub fn text_baseline(&self) -> Baseline {
+        self.state.text_baseline
+    }
+
+    pub fn set_text_baseline(&mut self, baseline: Baseline) -> &mut Self {
+        self.state.text_baseline = baseline;
+        self
+    }
+
+    pub fn translate(&mut self, x: scalar, y: scalar) -> &mut Self {
+        self.canvas.translate((x, y));
+        self
+    }
+
+    /// Rotates by `angle` radians.
+    pub fn rotate(&mut self, angle: scalar) -> &mut Self {
+        self.canvas.rotate(angle.to_degrees(), None);
+        self
+    }
+
+    pub fn scale(&mut self, x: scalar, y: scalar) -> &mut Self {
+        self.canvas.scale((x, y));
+        self
+    }
+
+    /// Multiplies the current transform with the matrix
+    /// `[a c e]`, `[b d f]`, `[0 0 1]`.
+    pub fn transform(
+        &mut self,
+        a: scalar,
+        b: scalar,
+        c: scalar,
+        d: scalar,
+        e: scalar,
+        f: scalar,
+    ) -> &mut Self {
+        self.canvas
+            .concat(&Matrix::new_all(a, c, e, b, d, f, 0.0, 0.0, 1.0));
+        self
+    }
+
+    /// Replaces the current transform with the matrix `[a c e]`, `[b d f]`, `[0 0 1]`.
+    pub fn set_transform(
+        &mut self,
+        a: scalar,
+        b: scalar,
+        c: scalar,
+        d: scalar,
+        e: scalar,
+        f: scalar,
+    ) -> &mut Self {
+        self.canvas.reset_matrix();
+        self.transform(a, b, c, d, e, f)
+    }
+
+    pub fn reset_transform(&mut self) -> &mut Self {
+        self.canvas.reset_matrix();
+        self
+    }
+
+    /// Starts a new, empty path.
+    pub fn begin_path(&mut self) -> &mut Self {
+        self.path.reset();
+        self
+    }
+
+    pub fn close_path(&mut self) -> &mut Self {
+        self.path.close();
+        self
+    }
+
+    pub fn move_to(&mut self, x: scalar, y: scalar) -> &mut Self {
+        self.path.move_to((x, y));
+        self
+    }
+
+    pub fn line_to(&mut self, x: scalar, y: scalar) -> &mut Self {
+        self.path.line_to((x, y));
+        self
+    }
+
+    pub fn quadratic_curve_to(
+        &mut self,
+        cpx: scalar,
+        cpy: scalar,
+        x: scalar,
+        y: scalar,
+    ) -> &mut Self {
+        self.path.quad_to((cpx, cpy), (x, y));
+        self
+    }
+
+    pub fn bezier_curve_to(
+        &mut self,
+        cp1x: scalar,
+        cp1y: scalar,
+        cp2x: scalar,
+        cp2y: scalar,
+        x: scalar,
+        y: scalar,
+    ) -> &mut Self {
+        self.path.cubic_to((cp1x, cp1y), (cp2x, cp2y), (x, y));
+        self
+    }
+
+    pub fn arc_to(
+        &mut self,
+        x1: scalar,
+        y1: scalar,
+        x2: scalar,
+        y2: scalar,
+        radius: scalar,
+    ) -> &mut Self {
+        self.path.arc_to_tangent((x1, y1), (x2, y2), radius);
+        self
+    }
+
+    /// Adds a circular arc centered at `(x, y)`. The angles are specified in radians and are
+    /// measured clockwise from the positive x axis.
+    pub fn arc(
+        &mut self,
+        x: scalar,
+        y: scalar,
+        radius: scalar,
+        start_angle: scalar,
+        end_angle: scalar,
+        counter_clockwise: bool,
+    ) -> &mut Self {
+        self.ellipse(
+            x,
+            y,
+            radius,
+            radius,
+            start_angle,
+            end_angle,
+            counter_clockwise,
+        )
+    }
+
+    /// Adds an elliptical arc centered at `(x, y)`. The angles are specified in radians and are
+    /// measured clockwise from the positive x axis.
+    #[allow(clippy::too_many_arguments)]
+    pub fn ellipse(
+        &mut self,
+        x: scalar,
+        y: scalar,
+        radius_x: scalar,
+        radius_y: scalar,
+        start_angle: scalar,
+        end_angle: scalar,
+        counter_clockwise: bool,
+    ) -> &mut Self {
+        use std::f32::consts::PI;
+        let oval = Rect::new(x - radius_x, y - radius_y, x + radius_x, y + radius_y);
+        const FULL: scalar = 2.0 * PI;
+        let sweep = match end_angle - start_angle {
+            s if !counter_clockwise && s >= FULL => FULL,
+            s if counter_clockwise && s <= -FULL => -FULL,
+            s if !counter_clockwise => s.rem_euclid(FULL),
+            s => -(-s).rem_euclid(FULL),
+        };
+        let start_degrees = start_angle.to_degrees();
+        let sweep_degrees = sweep.to_degrees();
+        if sweep_degrees.abs() >= 360.0 {
+            // Skia's arc_to() does not draw full ovals, split them into two halves.
+            let half = sweep_degrees / 2.0;
+            self.path.arc_to(oval, start_degrees, half, false);
+            self.path.arc_to(oval, start_degrees + half, half, false);
+        } else {
+            self.path.arc_to(oval, start_degrees, sweep_degrees, false);
+        }
+        self
+    }
+
+    pub fn rect(&mut self, x: scalar, y: scalar, width: scalar, height: scalar) -> &mut Self {
+        self.path
+            .add_rect(Rect::from_xywh(x, y, width, height), None);
+        self
+    }
+
+    /// Fills the current path with the fill style.
+    pub fn fill(&mut self) -> &mut Self {
+        let paint = self.fill_paint();
+        self.canvas.draw_path(&self.path, &paint);
+        self
+    }
+
+    /// Strokes the current path with the stroke style and the line attributes.
+    pub fn stroke(&mut self) -> &mut Self {
+        let paint = self.stroke_paint();
+        self.canvas.draw_path(&self.path, &paint);
+        self
+    }
+
+    /// Intersects the clip with the current path.
+    pub fn clip(&mut self) -> &mut Self {
+        self.canvas.clip_path(&self.path, ClipOp::Intersect, true);
+        self
+    }
+
+    pub fn fill_rect(&mut self, x: scalar, y: scalar, width: scalar, height: scalar) -> &mut Self {
+        let paint = self.fill_paint();
+        self.canvas
+            .draw_rect(Rect::from_xywh(x, y, width, height), &paint);
+        self
+    }
+
+    pub fn stroke_rect(
+        &mut self,
+        x: scalar,
+        y: scalar,
+        width: scalar,
+        height: scalar,
+    ) -> &mut Self {
+        let paint = self.stroke_paint();
+        self.canvas
+            .draw_rect(Rect::from_xywh(x, y, width, height), &paint);
+        self
+    }
+
+    /// Sets the pixels of the rectangle to transparent black.
+    pub fn clear_rect(&mut self, x: scalar, y: scalar, width: scalar, height: scalar) -> &mut Self {
+        let mut paint = Paint::default();
+        paint.set_blend_mode(BlendMode::Clear);
+        self.canvas
+            .draw_rect(Rect::from_xywh(x, y, width, height), &paint);
+        self
+    }
+
+    pub fn fill_text(&mut self, text: impl AsRef<str>, x: scalar, y: scalar) -> &mut Self {
+        let paint = self.fill_paint();
+        self.draw_text(text.as_ref(), x, y, &paint)
+    }
+
+    pub fn stroke_text(&mut self, text: impl AsRef<str>, x: scalar, y: scalar) -> &mut Self {
+        let paint = self.stroke_paint();
+        self.draw_text(text.as_ref(), x, y, &paint)
+    }
+
+    pub fn measure_text(&self, text: impl AsRef<str>) -> TextMetrics {
+        let (width, bounds) = self.state.font.measure_str(text, None);
+        TextMetrics { width, bounds }
+    }
+
+    fn draw_text(&mut self, text: &str, x: scalar, y: scalar, paint: &Paint) -> &mut Self {
+        self.canvas.draw_str_aligned(
+            text,
+            (x, y),
+            &self.state.font,
+            paint,
+            self.state.text_align,
+            self.state.text_baseline,
+        );
+        self
+    }
+
+    fn fill_paint(&self) -> Paint {
+        self.paint(&self.state.fill_style, paint::Style::Fill)
+    }
+
+    fn stroke_paint(&self) -> Paint {
+        let mut paint = self.paint(&self.state.stroke_style, paint::Style::Stroke);
+        paint
+            .set_stroke_width(self.state.line_width)
+            .set_stroke_cap(self.state.line_cap)
+            .set_stroke_join(self.state.line_join)
+            .set_stroke_miter(self.state.miter_limit);
+        paint
+    }
+
+    fn paint(&self, style: &Style, paint_style: paint::Style) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_style(paint_style);
+        let alpha = match style {
+            Style::Color(color) => {
+                paint.set_color(*color);
+                scalar::from(color.a()) / 255.0
+            }
+            Style::Shader(shader) => {
+                paint.set_shader(shader.clone());
+                1.0
+            }
+        };
+        paint.set_alpha_f(alpha * self.state.global_alpha);
+        paint
+    }
+}
+
+#[test]
+fn test_fill_and_stroke_paints() {
+    let mut surface = crate::Surface::new_raster_n32_premul((10, 10)).unwrap();
+    let mut ctx = Context2D::new(surface.canvas());
+    ctx.set_fill_style(Color::from_argb(0x80, 0xff, 0, 0))
+        .set_global_alpha(0.5)
+        .set_line_width(3.0);
+    let fill = ctx.fill_paint();
+    assert_eq!(fill.style(), paint::Style::Fill);
+    assert_eq!(fill.color().a(), 0x40);
+    let stroke = ctx.stroke_paint();
+    assert_eq!(stroke.style(), paint::Style::Stroke);
+    assert_eq!(stroke.stroke_width(), 3.0);
+
+    ctx.save().set_line_width(5.0).restore();
+    assert_eq!(ctx.line_width(), 3.0);
+}
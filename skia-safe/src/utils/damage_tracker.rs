@@ -0,0 +1,167 @@
+//! Tracks the areas of a canvas that change from frame to frame, so that only these need to be
+//! presented with partial swaps, for example with `EGL_KHR_partial_update`,
+//! `EGL_KHR_swap_buffers_with_damage` or Vulkan's `VK_KHR_incremental_present`.
+//!
+//! Draw through a [super::debug_canvas::DebugCanvas] and pass the recorded commands to
+//! [DamageTracker::add_commands()]:
+//!
+//! ```rust,ignore
+//! let mut canvas = DebugCanvas::new(surface.canvas());
+//! draw_frame(&mut canvas);
+//! tracker.add_commands(&canvas.take_commands());
+//! let damage = tracker.end_frame();
+//! ```
+
+use super::debug_canvas::{DrawCommand, DrawOp};
+use crate::{IRect, ISize, Rect, RoundOut};
+use std::collections::VecDeque;
+
+/// Accumulates the device space bounds of draw commands. See the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct DamageTracker {
+    bounds: IRect,
+    damage: Option<IRect>,
+    // the damage of the previous frames, most recent first.
+    history: VecDeque<Option<IRect>>,
+    max_history: usize,
+}
+
+impl DamageTracker {
+    /// Creates a tracker for a canvas of the given size that remembers the damage of up to
+    /// `max_history` frames for [Self::buffer_damage()].
+    pub fn new(size: impl Into<ISize>, max_history: usize) -> Self {
+        DamageTracker {
+            bounds: IRect::from_size(size),
+            damage: None,
+            history: VecDeque::new(),
+            max_history,
+        }
+    }
+
+    /// Changes the size of the canvas. Everything is damaged after a resize.
+    pub fn resize(&mut self, size: impl Into<ISize>) {
+        self.bounds = IRect::from_size(size);
+        self.history.clear();
+        self.add_all();
+    }
+
+    /// Adds an area in device space. The area is rounded out and outset by one pixel to cover
+    /// anti-aliased edges.
+    pub fn add_rect(&mut self, device_rect: impl AsRef<Rect>) {
+        let rect: IRect = device_rect.as_ref().round_out();
+        self.add_irect(&rect.with_outset((1, 1)));
+    }
+
+    /// Damages the whole canvas.
+    pub fn add_all(&mut self) {
+        let bounds = self.bounds;
+        self.add_irect(&bounds);
+    }
+
+    /// Adds the areas of the commands that were recorded by a
+    /// [super::debug_canvas::DebugCanvas]. Commands that draw without bounds, like clearing the
+    /// canvas, damage the whole canvas.
+    pub fn add_commands(&mut self, commands: &[DrawCommand]) {
+        for command in commands.iter().filter(|command| draws(command.op)) {
+            match &command.bounds {
+                Some(bounds) => self.add_rect(bounds),
+                None => self.add_all(),
+            }
+        }
+    }
+
+    /// The damage of the current frame, or [None] if nothing changed.
+    pub fn damage(&self) -> Option<IRect> {
+        self.damage
+    }
+
+    /// Finishes the current frame and returns its damage.
+    pub fn end_frame(&mut self) -> Option<IRect> {
+        let damage = self.damage.take();
+        self.history.push_front(damage);
+        self.history.truncate(self.max_history);
+        damage
+    }
+
+    /// Returns the area of a back buffer that needs to be redrawn in addition to the damage of the
+    /// current frame, given the buffer's age as reported by `EGL_EXT_buffer_age`, for example.
+    ///
+    /// The age of a buffer is the number of frames since its content was presented. An age of
+    /// `0` means the content is undefined, so the whole buffer needs to be redrawn, which is also
+    /// the case if the age exceeds the history.
+    pub fn buffer_damage(&self, age: usize) -> IRect {
+        if age == 0 || age > self.history.len() + 1 {
+            return self.bounds;
+        }
+        self.history
+            .iter()
+            .take(age - 1)
+            .flatten()
+            .fold(IRect::new_empty(), |damage, rect| {
+                IRect::join(&damage, rect)
+            })
+    }
+
+    /// Converts a rectangle to the bottom-left origin coordinates that EGL expects.
+    pub fn to_bottom_left_origin(&self, rect: &IRect) -> IRect {
+        let height = self.bounds.height();
+        IRect::new(
+            rect.left,
+            height - rect.bottom,
+            rect.right,
+            height - rect.top,
+        )
+    }
+
+    fn add_irect(&mut self, rect: &IRect) {
+        if let Some(rect) = IRect::intersect(rect, &self.bounds) {
+            self.damage = Some(match &self.damage {
+                Some(damage) => IRect::join(damage, &rect),
+                None => rect,
+            });
+        }
+    }
+}
+
+fn draws(op: DrawOp) -> bool {
+    use DrawOp::*;
+    match op {
+        Save | Restore | Translate | Scale | Rotate | Concat | ClipRect | ClipRRect | ClipPath => {
+            false
+        }
+        _ => true,
+    }
+}
+
+#[test]
+fn test_damage_tracking() {
+    use super::debug_canvas::DebugCanvas;
+    use crate::{Paint, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+    let mut tracker = DamageTracker::new((100, 100), 2);
+    let paint = Paint::default();
+
+    let mut canvas = DebugCanvas::new(surface.canvas());
+    canvas.translate((10.0, 10.0));
+    canvas.draw_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0), &paint);
+    tracker.add_commands(&canvas.take_commands());
+    assert_eq!(tracker.end_frame(), Some(IRect::new(9, 9, 21, 21)));
+
+    assert_eq!(tracker.end_frame(), None);
+
+    canvas.clear(crate::Color::WHITE);
+    tracker.add_commands(&canvas.take_commands());
+    assert_eq!(tracker.damage(), Some(IRect::new(0, 0, 100, 100)));
+
+    // the frame before the previous one had the rectangle drawn.
+    assert_eq!(tracker.buffer_damage(1), IRect::new_empty());
+    assert_eq!(tracker.buffer_damage(3), IRect::new(9, 9, 21, 21));
+    assert_eq!(tracker.buffer_damage(0), IRect::new(0, 0, 100, 100));
+    assert_eq!(tracker.buffer_damage(4), IRect::new(0, 0, 100, 100));
+
+    assert_eq!(
+        tracker.to_bottom_left_origin(&IRect::new(9, 9, 21, 21)),
+        IRect::new(9, 79, 21, 91)
+    );
+}
@@ -0,0 +1,179 @@
+//! Verifies a damage tracking implementation, like [super::damage_tracker::DamageTracker],
+//! against the pixels that actually change from frame to frame.
+//!
+//! Every frame is rendered completely into the current of two raster surfaces and compared
+//! with the previous frame tile by tile. The bounds of the changed pixels must be covered by
+//! the damage the implementation reports:
+//!
+//! ```rust,ignore
+//! let diff = verifier.draw_frame(|canvas| draw_frame(canvas));
+//! assert!(diff.missed_by(tracker.end_frame()).is_empty());
+//! ```
+//!
+//! This is a development tool, comparing the frames is too slow for production use.
+
+use crate::{Canvas, Color, Contains, IRect, ISize, Surface};
+use std::mem;
+
+/// Renders frames into two surfaces and reports the tiles that differ. See the
+/// [module](self) documentation.
+pub struct DamageVerifier {
+    previous: Surface,
+    current: Surface,
+    tile_size: ISize,
+}
+
+/// The pixels that changed between two frames, returned by [DamageVerifier::draw_frame()].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct FrameDiff {
+    /// The bounds of the changed pixels of each tile that has changed, ordered by rows of
+    /// tiles from top to bottom and left to right.
+    pub changed: Vec<IRect>,
+}
+
+impl FrameDiff {
+    /// Returns `true` if no pixel changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// The bounds of all changed pixels, or [None] if no pixel changed.
+    pub fn bounds(&self) -> Option<IRect> {
+        self.changed
+            .iter()
+            .fold(None, |bounds: Option<IRect>, rect| match bounds {
+                Some(bounds) => Some(IRect::join(&bounds, rect)),
+                None => Some(*rect),
+            })
+    }
+
+    /// Returns the changes that are not covered by `damage`. If this is not empty, the damage
+    /// tracking missed changes, and presenting only the damaged area would show stale pixels.
+    pub fn missed_by(&self, damage: Option<IRect>) -> Vec<IRect> {
+        self.changed
+            .iter()
+            .filter(|rect| match damage {
+                Some(damage) => !damage.contains(*rect),
+                None => true,
+            })
+            .copied()
+            .collect()
+    }
+}
+
+impl DamageVerifier {
+    /// Creates a verifier for frames of `size` that compares them in tiles of `tile_size`. The
+    /// frame before the first one is transparent.
+    pub fn new(size: impl Into<ISize>, tile_size: impl Into<ISize>) -> Option<Self> {
+        let size = size.into();
+        let tile_size = tile_size.into();
+        assert!(!tile_size.is_empty(), "The tile size must not be empty");
+        let mut previous = Surface::new_raster_n32_premul(size)?;
+        previous.canvas().clear(Color::TRANSPARENT);
+        let current = Surface::new_raster_n32_premul(size)?;
+        Some(DamageVerifier {
+            previous,
+            current,
+            tile_size,
+        })
+    }
+
+    /// Renders a frame with `draw`, which must draw the complete frame on a transparent
+    /// canvas, and returns how it differs from the previous frame.
+    pub fn draw_frame(&mut self, draw: impl FnOnce(&mut Canvas)) -> FrameDiff {
+        let canvas = self.current.canvas();
+        let save_count = canvas.save();
+        canvas.clear(Color::TRANSPARENT);
+        draw(canvas);
+        canvas.restore_to_count(save_count);
+
+        let (previous, _) = read_pixels(&mut self.previous);
+        let (current, row_bytes) = read_pixels(&mut self.current);
+        mem::swap(&mut self.previous, &mut self.current);
+
+        let bounds = IRect::from_size(self.previous.image_info().dimensions());
+        let mut changed = Vec::new();
+        for top in (0..bounds.bottom).step_by(self.tile_size.height as usize) {
+            for left in (0..bounds.right).step_by(self.tile_size.width as usize) {
+                let tile = IRect::new(
+                    left,
+                    top,
+                    (left + self.tile_size.width).min(bounds.right),
+                    (top + self.tile_size.height).min(bounds.bottom),
+                );
+                if let Some(rect) = changed_bounds(&previous, &current, row_bytes, &tile) {
+                    changed.push(rect);
+                }
+            }
+        }
+        FrameDiff { changed }
+    }
+
+    /// The last frame that was rendered.
+    pub fn last_frame(&mut self) -> &mut Surface {
+        &mut self.previous
+    }
+}
+
+const BYTES_PER_PIXEL: usize = 4;
+
+fn read_pixels(surface: &mut Surface) -> (Vec<u8>, usize) {
+    let info = surface.image_info();
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_min_byte_size()];
+    assert!(surface.read_pixels(&info, &mut pixels, row_bytes, (0, 0)));
+    (pixels, row_bytes)
+}
+
+fn changed_bounds(a: &[u8], b: &[u8], row_bytes: usize, tile: &IRect) -> Option<IRect> {
+    let mut bounds: Option<IRect> = None;
+    for y in tile.top..tile.bottom {
+        let row = y as usize * row_bytes;
+        for x in tile.left..tile.right {
+            let i = row + x as usize * BYTES_PER_PIXEL;
+            if a[i..i + BYTES_PER_PIXEL] != b[i..i + BYTES_PER_PIXEL] {
+                let pixel = IRect::new(x, y, x + 1, y + 1);
+                bounds = Some(match bounds {
+                    Some(bounds) => IRect::join(&bounds, &pixel),
+                    None => pixel,
+                });
+            }
+        }
+    }
+    bounds
+}
+
+#[test]
+fn test_damage_verification() {
+    use crate::{Paint, Rect};
+
+    let mut verifier = DamageVerifier::new((100, 100), (32, 32)).unwrap();
+    let paint = Paint::default();
+
+    let diff = verifier.draw_frame(|canvas| {
+        canvas.draw_rect(Rect::new(10.0, 10.0, 20.0, 20.0), &paint);
+    });
+    assert_eq!(diff.changed, vec![IRect::new(10, 10, 20, 20)]);
+
+    assert!(verifier
+        .draw_frame(|canvas| {
+            canvas.draw_rect(Rect::new(10.0, 10.0, 20.0, 20.0), &paint);
+        })
+        .is_empty());
+
+    // the rectangle moves across a tile boundary.
+    let diff = verifier.draw_frame(|canvas| {
+        canvas.draw_rect(Rect::new(30.0, 10.0, 40.0, 20.0), &paint);
+    });
+    assert_eq!(
+        diff.changed,
+        vec![IRect::new(10, 10, 32, 20), IRect::new(32, 10, 40, 20)]
+    );
+    assert_eq!(diff.bounds(), Some(IRect::new(10, 10, 40, 20)));
+    assert!(diff.missed_by(Some(IRect::new(9, 9, 41, 21))).is_empty());
+    assert_eq!(
+        diff.missed_by(Some(IRect::new(29, 9, 41, 21))),
+        vec![IRect::new(10, 10, 32, 20)]
+    );
+    assert_eq!(diff.missed_by(None).len(), 2);
+}
@@ -0,0 +1,154 @@
+//! Encodes and decodes `data:` URIs, which embed resources like images in SVG or HTML
+//! documents.
+//!
+//! ```rust,ignore
+//! let uri = data_uri::encode_image(&image).unwrap();
+//! write!(html, "<img src=\"{}\">", uri)?;
+//! ```
+
+use crate::{Data, EncodedImageFormat, Image};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns a `data:` URI with the base64 encoded `data` of `media_type`, for example
+/// `"image/png"`.
+pub fn encode(media_type: &str, data: &[u8]) -> String {
+    let mut uri = String::with_capacity(media_type.len() + 13 + (data.len() + 2) / 3 * 4);
+    uri.push_str("data:");
+    uri.push_str(media_type);
+    uri.push_str(";base64,");
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                uri.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                uri.push('=');
+            }
+        }
+    }
+    uri
+}
+
+/// Decodes a `data:` URI and returns its media type and data. The data can be base64 or
+/// percent encoded. The media type is empty if the URI does not specify one.
+///
+/// Returns [None] if `uri` is not a valid `data:` URI.
+pub fn decode(uri: &str) -> Option<(String, Data)> {
+    let uri = uri.trim();
+    if !uri.get(..5)?.eq_ignore_ascii_case("data:") {
+        return None;
+    }
+    let (header, payload) = uri[5..].split_at(uri[5..].find(',')?);
+    let payload = &payload[1..];
+    let (media_type, bytes) = match header.rfind(';') {
+        Some(i) if header[i + 1..].eq_ignore_ascii_case("base64") => {
+            (&header[..i], decode_base64(payload)?)
+        }
+        _ => (header, decode_percent(payload)?),
+    };
+    Some((media_type.to_owned(), Data::new_copy(&bytes)))
+}
+
+/// Encodes `image` as PNG and returns it as a `data:` URI. Returns [None] if the image can not
+/// be encoded, for example because it is texture-backed.
+pub fn encode_image(image: &Image) -> Option<String> {
+    let png = image.encode_to_data(EncodedImageFormat::PNG)?;
+    Some(encode("image/png", png.as_bytes()))
+}
+
+/// Decodes an image from a `data:` URI in any format Skia can decode, regardless of the media
+/// type the URI specifies.
+pub fn decode_image(uri: &str) -> Option<Image> {
+    let (_, data) = decode(uri)?;
+    Image::from_encoded(data)
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        if c == b'=' {
+            break;
+        }
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        n = (n << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+fn decode_percent(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+    while let Some(c) = iter.next() {
+        if c == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(c);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_round_trip() {
+        for (text, encoded) in &[
+            ("", "data:text/plain;base64,"),
+            ("f", "data:text/plain;base64,Zg=="),
+            ("fo", "data:text/plain;base64,Zm8="),
+            ("foo", "data:text/plain;base64,Zm9v"),
+            ("foobar", "data:text/plain;base64,Zm9vYmFy"),
+        ] {
+            assert_eq!(encode("text/plain", text.as_bytes()), *encoded);
+            let (media_type, data) = decode(encoded).unwrap();
+            assert_eq!(media_type, "text/plain");
+            assert_eq!(data.as_bytes(), text.as_bytes());
+        }
+    }
+
+    #[test]
+    fn decode_percent_encoded() {
+        let (media_type, data) = decode("data:,A%20brief%20note").unwrap();
+        assert_eq!(media_type, "");
+        assert_eq!(data.as_bytes(), b"A brief note");
+        assert!(decode("data:text/plain;base64,Zm9v!").is_none());
+        assert!(decode("http://example.com").is_none());
+    }
+
+    #[test]
+    fn image_round_trip() {
+        use crate::{Color, Surface};
+        let mut surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let uri = encode_image(&surface.image_snapshot()).unwrap();
+        assert!(uri.starts_with("data:image/png;base64,iVBORw0KGgo"));
+        let image = decode_image(&uri).unwrap();
+        assert_eq!(image.width(), 4);
+        assert_eq!(image.height(), 4);
+    }
+}
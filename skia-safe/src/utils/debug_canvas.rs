@@ -0,0 +1,378 @@
+//! A canvas wrapper that records the commands that are drawn through it.
+//!
+//! Each command is forwarded to the wrapped [Canvas] and recorded as a [DrawCommand] that
+//! describes the operation, the device space bounds it affects and a summary of the paint it
+//! was drawn with. This is useful to write tests that check what was drawn, or to visualize
+//! the draw commands in debugging overlays.
+
+use crate::{
+    canvas::SrcRectConstraint, paint, scalar, BlendMode, Canvas, ClipOp, Color, Color4f, Font,
+    Image, Matrix, Paint, Path, Point, RRect, Rect, TextBlob,
+};
+
+/// The type of a recorded [DrawCommand].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DrawOp {
+    Save,
+    Restore,
+    Translate,
+    Scale,
+    Rotate,
+    Concat,
+    ClipRect,
+    ClipRRect,
+    ClipPath,
+    DrawColor,
+    DrawPaint,
+    DrawPoint,
+    DrawLine,
+    DrawRect,
+    DrawOval,
+    DrawRRect,
+    DrawCircle,
+    DrawArc,
+    DrawPath,
+    DrawImage,
+    DrawImageRect,
+    DrawTextBlob,
+    DrawStr,
+}
+
+/// The attributes of the [Paint] a command was drawn with.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PaintSummary {
+    pub color: Color,
+    pub style: paint::Style,
+    pub stroke_width: scalar,
+    pub anti_alias: bool,
+    pub blend_mode: BlendMode,
+    pub has_shader: bool,
+}
+
+impl From<&Paint> for PaintSummary {
+    fn from(paint: &Paint) -> Self {
+        PaintSummary {
+            color: paint.color(),
+            style: paint.style(),
+            stroke_width: paint.stroke_width(),
+            anti_alias: paint.is_anti_alias(),
+            blend_mode: paint.blend_mode(),
+            has_shader: paint.shader().is_some(),
+        }
+    }
+}
+
+/// A command that was recorded by a [DebugCanvas].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DrawCommand {
+    pub op: DrawOp,
+    /// The device space bounds the command draws into or clips to. [None] for commands that
+    /// don't draw or that cover the whole canvas.
+    pub bounds: Option<Rect>,
+    pub paint: Option<PaintSummary>,
+}
+
+/// Wraps a [Canvas] and records all commands that are drawn through it.
+pub struct DebugCanvas<'a> {
+    canvas: &'a mut Canvas,
+    commands: Vec<DrawCommand>,
+}
+
+impl<'a> DebugCanvas<'a> {
+    pub fn new(canvas: &'a mut Canvas) -> Self {
+        DebugCanvas {
+            canvas,
+            commands: Vec::new(),
+        }
+    }
+
+    /// The wrapped canvas. Commands that are drawn directly into it are not recorded.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.canvas
+    }
+
+    /// The commands recorded so far.
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Removes and returns the commands recorded so far.
+    pub fn take_commands(&mut self) -> Vec<DrawCommand> {
+        std::mem::take(&mut self.commands)
+    }
+
+    pub fn save(&mut self) -> usize {
+        self.record(DrawOp::Save, None, None);
+        self.canvas.save()
+    }
+
+    pub fn restore(&mut self) -> &mut Self {
+        self.record(DrawOp::Restore, None, None);
+        self.canvas.restore();
+        self
+    }
+
+    pub fn translate(&mut self, d: impl Into<Point>) -> &mut Self {
+        self.record(DrawOp::Translate, None, None);
+        self.canvas.translate(d.into());
+        self
+    }
+
+    pub fn scale(&mut self, s: (scalar, scalar)) -> &mut Self {
+        self.record(DrawOp::Scale, None, None);
+        self.canvas.scale(s);
+        self
+    }
+
+    pub fn rotate(&mut self, degrees: scalar, point: Option<Point>) -> &mut Self {
+        self.record(DrawOp::Rotate, None, None);
+        self.canvas.rotate(degrees, point);
+        self
+    }
+
+    pub fn concat(&mut self, matrix: &Matrix) -> &mut Self {
+        self.record(DrawOp::Concat, None, None);
+        self.canvas.concat(matrix);
+        self
+    }
+
+    pub fn clip_rect(
+        &mut self,
+        rect: impl AsRef<Rect>,
+        op: impl Into<Option<ClipOp>>,
+        do_anti_alias: impl Into<Option<bool>>,
+    ) -> &mut Self {
+        let rect = rect.as_ref();
+        self.record(DrawOp::ClipRect, Some(rect), None);
+        self.canvas.clip_rect(rect, op, do_anti_alias);
+        self
+    }
+
+    pub fn clip_rrect(
+        &mut self,
+        rrect: impl AsRef<RRect>,
+        op: impl Into<Option<ClipOp>>,
+        do_anti_alias: impl Into<Option<bool>>,
+    ) -> &mut Self {
+        let rrect = rrect.as_ref();
+        self.record(DrawOp::ClipRRect, Some(rrect.rect()), None);
+        self.canvas.clip_rrect(rrect, op, do_anti_alias);
+        self
+    }
+
+    pub fn clip_path(
+        &mut self,
+        path: &Path,
+        op: impl Into<Option<ClipOp>>,
+        do_anti_alias: impl Into<Option<bool>>,
+    ) -> &mut Self {
+        self.record(DrawOp::ClipPath, Some(path.bounds()), None);
+        self.canvas.clip_path(path, op, do_anti_alias);
+        self
+    }
+
+    pub fn clear(&mut self, color: impl Into<Color4f>) -> &mut Self {
+        self.draw_color(color, BlendMode::Src)
+    }
+
+    pub fn draw_color(
+        &mut self,
+        color: impl Into<Color4f>,
+        mode: impl Into<Option<BlendMode>>,
+    ) -> &mut Self {
+        self.record(DrawOp::DrawColor, None, None);
+        self.canvas.draw_color(color, mode);
+        self
+    }
+
+    pub fn draw_paint(&mut self, paint: &Paint) -> &mut Self {
+        self.record(DrawOp::DrawPaint, None, Some(paint));
+        self.canvas.draw_paint(paint);
+        self
+    }
+
+    pub fn draw_point(&mut self, p: impl Into<Point>, paint: &Paint) -> &mut Self {
+        let p = p.into();
+        self.record(
+            DrawOp::DrawPoint,
+            Some(&Rect::new(p.x, p.y, p.x, p.y)),
+            Some(paint),
+        );
+        self.canvas.draw_point(p, paint);
+        self
+    }
+
+    pub fn draw_line(
+        &mut self,
+        p1: impl Into<Point>,
+        p2: impl Into<Point>,
+        paint: &Paint,
+    ) -> &mut Self {
+        let (p1, p2) = (p1.into(), p2.into());
+        let mut bounds = Rect::new(p1.x, p1.y, p2.x, p2.y);
+        bounds.sort();
+        self.record(DrawOp::DrawLine, Some(&bounds), Some(paint));
+        self.canvas.draw_line(p1, p2, paint);
+        self
+    }
+
+    pub fn draw_rect(&mut self, rect: impl AsRef<Rect>, paint: &Paint) -> &mut Self {
+        let rect = rect.as_ref();
+        self.record(DrawOp::DrawRect, Some(rect), Some(paint));
+        self.canvas.draw_rect(rect, paint);
+        self
+    }
+
+    pub fn draw_oval(&mut self, oval: impl AsRef<Rect>, paint: &Paint) -> &mut Self {
+        let oval = oval.as_ref();
+        self.record(DrawOp::DrawOval, Some(oval), Some(paint));
+        self.canvas.draw_oval(oval, paint);
+        self
+    }
+
+    pub fn draw_rrect(&mut self, rrect: impl AsRef<RRect>, paint: &Paint) -> &mut Self {
+        let rrect = rrect.as_ref();
+        self.record(DrawOp::DrawRRect, Some(rrect.rect()), Some(paint));
+        self.canvas.draw_rrect(rrect, paint);
+        self
+    }
+
+    pub fn draw_circle(
+        &mut self,
+        center: impl Into<Point>,
+        radius: scalar,
+        paint: &Paint,
+    ) -> &mut Self {
+        let center = center.into();
+        let bounds = Rect::new(
+            center.x - radius,
+            center.y - radius,
+            center.x + radius,
+            center.y + radius,
+        );
+        self.record(DrawOp::DrawCircle, Some(&bounds), Some(paint));
+        self.canvas.draw_circle(center, radius, paint);
+        self
+    }
+
+    pub fn draw_arc(
+        &mut self,
+        oval: impl AsRef<Rect>,
+        start_angle: scalar,
+        sweep_angle: scalar,
+        use_center: bool,
+        paint: &Paint,
+    ) -> &mut Self {
+        let oval = oval.as_ref();
+        self.record(DrawOp::DrawArc, Some(oval), Some(paint));
+        self.canvas
+            .draw_arc(oval, start_angle, sweep_angle, use_center, paint);
+        self
+    }
+
+    pub fn draw_path(&mut self, path: &Path, paint: &Paint) -> &mut Self {
+        self.record(DrawOp::DrawPath, Some(path.bounds()), Some(paint));
+        self.canvas.draw_path(path, paint);
+        self
+    }
+
+    pub fn draw_image(
+        &mut self,
+        image: impl AsRef<Image>,
+        left_top: impl Into<Point>,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let image = image.as_ref();
+        let left_top = left_top.into();
+        let bounds = Rect::from_point_and_size(left_top, image.dimensions());
+        self.record(DrawOp::DrawImage, Some(&bounds), paint);
+        self.canvas.draw_image(image, left_top, paint);
+        self
+    }
+
+    pub fn draw_image_rect(
+        &mut self,
+        image: impl AsRef<Image>,
+        src: Option<(&Rect, SrcRectConstraint)>,
+        dst: impl AsRef<Rect>,
+        paint: &Paint,
+    ) -> &mut Self {
+        let dst = dst.as_ref();
+        self.record(DrawOp::DrawImageRect, Some(dst), Some(paint));
+        self.canvas.draw_image_rect(image, src, dst, paint);
+        self
+    }
+
+    pub fn draw_text_blob(
+        &mut self,
+        blob: impl AsRef<TextBlob>,
+        origin: impl Into<Point>,
+        paint: &Paint,
+    ) -> &mut Self {
+        let blob = blob.as_ref();
+        let origin = origin.into();
+        let bounds = blob.bounds().with_offset(origin);
+        self.record(DrawOp::DrawTextBlob, Some(&bounds), Some(paint));
+        self.canvas.draw_text_blob(blob, origin, paint);
+        self
+    }
+
+    pub fn draw_str(
+        &mut self,
+        str: impl AsRef<str>,
+        origin: impl Into<Point>,
+        font: &Font,
+        paint: &Paint,
+    ) -> &mut Self {
+        let str = str.as_ref();
+        let origin = origin.into();
+        let (_, bounds) = font.measure_str(str, Some(paint));
+        self.record(
+            DrawOp::DrawStr,
+            Some(&bounds.with_offset(origin)),
+            Some(paint),
+        );
+        self.canvas.draw_str(str, origin, font, paint);
+        self
+    }
+
+    fn record(&mut self, op: DrawOp, local_bounds: Option<&Rect>, paint: Option<&Paint>) {
+        let bounds = local_bounds.map(|bounds| {
+            let bounds = match paint {
+                Some(paint) if paint.style() != paint::Style::Fill => {
+                    // hairlines are drawn one pixel wide.
+                    let outset = paint.stroke_width().max(1.0) / 2.0;
+                    bounds.with_outset((outset, outset))
+                }
+                _ => *bounds,
+            };
+            self.canvas.total_matrix().map_rect(bounds).0
+        });
+        self.commands.push(DrawCommand {
+            op,
+            bounds,
+            paint: paint.map(PaintSummary::from),
+        });
+    }
+}
+
+#[test]
+fn test_records_device_bounds() {
+    let mut surface = crate::Surface::new_raster_n32_premul((100, 100)).unwrap();
+    let mut canvas = DebugCanvas::new(surface.canvas());
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    canvas.translate((10.0, 20.0));
+    canvas.draw_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0), &paint);
+
+    let commands = canvas.commands();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[0].op, DrawOp::Translate);
+    assert_eq!(commands[0].bounds, None);
+    assert_eq!(commands[1].op, DrawOp::DrawRect);
+    assert_eq!(
+        commands[1].bounds,
+        Some(Rect::from_xywh(10.0, 20.0, 10.0, 10.0))
+    );
+    assert_eq!(commands[1].paint.unwrap().color, Color::RED);
+}
@@ -0,0 +1,157 @@
+//! A cache for resources that an embedder derives from [Image]s and [Picture]s, for example
+//! textures of another renderer, or tessellations.
+//!
+//! The entries are keyed by the unique ids of the sources. The cache keeps a reference to each
+//! source, so that an id is not reused while its entry exists. As soon as the application
+//! drops its last reference to a source, [EmbedderCache::purge()] removes the entry and
+//! returns the value, so that the dependent resources can be released:
+//!
+//! ```rust,ignore
+//! let texture = cache.get_or_insert_with(&image, |image| upload(image));
+//! // ... once per frame:
+//! for texture in cache.purge() {
+//!     release(texture);
+//! }
+//! ```
+//!
+//! A modified surface returns a snapshot with a new id, so a changed image always gets a new
+//! entry.
+
+use crate::prelude::*;
+use crate::{Image, Picture};
+use std::collections::HashMap;
+
+/// The objects an [EmbedderCache] can key entries by.
+pub trait CacheSource: Clone {
+    /// The id that identifies the content of the object, for example [Image::unique_id()].
+    fn cache_id(&self) -> u32;
+
+    /// Returns `true` if there are no other references to the object than this one.
+    fn is_unique(&self) -> bool;
+}
+
+impl CacheSource for Image {
+    fn cache_id(&self) -> u32 {
+        self.unique_id()
+    }
+
+    fn is_unique(&self) -> bool {
+        NativeRefCounted::unique(self.native())
+    }
+}
+
+impl CacheSource for Picture {
+    fn cache_id(&self) -> u32 {
+        self.unique_id()
+    }
+
+    fn is_unique(&self) -> bool {
+        NativeRefCounted::unique(self.native())
+    }
+}
+
+/// Maps [Image]s or [Picture]s to values of the embedder. See the [module](self)
+/// documentation.
+pub struct EmbedderCache<S: CacheSource, V> {
+    entries: HashMap<u32, (S, V)>,
+}
+
+impl<S: CacheSource, V> Default for EmbedderCache<S, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: CacheSource, V> EmbedderCache<S, V> {
+    pub fn new() -> Self {
+        EmbedderCache {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, source: &S) -> Option<&V> {
+        self.entries.get(&source.cache_id()).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, source: &S) -> Option<&mut V> {
+        self.entries
+            .get_mut(&source.cache_id())
+            .map(|(_, value)| value)
+    }
+
+    /// Inserts a value for `source` and returns the value it replaces.
+    pub fn insert(&mut self, source: &S, value: V) -> Option<V> {
+        self.entries
+            .insert(source.cache_id(), (source.clone(), value))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the value for `source`, and creates it with `create` if there is none.
+    pub fn get_or_insert_with(&mut self, source: &S, create: impl FnOnce(&S) -> V) -> &mut V {
+        &mut self
+            .entries
+            .entry(source.cache_id())
+            .or_insert_with(|| (source.clone(), create(source)))
+            .1
+    }
+
+    pub fn remove(&mut self, source: &S) -> Option<V> {
+        self.entries
+            .remove(&source.cache_id())
+            .map(|(_, value)| value)
+    }
+
+    /// Removes the entries of sources that are referenced only by the cache and returns their
+    /// values.
+    pub fn purge(&mut self) -> Vec<V> {
+        let unused: Vec<u32> = self
+            .entries
+            .iter()
+            .filter(|(_, (source, _))| source.is_unique())
+            .map(|(id, _)| *id)
+            .collect();
+        unused
+            .into_iter()
+            .filter_map(|id| self.entries.remove(&id))
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Removes all entries and returns their values.
+    pub fn clear(&mut self) -> Vec<V> {
+        self.entries.drain().map(|(_, (_, value))| value).collect()
+    }
+}
+
+#[test]
+fn test_purge_dropped_images() {
+    use crate::{Color, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+    let mut cache = EmbedderCache::new();
+
+    let first = surface.image_snapshot();
+    assert_eq!(*cache.get_or_insert_with(&first, |_| 1), 1);
+    assert_eq!(*cache.get_or_insert_with(&first, |_| 2), 1);
+
+    surface.canvas().clear(Color::RED);
+    let second = surface.image_snapshot();
+    assert_ne!(first.unique_id(), second.unique_id());
+    cache.insert(&second, 2);
+    assert_eq!(cache.len(), 2);
+
+    assert!(cache.purge().is_empty());
+    drop(first);
+    assert_eq!(cache.purge(), vec![1]);
+    assert_eq!(cache.get(&second), Some(&2));
+    assert_eq!(cache.clear(), vec![2]);
+}
@@ -0,0 +1,174 @@
+//! Rasterizes glyphs into a caller-managed atlas pixel buffer, for text renderers outside of
+//! Skia that want to use Skia's glyph rasterizer, including its support for color glyphs.
+
+use crate::{
+    Canvas, Color, Font, GlyphId, IPoint, IRect, ISize, ImageInfo, Paint, Point, Rect, TextBlob,
+    TextEncoding,
+};
+
+/// The place of a glyph in the atlas, see [GlyphAtlas::add_glyphs()].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AtlasGlyph {
+    pub glyph: GlyphId,
+    /// The pixels of the glyph in the atlas, without padding.
+    pub rect: IRect,
+    /// The position of the glyph's origin relative to the top left corner of [Self::rect].
+    /// To draw the glyph at a baseline position `p`, draw [Self::rect] at `p - origin`.
+    pub origin: IPoint,
+    /// [Self::rect] in normalized texture coordinates of the atlas.
+    pub uv: Rect,
+}
+
+/// Packs glyphs into rows of an atlas and keeps track of the space that is left.
+///
+/// The atlas pixels themselves are owned by the caller, who decides when it is uploaded to a
+/// texture. Glyphs are drawn with the color of the paint, which should be white for atlases
+/// with [crate::ColorType::Alpha8] masks. Color glyphs (e.g. emoji) keep their colors in
+/// atlases that have a color type with color channels.
+#[derive(Clone, Debug)]
+pub struct GlyphAtlas {
+    size: ISize,
+    padding: i32,
+    cursor: IPoint,
+    row_height: i32,
+}
+
+impl GlyphAtlas {
+    /// Creates a packer for an atlas of `size` that leaves `padding` transparent pixels
+    /// between the glyphs, so that they don't bleed into each other when the atlas is sampled
+    /// with filtering.
+    pub fn new(size: impl Into<ISize>, padding: i32) -> Self {
+        let padding = padding.max(0);
+        Self {
+            size: size.into(),
+            padding,
+            cursor: IPoint::new(padding, padding),
+            row_height: 0,
+        }
+    }
+
+    pub fn size(&self) -> ISize {
+        self.size
+    }
+
+    /// Forgets all glyphs, so that the atlas is filled from the top left corner again.
+    pub fn reset(&mut self) {
+        self.cursor = IPoint::new(self.padding, self.padding);
+        self.row_height = 0;
+    }
+
+    /// Rasterizes `glyphs` of `font` into the `atlas` pixels described by `atlas_info` and
+    /// `row_bytes`, which default to the minimum row bytes of `atlas_info`. `atlas_info` must
+    /// have the size of this atlas.
+    ///
+    /// Returns the places of the glyphs in the same order, [None] for glyphs that don't fit into
+    /// the atlas anymore. Glyphs without pixels, like spaces, are returned with an empty
+    /// [AtlasGlyph::rect].
+    pub fn add_glyphs(
+        &mut self,
+        atlas_info: &ImageInfo,
+        atlas: &mut [u8],
+        row_bytes: impl Into<Option<usize>>,
+        font: &Font,
+        glyphs: &[GlyphId],
+        paint: Option<&Paint>,
+    ) -> Vec<Option<AtlasGlyph>> {
+        assert_eq!(atlas_info.dimensions(), self.size);
+        let mut bounds = vec![Rect::default(); glyphs.len()];
+        font.get_bounds(glyphs, &mut bounds, paint);
+
+        let default_paint = Paint::default();
+        let paint = paint.unwrap_or(&default_paint);
+        let mut canvas = match Canvas::from_raster_direct(atlas_info, atlas, row_bytes, None) {
+            Some(canvas) => canvas,
+            None => return vec![None; glyphs.len()],
+        };
+
+        glyphs
+            .iter()
+            .zip(bounds)
+            .map(|(&glyph, bounds)| {
+                // one more pixel on each side for antialiasing.
+                let bounds = IRect::new(
+                    bounds.left.floor() as i32 - 1,
+                    bounds.top.floor() as i32 - 1,
+                    bounds.right.ceil() as i32 + 1,
+                    bounds.bottom.ceil() as i32 + 1,
+                );
+                let origin = IPoint::new(-bounds.left, -bounds.top);
+                if bounds.width() <= 2 || bounds.height() <= 2 {
+                    return Some(self.glyph(glyph, IRect::new_empty(), origin));
+                }
+                let rect = self.allocate(bounds.size())?;
+                let blob = TextBlob::from_text(&glyph.to_ne_bytes(), TextEncoding::GlyphId, font)?;
+                canvas.save();
+                canvas.clip_rect(Rect::from(rect), None, None);
+                canvas.clear(Color::TRANSPARENT);
+                let baseline =
+                    Point::new((rect.left + origin.x) as f32, (rect.top + origin.y) as f32);
+                canvas.draw_text_blob(blob, baseline, paint);
+                canvas.restore();
+                Some(self.glyph(glyph, rect, origin))
+            })
+            .collect()
+    }
+
+    fn allocate(&mut self, size: ISize) -> Option<IRect> {
+        if self.cursor.x + size.width + self.padding > self.size.width {
+            self.cursor = IPoint::new(self.padding, self.cursor.y + self.row_height + self.padding);
+            self.row_height = 0;
+        }
+        if self.cursor.x + size.width + self.padding > self.size.width
+            || self.cursor.y + size.height + self.padding > self.size.height
+        {
+            return None;
+        }
+        let rect = IRect::from_xywh(self.cursor.x, self.cursor.y, size.width, size.height);
+        self.cursor.x += size.width + self.padding;
+        self.row_height = self.row_height.max(size.height);
+        Some(rect)
+    }
+
+    fn glyph(&self, glyph: GlyphId, rect: IRect, origin: IPoint) -> AtlasGlyph {
+        let (width, height) = (self.size.width as f32, self.size.height as f32);
+        AtlasGlyph {
+            glyph,
+            rect,
+            origin,
+            uv: Rect::new(
+                rect.left as f32 / width,
+                rect.top as f32 / height,
+                rect.right as f32 / width,
+                rect.bottom as f32 / height,
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_glyph_atlas() {
+    use crate::{AlphaType, ColorType, Pixmap, Typeface};
+
+    let font = Font::new(Typeface::default(), 24.0);
+    let glyphs = font.str_to_glyphs_vec("AB A");
+    let info = ImageInfo::new((48, 32), ColorType::Alpha8, AlphaType::Premul, None);
+    let mut pixels = vec![0u8; info.compute_min_byte_size()];
+    let mut packer = GlyphAtlas::new(info.dimensions(), 1);
+
+    let placed = packer.add_glyphs(&info, &mut pixels, None, &font, &glyphs, None);
+    assert_eq!(placed.len(), 4);
+    let a = placed[0].unwrap();
+    let b = placed[1].unwrap();
+    let space = placed[2].unwrap();
+    assert!(!a.rect.is_empty());
+    assert!(!IRect::intersects(&a.rect, &b.rect));
+    assert!(space.rect.is_empty());
+    assert!(a.uv.left >= 0.0 && a.uv.right <= 1.0);
+    // the second "A" does not fit into the atlas anymore.
+    assert!(placed[3].is_none());
+
+    let atlas = Pixmap::new(&info, &pixels, info.min_row_bytes());
+    let center_y = a.rect.top + a.rect.height() / 2;
+    assert!((a.rect.left..a.rect.right).any(|x| atlas.get_alpha_f((x, center_y)) > 0.0));
+    assert_eq!(atlas.get_alpha_f((47, 31)), 0.0);
+}
@@ -1,5 +1,8 @@
 use crate::prelude::*;
-use crate::{Canvas, Font, Paint, Path, Point, TextEncoding};
+use crate::{
+    scalar, Canvas, ContourMeasure, ContourMeasureIter, Font, FontMetrics, Paint, Path, Point,
+    RSXform, TextBlob, TextBlobBuilder, TextEncoding, Vector,
+};
 use core::borrow::BorrowMut;
 use skia_bindings::SkTextUtils;
 
@@ -48,6 +51,77 @@ impl Canvas {
     }
 }
 
+/// The vertical position of the text relative to the point it is drawn at, see
+/// [draw_str_aligned()].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Baseline {
+    /// The point is on the top of the font's ascent.
+    Top,
+    /// The point is halfway between the font's ascent and descent.
+    Middle,
+    /// The point is on the alphabetic baseline, which is how text is drawn by default.
+    Alphabetic,
+    /// The point is on the bottom of the font's descent.
+    Bottom,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Baseline::Alphabetic
+    }
+}
+
+impl Baseline {
+    /// Returns the vertical distance from the point text is drawn at to the alphabetic
+    /// baseline.
+    pub fn offset(self, metrics: &FontMetrics) -> scalar {
+        match self {
+            Baseline::Top => -metrics.ascent,
+            Baseline::Middle => -(metrics.ascent + metrics.descent) / 2.0,
+            Baseline::Alphabetic => 0.0,
+            Baseline::Bottom => -metrics.descent,
+        }
+    }
+}
+
+/// Draws `text` with its horizontal position relative to `p` defined by `align` and its vertical
+/// position defined by `baseline`, which is computed from the [FontMetrics] of `font`.
+///
+/// This draws a label centered in a rectangle with a single call:
+///
+/// ```rust,ignore
+/// draw_str_aligned(canvas, "OK", rect.center(), &font, &paint, Align::Center, Baseline::Middle);
+/// ```
+pub fn draw_str_aligned(
+    canvas: &mut Canvas,
+    text: impl AsRef<str>,
+    p: impl Into<Point>,
+    font: &Font,
+    paint: &Paint,
+    align: Align,
+    baseline: Baseline,
+) {
+    let p = p.into();
+    let (_, metrics) = font.metrics();
+    let y = p.y + baseline.offset(&metrics);
+    draw_str(canvas, text, (p.x, y), font, paint, align);
+}
+
+impl Canvas {
+    pub fn draw_str_aligned(
+        &mut self,
+        text: impl AsRef<str>,
+        p: impl Into<Point>,
+        font: &Font,
+        paint: &Paint,
+        align: Align,
+        baseline: Baseline,
+    ) -> &mut Self {
+        draw_str_aligned(self.borrow_mut(), text, p, font, paint, align, baseline);
+        self
+    }
+}
+
 pub fn get_path(text: impl AsRef<str>, p: impl Into<Point>, font: &Font) -> Path {
     let text = text.as_ref().as_bytes();
     let p = p.into();
@@ -71,3 +145,139 @@ impl Path {
         get_path(text, p, font)
     }
 }
+
+/// Lays out `text` along `path` and returns a [TextBlob] that contains one [RSXform] per glyph.
+///
+/// Each glyph is centered on the point of the path at its horizontal position and rotated along
+/// the path's tangent at that point. `offset.x` moves the text along the path, `offset.y`
+/// moves it perpendicular to the path, with positive values moving it in the direction of the
+/// glyph's descent. `align` positions the text relative to the length of all contours of the
+/// path combined.
+///
+/// Glyphs that do not fit on the path are omitted. Returns [None] if no glyph fits.
+pub fn text_blob_on_path(
+    text: impl AsRef<str>,
+    path: &Path,
+    offset: impl Into<Vector>,
+    font: &Font,
+    align: Align,
+) -> Option<TextBlob> {
+    let offset = offset.into();
+    let glyphs = font.str_to_glyphs_vec(text);
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    let mut widths = vec![0.0; glyphs.len()];
+    font.get_widths(&glyphs, &mut widths);
+    let mut xpos = vec![0.0; glyphs.len()];
+    font.get_x_pos(&glyphs, &mut xpos, None);
+
+    let contours: Vec<ContourMeasure> = ContourMeasureIter::new(path, false, None).collect();
+    let path_length: scalar = contours.iter().map(|c| c.length()).sum();
+    let text_width = xpos[xpos.len() - 1] + widths[widths.len() - 1];
+
+    let start = offset.x
+        + match align {
+            Align::Left => 0.0,
+            Align::Center => (path_length - text_width) / 2.0,
+            Align::Right => path_length - text_width,
+        };
+
+    let placed: Vec<_> = glyphs
+        .iter()
+        .zip(xpos.iter().zip(widths.iter()))
+        .filter_map(|(glyph, (x, width))| {
+            let half_width = width / 2.0;
+            let (p, t) = pos_tan_on_contours(&contours, start + x + half_width)?;
+            let xform = RSXform::new(
+                t.x,
+                t.y,
+                (
+                    p.x - t.x * half_width - t.y * offset.y,
+                    p.y - t.y * half_width + t.x * offset.y,
+                ),
+            );
+            Some((*glyph, xform))
+        })
+        .collect();
+
+    if placed.is_empty() {
+        return None;
+    }
+
+    let mut builder = TextBlobBuilder::new();
+    let (run_glyphs, run_xforms) = builder.alloc_run_rsxform(font, placed.len());
+    for (i, (glyph, xform)) in placed.into_iter().enumerate() {
+        run_glyphs[i] = glyph;
+        run_xforms[i] = xform;
+    }
+    builder.make()
+}
+
+/// Returns the position and tangent at `distance` measured over all `contours`.
+fn pos_tan_on_contours(contours: &[ContourMeasure], distance: scalar) -> Option<(Point, Vector)> {
+    if distance < 0.0 {
+        return None;
+    }
+    let mut distance = distance;
+    for contour in contours {
+        let length = contour.length();
+        if distance <= length {
+            return contour.pos_tan(distance);
+        }
+        distance -= length;
+    }
+    None
+}
+
+/// Draws `text` along `path`, see [text_blob_on_path()].
+pub fn draw_text_on_path(
+    canvas: &mut Canvas,
+    text: impl AsRef<str>,
+    path: &Path,
+    offset: impl Into<Vector>,
+    font: &Font,
+    paint: &Paint,
+    align: Align,
+) {
+    if let Some(blob) = text_blob_on_path(text, path, offset, font, align) {
+        canvas.draw_text_blob(&blob, Point::default(), paint);
+    }
+}
+
+impl Canvas {
+    pub fn draw_text_on_path(
+        &mut self,
+        text: impl AsRef<str>,
+        path: &Path,
+        offset: impl Into<Vector>,
+        font: &Font,
+        paint: &Paint,
+        align: Align,
+    ) -> &mut Self {
+        draw_text_on_path(self.borrow_mut(), text, path, offset, font, paint, align);
+        self
+    }
+}
+
+#[test]
+fn test_text_blob_on_path() {
+    let font = Font::default();
+    let path = Path::line((0, 0), (1000, 0));
+    assert!(text_blob_on_path("", &path, (0, 0), &font, Align::Left).is_none());
+    assert!(text_blob_on_path("Skia", &path, (0, 0), &font, Align::Center).is_some());
+    assert!(text_blob_on_path("Skia", &path, (2000, 0), &font, Align::Left).is_none());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_baseline_offset() {
+    let (_, metrics) = Font::default().metrics();
+    assert!(metrics.ascent < 0.0 && metrics.descent > 0.0);
+    assert_eq!(Baseline::Alphabetic.offset(&metrics), 0.0);
+    assert_eq!(Baseline::Top.offset(&metrics), -metrics.ascent);
+    assert_eq!(Baseline::Bottom.offset(&metrics), -metrics.descent);
+    let middle = Baseline::Middle.offset(&metrics);
+    assert!(middle > Baseline::Bottom.offset(&metrics) && middle < Baseline::Top.offset(&metrics));
+}
@@ -0,0 +1,183 @@
+//! A replacement for [super::View3D] (`Sk3DView`) that is based on [M44].
+//!
+//! Like `Sk3DView`, a [Transform3D] rotates and translates content in 3D and projects it back
+//! onto the canvas with a camera that looks at the `z = 0` plane from a distance, so that
+//! content at `z = 0` keeps its size. Positive `z` values are farther away from the viewer.
+//!
+//! ```rust,ignore
+//! let mut transform = Transform3D::new();
+//! transform.rotate_y(30.0);
+//! canvas.save();
+//! canvas.concat_44(&transform.matrix_around(card.center()));
+//! draw_card(canvas);
+//! canvas.restore();
+//! ```
+
+use crate::{scalar, Canvas, Point, M44, V3};
+
+/// Builds a 3D transformation with a perspective projection. See the [module](self)
+/// documentation.
+///
+/// The transformations are applied in the same order as those of a [Canvas]: the one that was
+/// added last is applied to the content first.
+#[derive(Clone)]
+pub struct Transform3D {
+    matrix: M44,
+    saved: Vec<M44>,
+    camera_distance: scalar,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transform3D {
+    /// The default distance of the camera from the `z = 0` plane, which is the same as the
+    /// one of `Sk3DView`: 8 inches at 72 pixels per inch.
+    pub const DEFAULT_CAMERA_DISTANCE: scalar = 576.0;
+
+    /// Creates an identity transformation.
+    pub fn new() -> Self {
+        Self {
+            matrix: M44::new_identity(),
+            saved: Vec::new(),
+            camera_distance: Self::DEFAULT_CAMERA_DISTANCE,
+        }
+    }
+
+    /// Saves the current transformation, so that it can be restored with [Self::restore()].
+    pub fn save(&mut self) -> &mut Self {
+        self.saved.push(self.matrix.clone());
+        self
+    }
+
+    /// Restores the transformation that was saved last. Does nothing if there is none.
+    pub fn restore(&mut self) -> &mut Self {
+        if let Some(matrix) = self.saved.pop() {
+            self.matrix = matrix;
+        }
+        self
+    }
+
+    /// Translates by `d`. Positive `z` values move the content away from the viewer.
+    pub fn translate(&mut self, d: impl Into<V3>) -> &mut Self {
+        let d = d.into();
+        self.matrix.pre_concat(&M44::translate(d.x, d.y, d.z));
+        self
+    }
+
+    /// Rotates around the x axis. Positive angles turn the bottom edge away from the viewer.
+    pub fn rotate_x(&mut self, degrees: scalar) -> &mut Self {
+        self.rotate(V3::new(1.0, 0.0, 0.0), degrees)
+    }
+
+    /// Rotates around the y axis. Positive angles turn the right edge away from the viewer.
+    pub fn rotate_y(&mut self, degrees: scalar) -> &mut Self {
+        self.rotate(V3::new(0.0, -1.0, 0.0), degrees)
+    }
+
+    /// Rotates around the z axis. Positive angles rotate clockwise, like
+    /// [Canvas::rotate()].
+    pub fn rotate_z(&mut self, degrees: scalar) -> &mut Self {
+        self.rotate(V3::new(0.0, 0.0, 1.0), degrees)
+    }
+
+    fn rotate(&mut self, axis: V3, degrees: scalar) -> &mut Self {
+        self.matrix
+            .pre_concat(&M44::rotate(axis, degrees.to_radians()));
+        self
+    }
+
+    /// The distance of the camera from the `z = 0` plane, in pixels. Smaller distances
+    /// exaggerate the perspective.
+    pub fn camera_distance(&self) -> scalar {
+        self.camera_distance
+    }
+
+    /// Sets the distance of the camera from the `z = 0` plane, which must be positive. See
+    /// [Self::camera_distance()].
+    pub fn set_camera_distance(&mut self, distance: scalar) -> &mut Self {
+        assert!(distance > 0.0, "The camera distance must be positive");
+        self.camera_distance = distance;
+        self
+    }
+
+    /// Returns the transformation including the perspective projection, which transforms
+    /// around the origin of the canvas.
+    pub fn matrix(&self) -> M44 {
+        #[rustfmt::skip]
+        let projection = M44::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0 / self.camera_distance, 1.0,
+        );
+        M44::concat(&projection, &self.matrix)
+    }
+
+    /// Returns the transformation including the perspective projection, which transforms
+    /// around `pivot`, for example the center of the content.
+    pub fn matrix_around(&self, pivot: impl Into<Point>) -> M44 {
+        let pivot = pivot.into();
+        let to_pivot = M44::translate(pivot.x, pivot.y, 0.0);
+        let from_pivot = M44::translate(-pivot.x, -pivot.y, 0.0);
+        M44::concat(&M44::concat(&to_pivot, &self.matrix()), &from_pivot)
+    }
+
+    /// Concatenates [Self::matrix()] to the current matrix of `canvas`.
+    pub fn apply_to_canvas(&self, canvas: &mut Canvas) -> &Self {
+        canvas.concat_44(&self.matrix());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform3D;
+    use crate::V3;
+
+    fn project(transform: &Transform3D, (x, y): (f32, f32)) -> (f32, f32) {
+        let v = transform.matrix().map(x, y, 0.0, 1.0);
+        (v.x / v.w, v.y / v.w)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn translate_away_halves_the_size() {
+        let mut transform = Transform3D::new();
+        assert_eq!(project(&transform, (100.0, 50.0)), (100.0, 50.0));
+        transform.save();
+        transform.translate(V3::new(0.0, 0.0, Transform3D::DEFAULT_CAMERA_DISTANCE));
+        assert_eq!(project(&transform, (100.0, 50.0)), (50.0, 25.0));
+        transform.restore();
+        assert_eq!(project(&transform, (100.0, 50.0)), (100.0, 50.0));
+    }
+
+    #[test]
+    fn rotations() {
+        let mut transform = Transform3D::new();
+        transform.rotate_y(60.0);
+        // the right edge turns away from the viewer and gets smaller.
+        let (x, _) = project(&transform, (100.0, 0.0));
+        assert!((x - 50.0 / (1.0 + 86.602_54 / 576.0)).abs() < 0.01);
+        let (x, _) = project(&transform, (-100.0, 0.0));
+        assert!(x < -50.0);
+
+        let mut transform = Transform3D::new();
+        transform.rotate_z(90.0);
+        let (x, y) = project(&transform, (1.0, 0.0));
+        assert!(x.abs() < 1e-6 && (y - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn matrix_around_keeps_the_pivot() {
+        let mut transform = Transform3D::new();
+        transform.rotate_x(45.0).rotate_y(30.0);
+        let v = transform
+            .matrix_around((20.0, 30.0))
+            .map(20.0, 30.0, 0.0, 1.0);
+        assert!((v.x / v.w - 20.0).abs() < 1e-4 && (v.y / v.w - 30.0).abs() < 1e-4);
+    }
+}
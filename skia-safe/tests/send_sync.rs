@@ -132,11 +132,13 @@ mod effects {
 #[cfg(feature = "gpu")]
 mod gpu {
     use skia_safe::gpu::{
-        BackendFormat, BackendRenderTarget, BackendSurfaceMutableState, BackendTexture, Context,
-        ContextOptions, DirectContext, DriverBugWorkarounds, RecordingContext,
+        BackendFormat, BackendRenderTarget, BackendSemaphore, BackendSurfaceMutableState,
+        BackendTexture, Context, ContextOptions, DirectContext, DriverBugWorkarounds,
+        RecordingContext,
     };
     use static_assertions::*;
     assert_impl_all!(BackendFormat: Send, Sync);
+    assert_impl_all!(BackendSemaphore: Send, Sync);
     assert_impl_all!(BackendTexture: Send, Sync);
     assert_impl_all!(BackendRenderTarget: Send, Sync);
     assert_impl_all!(BackendSurfaceMutableState: Send, Sync);
@@ -162,9 +164,13 @@ mod gpu {
 
     #[cfg(feature = "metal")]
     mod mtl {
-        use skia_safe::gpu::mtl::TextureInfo;
+        use skia_safe::gpu::mtl::{CommandQueue, Device, Texture, TextureInfo};
         use static_assertions::*;
         assert_impl_all!(TextureInfo: Send, Sync);
+        // non-owning handles to Metal objects.
+        assert_not_impl_any!(Device: Send, Sync);
+        assert_not_impl_any!(CommandQueue: Send, Sync);
+        assert_not_impl_any!(Texture: Send, Sync);
     }
 
     #[cfg(feature = "vulkan")]